@@ -0,0 +1,31 @@
+use rqlite_rs::prelude::*;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let client = RqliteClientBuilder::new()
+        .known_host("localhost:4001")
+        .build()?;
+
+    let migrator = rqlite_rs::migrate!("./migrations");
+
+    // Report what would be applied without touching the database
+    let report = migrator.clone().dry_run(true).run(&client).await?;
+
+    for migration in &report.migrations {
+        println!(
+            "Pending migration {}: {} ({} statements)",
+            migration.version, migration.description, migration.statements
+        );
+    }
+
+    let report = migrator.run(&client).await?;
+
+    println!("Applied {} migrations", report.migrations.len());
+
+    // Revert everything again
+    migrator.undo(&client, 0).await?;
+
+    println!("Successfully cleaned up");
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//! Compares decoding a large result set the way rows used to be decoded, by cloning every
+//! `serde_json::Value` before deserializing it, with decoding borrowed values from a `Row`.
+//!
+//! Run with `cargo bench -p rqlite-rs-core --bench row_decode`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::HashMap,
+    hint::black_box,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use rqlite_rs_core::{Column, Row, SqlValue};
+use serde_json::Value;
+
+const ROWS: usize = 100_000;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+// SAFETY: all allocations are delegated to the system allocator, only counters are added
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::alloc`
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::dealloc`
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+struct Measurement {
+    allocations: usize,
+    bytes: usize,
+    elapsed: Duration,
+}
+
+fn measure(f: impl FnOnce()) -> Measurement {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    f();
+
+    Measurement {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+        elapsed: start.elapsed(),
+    }
+}
+
+fn response_body() -> String {
+    let values = (0..ROWS)
+        .map(|i| format!(r#"[{i},"name {i}","a somewhat longer description of row {i}",{i}.5]"#))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("[{values}]")
+}
+
+fn columns() -> (Arc<Vec<Column>>, Arc<HashMap<String, usize>>) {
+    let columns = ["id", "name", "description", "score"]
+        .into_iter()
+        .zip(["integer", "text", "text", "real"])
+        .enumerate()
+        .map(|(i, (name, type_data))| Column::new(name.to_string(), i, type_data.to_string()))
+        .collect::<Vec<_>>();
+
+    let column_names = columns
+        .iter()
+        .map(|c| (c.name().to_string(), c.ordinal()))
+        .collect();
+
+    (Arc::new(columns), Arc::new(column_names))
+}
+
+/// Decoding as it was done before rows stored `SqlValue`s.
+fn decode_cloned(
+    body: &str,
+    column_names: &HashMap<String, usize>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let rows: Vec<Vec<Value>> = serde_json::from_str(body)?;
+
+    for row in rows {
+        let get = |name: &str| column_names.get(name).and_then(|i| row.get(*i)).cloned();
+
+        black_box(serde_json::from_value::<i64>(
+            get("id").unwrap_or_default(),
+        )?);
+        black_box(serde_json::from_value::<String>(
+            get("name").unwrap_or_default(),
+        )?);
+        black_box(serde_json::from_value::<String>(
+            get("description").unwrap_or_default(),
+        )?);
+        black_box(serde_json::from_value::<f64>(
+            get("score").unwrap_or_default(),
+        )?);
+    }
+
+    Ok(())
+}
+
+fn decode_borrowed(
+    body: &str,
+    columns: &Arc<Vec<Column>>,
+    column_names: &Arc<HashMap<String, usize>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let rows: Vec<Vec<SqlValue>> = serde_json::from_str(body)?;
+
+    for values in rows {
+        let row = Row::from_values(columns, column_names, values.into_boxed_slice());
+
+        black_box(row.get::<i64>("id")?);
+        black_box(row.get::<&str>("name")?);
+        black_box(row.get_str("description")?);
+        black_box(row.get::<f64>("score")?);
+    }
+
+    Ok(())
+}
+
+#[expect(clippy::print_stdout, reason = "benchmark results are printed")]
+fn report(name: &str, measurement: &Measurement) {
+    println!(
+        "{name:<10} {:>10} allocations {:>12} bytes {:>10.2?}",
+        measurement.allocations, measurement.bytes, measurement.elapsed
+    );
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let body = response_body();
+    let (columns, column_names) = columns();
+
+    let mut result = Ok(());
+    let cloned = measure(|| result = decode_cloned(&body, &column_names));
+    result?;
+
+    let mut result = Ok(());
+    let borrowed = measure(|| result = decode_borrowed(&body, &columns, &column_names));
+    result?;
+
+    report("cloned", &cloned);
+    report("borrowed", &borrowed);
+
+    Ok(())
+}
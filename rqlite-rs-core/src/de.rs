@@ -0,0 +1,429 @@
+//! Serde support for [`SqlValue`].
+//!
+//! `&SqlValue` implements [`Deserializer`], so typed values are decoded straight from a row
+//! without going through an intermediate `serde_json::Value`. Text and blobs are handed out as
+//! borrowed data, which allows decoding into `&str` and `&[u8]` without allocating.
+
+use std::fmt;
+
+use serde::{
+    de::{
+        self,
+        value::{BorrowedStrDeserializer, SeqDeserializer},
+        DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserialize, Deserializer,
+};
+use serde_json::Value;
+
+use crate::value::SqlValue;
+
+type Error = serde_json::Error;
+
+struct SqlValueVisitor;
+
+impl<'de> Visitor<'de> for SqlValueVisitor {
+    type Value = SqlValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a SQLite value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<SqlValue, E> {
+        Ok(SqlValue::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<SqlValue, E> {
+        Ok(SqlValue::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<SqlValue, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<SqlValue, E> {
+        Ok(SqlValue::Integer(i64::from(v)))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<SqlValue, E> {
+        Ok(SqlValue::Integer(v))
+    }
+
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "integers beyond i64 are stored as reals by SQLite as well"
+    )]
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<SqlValue, E> {
+        Ok(i64::try_from(v).map_or(SqlValue::Real(v as f64), SqlValue::Integer))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<SqlValue, E> {
+        Ok(SqlValue::Real(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<SqlValue, E> {
+        Ok(SqlValue::Text(v.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<SqlValue, E> {
+        Ok(SqlValue::Text(v))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<SqlValue, E> {
+        Ok(SqlValue::Blob(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<SqlValue, E> {
+        Ok(SqlValue::Blob(v))
+    }
+
+    // Blobs are returned as arrays of bytes if `blob_array` is set, anything else is kept as JSON text
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<SqlValue, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(item) = seq.next_element::<Value>()? {
+            items.push(item);
+        }
+
+        Ok(items
+            .iter()
+            .map(|item| u8::try_from(item.as_u64()?).ok())
+            .collect::<Option<Vec<u8>>>()
+            .map_or_else(
+                || SqlValue::Text(Value::Array(items).to_string()),
+                SqlValue::Blob,
+            ))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<SqlValue, A::Error> {
+        let object = Value::deserialize(de::value::MapAccessDeserializer::new(map))?;
+        Ok(SqlValue::Text(object.to_string()))
+    }
+}
+
+impl<'de> Deserialize<'de> for SqlValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(SqlValueVisitor)
+    }
+}
+
+impl SqlValue {
+    fn invalid_type<E: de::Error>(&self, expected: &dyn de::Expected) -> E {
+        let unexpected = match self {
+            Self::Null => Unexpected::Unit,
+            Self::Integer(i) => Unexpected::Signed(*i),
+            Self::Real(f) => Unexpected::Float(*f),
+            Self::Text(s) => Unexpected::Str(s),
+            Self::Blob(b) => Unexpected::Bytes(b),
+        };
+
+        E::invalid_type(unexpected, expected)
+    }
+
+    /// Reals without a fractional part are accepted where integers are expected,
+    /// as rqlite can not tell them apart in its JSON output.
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "the value is checked to be whole and in range"
+    )]
+    fn whole_real(f: f64) -> Option<i64> {
+        #[expect(clippy::cast_precision_loss, reason = "only used as a range check")]
+        let in_range = f >= i64::MIN as f64 && f < i64::MAX as f64;
+
+        (f.fract() == 0.0 && in_range).then_some(f as i64)
+    }
+}
+
+macro_rules! deserialize_integer {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self {
+                    SqlValue::Real(f) => match SqlValue::whole_real(*f) {
+                        Some(i) => visitor.visit_i64(i),
+                        None => visitor.visit_f64(*f),
+                    },
+                    _ => self.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for &'de SqlValue {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            SqlValue::Null => visitor.visit_unit(),
+            SqlValue::Integer(i) => visitor.visit_i64(*i),
+            SqlValue::Real(f) => visitor.visit_f64(*f),
+            SqlValue::Text(s) => visitor.visit_borrowed_str(s),
+            SqlValue::Blob(b) => visitor.visit_borrowed_bytes(b),
+        }
+    }
+
+    deserialize_integer! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+    }
+
+    // SQLite stores booleans as integers, where any value other than 0 is true
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            SqlValue::Integer(i) => visitor.visit_bool(*i != 0),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            // Keep handing out blobs the way rqlite returns them
+            #[cfg(feature = "fast-blob")]
+            SqlValue::Blob(b) => {
+                use base64::{engine::general_purpose, Engine};
+
+                visitor.visit_string(general_purpose::STANDARD.encode(b))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            SqlValue::Text(s) => visitor.visit_borrowed_bytes(s.as_bytes()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            SqlValue::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            SqlValue::Null => visitor.visit_unit(),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // Blobs are sequences of bytes, text is parsed as JSON
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            SqlValue::Blob(b) => visitor.visit_seq(SeqDeserializer::new(b.iter().copied())),
+            SqlValue::Text(s) => serde_json::Deserializer::from_str(s).deserialize_seq(visitor),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            SqlValue::Text(s) => serde_json::Deserializer::from_str(s).deserialize_map(visitor),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    // Only unit variants can be stored in a column, either by name or as JSON text
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            SqlValue::Text(s) if s.starts_with('{') => {
+                serde_json::Deserializer::from_str(s).deserialize_enum(name, variants, visitor)
+            }
+            SqlValue::Text(s) => visitor.visit_enum(UnitVariant(s)),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        f32 f64 char identifier ignored_any
+    }
+}
+
+struct UnitVariant<'de>(&'de str);
+
+impl<'de> EnumAccess<'de> for UnitVariant<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Self), Error> {
+        let variant = seed.deserialize(BorrowedStrDeserializer::<Error>::new(self.0))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for UnitVariant<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(self, _seed: S) -> Result<S::Value, Error> {
+        Err(de::Error::invalid_type(
+            Unexpected::UnitVariant,
+            &"newtype variant",
+        ))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, _visitor: V) -> Result<V::Value, Error> {
+        Err(de::Error::invalid_type(
+            Unexpected::UnitVariant,
+            &"tuple variant",
+        ))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Error> {
+        Err(de::Error::invalid_type(
+            Unexpected::UnitVariant,
+            &"struct variant",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn decode<'de, T: Deserialize<'de>>(value: &'de SqlValue) -> Result<T, Error> {
+        T::deserialize(value)
+    }
+
+    #[test]
+    fn unit_sql_value_deserialize() {
+        let values: Vec<SqlValue> =
+            serde_json::from_value(json!([null, true, 1, 1.5, "foo", [1, 2], [1, 256], {"a": 1}]))
+                .unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                SqlValue::Null,
+                SqlValue::Integer(1),
+                SqlValue::Integer(1),
+                SqlValue::Real(1.5),
+                SqlValue::Text("foo".to_string()),
+                SqlValue::Blob(vec![1, 2]),
+                SqlValue::Text("[1,256]".to_string()),
+                SqlValue::Text(r#"{"a":1}"#.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unit_sql_value_deserializer_primitives() {
+        assert_eq!(decode::<i32>(&SqlValue::Integer(1)).unwrap(), 1);
+        assert_eq!(decode::<u8>(&SqlValue::Real(2.0)).unwrap(), 2);
+        assert!(decode::<i64>(&SqlValue::Real(2.5)).is_err());
+        assert!((decode::<f64>(&SqlValue::Integer(2)).unwrap() - 2.0).abs() < f64::EPSILON);
+        assert!(decode::<bool>(&SqlValue::Integer(1)).unwrap());
+        assert!(!decode::<bool>(&SqlValue::Integer(0)).unwrap());
+        assert!(decode::<bool>(&SqlValue::Integer(2)).unwrap());
+        assert_eq!(decode::<Option<i64>>(&SqlValue::Null).unwrap(), None);
+        assert_eq!(
+            decode::<Option<i64>>(&SqlValue::Integer(3)).unwrap(),
+            Some(3)
+        );
+        assert!(decode::<String>(&SqlValue::Integer(1)).is_err());
+    }
+
+    #[test]
+    fn unit_sql_value_deserializer_borrowed() {
+        let text = SqlValue::Text("foo".to_string());
+        let s = decode::<&str>(&text).unwrap();
+        assert_eq!(s, "foo");
+        assert!(std::ptr::eq(s, text.as_str().unwrap()));
+
+        let blob = SqlValue::Blob(vec![1, 2, 3]);
+        let b = decode::<&[u8]>(&blob).unwrap();
+        assert_eq!(b, [1, 2, 3]);
+        assert_eq!(decode::<Vec<u8>>(&blob).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn unit_sql_value_deserializer_json_text() {
+        let value = SqlValue::Text(r#"{"a": 1, "b": 2}"#.to_string());
+        let map = decode::<HashMap<String, i64>>(&value).unwrap();
+        assert_eq!(map["b"], 2);
+
+        let value = SqlValue::Text("[1, 2]".to_string());
+        assert_eq!(decode::<Vec<i64>>(&value).unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn unit_sql_value_deserializer_enum() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        enum Status {
+            Active,
+            Inactive,
+        }
+
+        let value = SqlValue::Text("Inactive".to_string());
+        assert_eq!(decode::<Status>(&value).unwrap(), Status::Inactive);
+        assert!(decode::<Status>(&SqlValue::Text("Unknown".to_string())).is_err());
+    }
+}
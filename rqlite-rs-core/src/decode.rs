@@ -1,3 +1,141 @@
+//! Decoding of the values of a row into Rust types.
+
+use serde::Deserialize;
+
+use crate::{IntoTypedError, SqlValue};
+
+/// Decodes a single value of a row.
+///
+/// This is what `Row::get` and `#[derive(FromRow)]` use to read columns. It is implemented for
+/// primitive types, strings, blobs and [`SqlValue`] itself, as well as for the types in
+/// [`crate::types`] behind their features. On top of what the `Deserialize` implementations of
+/// these types accept, the conventions of `SQLite` are followed:
+///
+/// - `bool` is read from integers, where `0` is `false` and any other value `true`.
+/// - Integers are read from reals without a fractional part, as rqlite does not tell them apart.
+/// - Floats are read from integers.
+/// - Timestamps are read from ISO-8601 text and, where supported, from Unix timestamps.
+///
+/// Other types implement it by delegating to an existing implementation, or to their
+/// `Deserialize` implementation with [`deserialize`]:
+///
+/// ```
+/// use rqlite_rs_core::{decode::Decode, IntoTypedError, SqlValue};
+///
+/// struct UserId(i64);
+///
+/// impl Decode<'_> for UserId {
+///     fn decode(value: &SqlValue) -> Result<Self, IntoTypedError> {
+///         i64::decode(value).map(Self)
+///     }
+/// }
+///
+/// assert_eq!(UserId::decode(&SqlValue::Integer(7)).unwrap().0, 7);
+/// ```
+pub trait Decode<'r>: Sized {
+    /// Decodes a value, which may be borrowed from the row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value can not be represented as `Self`.
+    fn decode(value: &'r SqlValue) -> Result<Self, IntoTypedError>;
+}
+
+/// A type that can be decoded without borrowing from the row.
+pub trait DecodeOwned: for<'r> Decode<'r> {}
+
+impl<T: for<'r> Decode<'r>> DecodeOwned for T {}
+
+/// Decodes a value using its `Deserialize` implementation.
+///
+/// Sequences, maps and structs are parsed from JSON text and unit enum variants from their name.
+/// This can be used to implement [`Decode`], or for a single field with
+/// `#[rqlite(with = rqlite_rs::decode::deserialize)]`.
+///
+/// # Errors
+///
+/// Returns an error if the value can not be deserialized into `T`.
+pub fn deserialize<'r, T: Deserialize<'r>>(value: &'r SqlValue) -> Result<T, IntoTypedError> {
+    T::deserialize(value).map_err(IntoTypedError::ConversionError)
+}
+
+/// Implements [`Decode`] for owned types using their `Deserialize` implementation.
+macro_rules! decode_with_deserialize {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl $crate::decode::Decode<'_> for $ty {
+                fn decode(value: &$crate::SqlValue) -> Result<Self, $crate::IntoTypedError> {
+                    $crate::decode::deserialize(value)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(any(
+    feature = "chrono",
+    feature = "time",
+    feature = "uuid",
+    feature = "rust_decimal"
+))]
+pub(crate) use decode_with_deserialize;
+
+// Integers accept whole reals, floats accept integers and `bool` any integer, see `de.rs`
+decode_with_deserialize!(
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    char,
+    String,
+    Vec<u8>,
+);
+
+impl<'r> Decode<'r> for &'r str {
+    fn decode(value: &'r SqlValue) -> Result<Self, IntoTypedError> {
+        deserialize(value)
+    }
+}
+
+impl<'r> Decode<'r> for &'r [u8] {
+    fn decode(value: &'r SqlValue) -> Result<Self, IntoTypedError> {
+        deserialize(value)
+    }
+}
+
+impl Decode<'_> for SqlValue {
+    fn decode(value: &SqlValue) -> Result<Self, IntoTypedError> {
+        Ok(value.clone())
+    }
+}
+
+impl<'r> Decode<'r> for &'r SqlValue {
+    fn decode(value: &'r SqlValue) -> Result<Self, IntoTypedError> {
+        Ok(value)
+    }
+}
+
+impl<'r, T: Decode<'r>> Decode<'r> for Option<T> {
+    fn decode(value: &'r SqlValue) -> Result<Self, IntoTypedError> {
+        if value.is_null() {
+            Ok(None)
+        } else {
+            T::decode(value).map(Some)
+        }
+    }
+}
+
 /// Decodes a base64 encoded string into a blob-like type. This function is only available when the `blob` feature is enabled.
 ///
 /// # Errors
@@ -15,12 +153,82 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn unit_decode_bool() {
+        assert!(bool::decode(&SqlValue::Integer(1)).unwrap());
+        assert!(bool::decode(&SqlValue::Integer(2)).unwrap());
+        assert!(!bool::decode(&SqlValue::Integer(0)).unwrap());
+        assert!(bool::decode(&SqlValue::Text("true".to_string())).is_err());
+        assert!(bool::decode(&SqlValue::Null).is_err());
+    }
+
+    #[test]
+    fn unit_decode_numbers() {
+        assert_eq!(i64::decode(&SqlValue::Real(3.0)).unwrap(), 3);
+        assert!(i64::decode(&SqlValue::Real(3.5)).is_err());
+        assert!(u8::decode(&SqlValue::Integer(256)).is_err());
+        assert!((f64::decode(&SqlValue::Integer(3)).unwrap() - 3.0).abs() < f64::EPSILON);
+        assert!(i64::decode(&SqlValue::Text("3".to_string())).is_err());
+    }
+
+    #[test]
+    fn unit_decode_borrowed() {
+        let text = SqlValue::Text("foo".to_string());
+        assert!(std::ptr::eq(
+            <&str>::decode(&text).unwrap(),
+            text.as_str().unwrap()
+        ));
+        assert_eq!(String::decode(&text).unwrap(), "foo");
+        assert_eq!(<&SqlValue>::decode(&text).unwrap(), &text);
+
+        let blob = SqlValue::Blob(vec![1, 2]);
+        assert_eq!(<&[u8]>::decode(&blob).unwrap(), [1, 2]);
+        assert_eq!(SqlValue::decode(&blob).unwrap(), blob);
+    }
+
+    #[test]
+    fn unit_decode_option() {
+        assert_eq!(Option::<i64>::decode(&SqlValue::Null).unwrap(), None);
+        assert_eq!(
+            Option::<i64>::decode(&SqlValue::Integer(1)).unwrap(),
+            Some(1)
+        );
+        assert!(Option::<i64>::decode(&SqlValue::Text("a".to_string())).is_err());
+    }
+
+    #[test]
+    fn unit_decode_deserialize() {
+        let value = SqlValue::Text(r#"{"a": 1}"#.to_string());
+        let map: HashMap<String, i64> = deserialize(&value).unwrap();
+        assert_eq!(map["a"], 1);
+    }
+
+    #[test]
+    fn unit_decode_custom_impl() {
+        // Types deriving `Deserialize` can still implement `Decode` themselves
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Score(f64);
+
+        impl Decode<'_> for Score {
+            fn decode(value: &SqlValue) -> Result<Self, IntoTypedError> {
+                f64::decode(value).map(|score| Self(score / 100.0))
+            }
+        }
+
+        assert_eq!(Score::decode(&SqlValue::Integer(50)).unwrap(), Score(0.5));
+        assert_eq!(
+            deserialize::<Score>(&SqlValue::Integer(50)).unwrap(),
+            Score(50.0)
+        );
+    }
 
     #[test]
     #[cfg(feature = "fast-blob")]
     fn unit_decode_blob() {
-        use super::*;
-
         let blob = "SGVsbG8gV29ybGQ=";
         let decoded = decode_blob::<Vec<u8>>(blob).unwrap();
         assert_eq!(decoded, b"Hello World");
@@ -8,6 +8,10 @@ pub enum IntoTypedError {
     ColumnNotFound,
     #[error("Value not found")]
     ValueNotFound,
+    #[error("Expected a value of type {0}, found {1}")]
+    UnexpectedType(&'static str, &'static str),
+    #[error("Failed to convert column {0}: {1}")]
+    TryFromError(String, String),
     #[error("Expected {0} columns, found {1}")]
     ColumnCountMismatch(usize, usize),
     #[cfg(feature = "fast-blob")]
@@ -1,4 +1,4 @@
-use crate::{row::Row, IntoTypedError};
+use crate::{decode::DecodeOwned, row::Row, IntoTypedError};
 
 pub trait FromRow: Sized {
     /// Convert a `Row` into `Self`
@@ -12,7 +12,7 @@ macro_rules! impl_from_row_for_tuple {
     ($( ($idx:tt) - $T:ident );+;) => {
         impl<$($T,)+> FromRow for ($($T,)+)
         where
-            $($T: serde::de::DeserializeOwned,)+
+            $($T: DecodeOwned,)+
         {
             #[inline]
             fn from_row(row: Row) -> Result<Self, IntoTypedError> {
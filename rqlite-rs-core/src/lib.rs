@@ -1,22 +1,29 @@
 #![warn(clippy::pedantic, clippy::all)]
 
 mod column;
+mod de;
 pub mod decode;
 mod error;
 mod from_row;
 mod into_typed_rows;
 mod row;
+pub mod types;
+mod value;
 pub use column::Column;
+pub use decode::Decode;
 pub use error::IntoTypedError;
 pub use from_row::FromRow;
 pub use into_typed_rows::IntoTypedRows;
 pub use row::Row;
+pub use value::SqlValue;
 
 mod prelude {
     #![allow(unused_imports, reason = "re-exporting for prelude use")]
     pub use crate::column::Column;
+    pub use crate::decode::Decode;
     pub use crate::from_row::FromRow;
     pub use crate::into_typed_rows::IntoTypedRows;
     pub use crate::row::Row;
+    pub use crate::value::SqlValue;
     pub use crate::IntoTypedError;
 }
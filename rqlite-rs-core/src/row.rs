@@ -2,21 +2,43 @@ use std::{collections::HashMap, sync::Arc};
 
 use serde_json::Value;
 
-use crate::{column::Column, from_row::FromRow, IntoTypedError};
+use crate::{column::Column, decode::Decode, from_row::FromRow, value::SqlValue, IntoTypedError};
 
 #[derive(Debug)]
 pub struct Row {
-    values: Box<[Value]>,
+    values: Box<[SqlValue]>,
     columns: Arc<Vec<Column>>,
     column_names: Arc<HashMap<String, usize>>,
 }
 
 impl Row {
+    /// Create a row from the JSON values returned by rqlite.
+    /// The values are converted into [`SqlValue`]s based on the declared types of their columns.
     #[must_use]
     pub fn new(
         columns: &Arc<Vec<Column>>,
         column_names: &Arc<HashMap<String, usize>>,
         values: Box<[Value]>,
+    ) -> Self {
+        let values = values
+            .into_vec()
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let type_data = columns.get(index).map_or("", Column::type_data);
+                SqlValue::from_json(value, type_data)
+            })
+            .collect();
+
+        Self::from_values(columns, column_names, values)
+    }
+
+    /// Create a row from already converted values.
+    #[must_use]
+    pub fn from_values(
+        columns: &Arc<Vec<Column>>,
+        column_names: &Arc<HashMap<String, usize>>,
+        values: Box<[SqlValue]>,
     ) -> Self {
         Self {
             values,
@@ -35,35 +57,20 @@ impl Row {
         &self.column_names
     }
 
-    /// Get a value by column name
+    /// Get a value by column name, decoded with [`Decode`]
     ///
     /// # Errors
-    /// If the column name is not found, returns `IntoTypedError::ColumnNotFound`
-    pub fn get<T: serde::de::DeserializeOwned>(&self, name: &str) -> Result<T, IntoTypedError> {
-        let index = self
-            .column_names
-            .get(name)
-            .ok_or(IntoTypedError::ColumnNotFound)?;
-
-        let value = self
-            .values
-            .get(*index)
-            .ok_or(IntoTypedError::ValueNotFound)?;
-
-        let value =
-            serde_json::from_value(value.clone()).map_err(IntoTypedError::ConversionError)?;
-
-        Ok(value)
+    /// If the column name is not found, returns `IntoTypedError::ColumnNotFound`.
+    /// If the value can not be decoded, returns the error of [`Decode::decode`]
+    pub fn get<'r, T: Decode<'r>>(&'r self, name: &str) -> Result<T, IntoTypedError> {
+        T::decode(self.get_value(name)?)
     }
 
     /// Get a value by column name, returning `None` if the value is `null`
     ///
     /// # Errors
     /// If the column name is not found, returns `IntoTypedError::ColumnNotFound`
-    pub fn get_opt<T: serde::de::DeserializeOwned>(
-        &self,
-        name: &str,
-    ) -> Result<Option<T>, IntoTypedError> {
+    pub fn get_opt<'r, T: Decode<'r>>(&'r self, name: &str) -> Result<Option<T>, IntoTypedError> {
         let Some(index) = self.column_names.get(name) else {
             return Ok(None);
         };
@@ -74,12 +81,10 @@ impl Row {
             .get(*index)
             .ok_or(IntoTypedError::ValueNotFound)?;
 
-        if value == &Value::Null {
+        if value.is_null() {
             Ok(None)
         } else {
-            let value =
-                serde_json::from_value(value.clone()).map_err(IntoTypedError::ConversionError)?;
-            Ok(Some(value))
+            T::decode(value).map(Some)
         }
     }
 
@@ -87,42 +92,78 @@ impl Row {
     ///
     /// # Errors
     /// If the index is out of bounds, returns `IntoTypedError::ValueNotFound`
-    pub fn get_by_index<T: serde::de::DeserializeOwned>(
-        &self,
-        index: usize,
-    ) -> Result<T, IntoTypedError> {
-        let value = self
-            .values
-            .get(index)
-            .ok_or(IntoTypedError::ValueNotFound)?;
-        let value =
-            serde_json::from_value(value.clone()).map_err(IntoTypedError::ConversionError)?;
-        Ok(value)
+    pub fn get_by_index<'r, T: Decode<'r>>(&'r self, index: usize) -> Result<T, IntoTypedError> {
+        T::decode(self.get_value_by_index(index)?)
     }
 
     /// Get a value by index, returning `None` if the value is `null`
     ///
     /// # Errors
     /// If the index is out of bounds, returns `IntoTypedError::ValueNotFound`
-    pub fn get_by_index_opt<T: serde::de::DeserializeOwned>(
-        &self,
+    pub fn get_by_index_opt<'r, T: Decode<'r>>(
+        &'r self,
         index: usize,
     ) -> Result<Option<T>, IntoTypedError> {
         let Some(value) = self.values.get(index) else {
             return Ok(None);
         };
 
-        if value == &Value::Null {
+        if value.is_null() {
             Ok(None)
         } else {
-            let value =
-                serde_json::from_value(value.clone()).map_err(IntoTypedError::ConversionError)?;
-            Ok(Some(value))
+            T::decode(value).map(Some)
         }
     }
 
+    /// Get the raw value by column name
+    ///
+    /// # Errors
+    /// If the column name is not found, returns `IntoTypedError::ColumnNotFound`
+    pub fn get_value(&self, name: &str) -> Result<&SqlValue, IntoTypedError> {
+        let index = self
+            .column_names
+            .get(name)
+            .ok_or(IntoTypedError::ColumnNotFound)?;
+
+        self.get_value_by_index(*index)
+    }
+
+    /// Get the raw value by index
+    ///
+    /// # Errors
+    /// If the index is out of bounds, returns `IntoTypedError::ValueNotFound`
+    pub fn get_value_by_index(&self, index: usize) -> Result<&SqlValue, IntoTypedError> {
+        self.values.get(index).ok_or(IntoTypedError::ValueNotFound)
+    }
+
+    /// Get a text value by column name without copying it
+    ///
+    /// # Errors
+    /// If the column name is not found, returns `IntoTypedError::ColumnNotFound`.
+    /// If the value is not text, returns `IntoTypedError::UnexpectedType`
+    pub fn get_str(&self, name: &str) -> Result<&str, IntoTypedError> {
+        let value = self.get_value(name)?;
+
+        value
+            .as_str()
+            .ok_or_else(|| IntoTypedError::UnexpectedType("TEXT", value.type_name()))
+    }
+
+    /// Get a blob value by column name without copying it
+    ///
+    /// # Errors
+    /// If the column name is not found, returns `IntoTypedError::ColumnNotFound`.
+    /// If the value is not a blob, returns `IntoTypedError::UnexpectedType`
+    pub fn get_bytes(&self, name: &str) -> Result<&[u8], IntoTypedError> {
+        let value = self.get_value(name)?;
+
+        value
+            .as_bytes()
+            .ok_or_else(|| IntoTypedError::UnexpectedType("BLOB", value.type_name()))
+    }
+
     #[must_use]
-    pub fn values(&self) -> &[Value] {
+    pub fn values(&self) -> &[SqlValue] {
         &self.values
     }
 
@@ -244,8 +285,85 @@ mod tests {
         let row = create_row();
 
         assert_eq!(row.values().len(), 2);
-        assert_eq!(row.values()[0], serde_json::json!(1));
-        assert_eq!(row.values()[1], serde_json::json!("test"));
+        assert_eq!(row.values()[0], SqlValue::Integer(1));
+        assert_eq!(row.values()[1], SqlValue::Text("test".to_string()));
+    }
+
+    #[test]
+    fn unit_row_get_value() {
+        let row = create_row();
+
+        assert_eq!(row.get_value("id").unwrap(), &SqlValue::Integer(1));
+        assert_eq!(row.get_value("name").unwrap().as_str(), Some("test"));
+        assert!(matches!(
+            row.get_value("not_found").unwrap_err(),
+            IntoTypedError::ColumnNotFound
+        ));
+        assert!(matches!(
+            row.get_value_by_index(2).unwrap_err(),
+            IntoTypedError::ValueNotFound
+        ));
+    }
+
+    #[test]
+    fn unit_row_get_borrowed() {
+        let row = create_row();
+
+        let name: &str = row.get("name").unwrap();
+        assert_eq!(name, "test");
+        assert_eq!(row.get_str("name").unwrap(), "test");
+        assert!(std::ptr::eq(row.get_str("name").unwrap(), name));
+        assert!(matches!(
+            row.get_str("id").unwrap_err(),
+            IntoTypedError::UnexpectedType("TEXT", "INTEGER")
+        ));
+        assert!(matches!(
+            row.get_bytes("name").unwrap_err(),
+            IntoTypedError::UnexpectedType("BLOB", "TEXT")
+        ));
+    }
+
+    #[test]
+    fn unit_row_get_bytes() {
+        let columns = Arc::new(vec![Column::new(
+            "data".to_string(),
+            0usize,
+            "blob".to_string(),
+        )]);
+        let column_names = Arc::new(HashMap::from([("data".to_string(), 0)]));
+
+        let row = Row::from_values(
+            &columns,
+            &column_names,
+            vec![SqlValue::Blob(vec![1, 2, 3])].into_boxed_slice(),
+        );
+
+        assert_eq!(row.get_bytes("data").unwrap(), [1, 2, 3]);
+        assert_eq!(row.get::<&[u8]>("data").unwrap(), [1, 2, 3]);
+        assert_eq!(row.get::<Vec<u8>>("data").unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn unit_row_new_uses_column_types() {
+        use serde_json::json;
+
+        let columns = Arc::new(vec![
+            Column::new("score".to_string(), 0usize, "real".to_string()),
+            Column::new("count".to_string(), 1usize, "integer".to_string()),
+        ]);
+        let column_names = Arc::new(HashMap::from([
+            ("score".to_string(), 0),
+            ("count".to_string(), 1),
+        ]));
+
+        let row = Row::new(
+            &columns,
+            &column_names,
+            vec![json!(2), json!(2)].into_boxed_slice(),
+        );
+
+        assert_eq!(row.values(), &[SqlValue::Real(2.0), SqlValue::Integer(2)]);
+        assert!((row.get::<f64>("score").unwrap() - 2.0).abs() < f64::EPSILON);
     }
 
     #[test]
@@ -0,0 +1,174 @@
+use ::chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, Utc};
+
+use super::{
+    decode_timestamp, deserialize_from_i64, deserialize_from_str, text_value, UnixTimestamp,
+};
+use crate::SqlValue;
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M:%S%.f";
+
+text_value!(DateTime<Utc>, |t| t
+    .to_rfc3339_opts(SecondsFormat::AutoSi, true));
+text_value!(DateTime<FixedOffset>, |t| t
+    .to_rfc3339_opts(SecondsFormat::AutoSi, true));
+text_value!(NaiveDateTime, |t| t.format(DATE_TIME_FORMAT).to_string());
+text_value!(NaiveDate, |t| t.format(DATE_FORMAT).to_string());
+text_value!(NaiveTime, |t| t.format(TIME_FORMAT).to_string());
+
+impl From<UnixTimestamp<DateTime<Utc>>> for SqlValue {
+    fn from(value: UnixTimestamp<DateTime<Utc>>) -> Self {
+        Self::Integer(value.0.timestamp())
+    }
+}
+
+impl From<UnixTimestamp<NaiveDateTime>> for SqlValue {
+    fn from(value: UnixTimestamp<NaiveDateTime>) -> Self {
+        Self::Integer(value.0.and_utc().timestamp())
+    }
+}
+
+// SQLite itself writes timestamps with a space instead of a `T`, both are accepted
+fn parse_naive_date_time(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, DATE_TIME_FORMAT)
+        .or_else(|_space| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f"))
+        .ok()
+}
+
+deserialize_from_str!(Iso8601<DateTime<Utc>>, "an RFC 3339 timestamp", |s| {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+});
+deserialize_from_str!(
+    Iso8601<DateTime<FixedOffset>>,
+    "an RFC 3339 timestamp",
+    |s| DateTime::parse_from_rfc3339(s).ok()
+);
+deserialize_from_str!(
+    Iso8601<NaiveDateTime>,
+    "an ISO-8601 timestamp",
+    parse_naive_date_time
+);
+deserialize_from_str!(Iso8601<NaiveDate>, "an ISO-8601 date", |s| {
+    NaiveDate::parse_from_str(s, DATE_FORMAT).ok()
+});
+deserialize_from_str!(Iso8601<NaiveTime>, "an ISO-8601 time", |s| {
+    NaiveTime::parse_from_str(s, TIME_FORMAT).ok()
+});
+
+deserialize_from_i64!(UnixTimestamp<DateTime<Utc>>, "a Unix timestamp", |secs| {
+    DateTime::from_timestamp(secs, 0)
+});
+deserialize_from_i64!(UnixTimestamp<NaiveDateTime>, "a Unix timestamp", |secs| {
+    DateTime::from_timestamp(secs, 0).map(|t| t.naive_utc())
+});
+
+decode_timestamp!(DateTime<Utc>, unix);
+decode_timestamp!(DateTime<FixedOffset>);
+decode_timestamp!(NaiveDateTime, unix);
+decode_timestamp!(NaiveDate);
+decode_timestamp!(NaiveTime);
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::{
+        types::{Iso8601, UnixTimestamp},
+        Decode, SqlValue,
+    };
+
+    use super::*;
+
+    #[test]
+    fn unit_chrono_to_sql_value() {
+        let t = DateTime::from_timestamp(1_706_704_200, 0).unwrap();
+
+        assert_eq!(
+            SqlValue::from(t),
+            SqlValue::Text("2024-01-31T12:30:00Z".to_string())
+        );
+        assert_eq!(
+            SqlValue::from(t.naive_utc()),
+            SqlValue::Text("2024-01-31T12:30:00".to_string())
+        );
+        assert_eq!(
+            SqlValue::from(t.date_naive()),
+            SqlValue::Text("2024-01-31".to_string())
+        );
+        assert_eq!(
+            SqlValue::from(UnixTimestamp(t)),
+            SqlValue::Integer(1_706_704_200)
+        );
+
+        let t = DateTime::from_timestamp(1_706_704_200, 500_000_000).unwrap();
+        assert_eq!(
+            SqlValue::from(t.time()),
+            SqlValue::Text("12:30:00.500".to_string())
+        );
+    }
+
+    #[test]
+    fn unit_chrono_round_trip() {
+        let t = DateTime::from_timestamp(1_706_704_200, 123_000).unwrap();
+
+        let value = SqlValue::from(t);
+        assert_eq!(DateTime::<Utc>::deserialize(&value).unwrap(), t);
+        assert_eq!(Iso8601::<DateTime<Utc>>::deserialize(&value).unwrap().0, t);
+
+        let value = SqlValue::from(t.naive_utc());
+        assert_eq!(NaiveDateTime::deserialize(&value).unwrap(), t.naive_utc());
+
+        let value = SqlValue::from(t.time());
+        assert_eq!(NaiveTime::deserialize(&value).unwrap(), t.time());
+    }
+
+    #[test]
+    fn unit_chrono_iso8601() {
+        let value = SqlValue::Text("2024-01-31T12:30:00Z".to_string());
+        let Iso8601(t) = Iso8601::<DateTime<Utc>>::deserialize(&value).unwrap();
+        assert_eq!(t.timestamp(), 1_706_704_200);
+
+        let value = SqlValue::Text("2024-01-31 12:30:00.5".to_string());
+        let Iso8601(t) = Iso8601::<NaiveDateTime>::deserialize(&value).unwrap();
+        assert_eq!(t.and_utc().timestamp_millis(), 1_706_704_200_500);
+
+        let value = SqlValue::Text("31.01.2024".to_string());
+        assert!(Iso8601::<NaiveDate>::deserialize(&value).is_err());
+    }
+
+    #[test]
+    fn unit_chrono_unix_timestamp() {
+        let value = SqlValue::Integer(1_706_704_200);
+        let UnixTimestamp(t) = UnixTimestamp::<DateTime<Utc>>::deserialize(&value).unwrap();
+        assert_eq!(t.to_rfc3339(), "2024-01-31T12:30:00+00:00");
+    }
+
+    #[test]
+    fn unit_chrono_decode() {
+        let t = DateTime::from_timestamp(1_706_704_200, 0).unwrap();
+
+        for value in [
+            SqlValue::from(t),
+            SqlValue::from(UnixTimestamp(t)),
+            SqlValue::Text("2024-01-31T13:30:00+01:00".to_string()),
+        ] {
+            assert_eq!(DateTime::<Utc>::decode(&value).unwrap(), t);
+        }
+
+        // Written by `SQLite`'s `CURRENT_TIMESTAMP`
+        let value = SqlValue::Text("2024-01-31 12:30:00".to_string());
+        assert_eq!(NaiveDateTime::decode(&value).unwrap(), t.naive_utc());
+        assert_eq!(
+            NaiveDateTime::decode(&SqlValue::from(UnixTimestamp(t.naive_utc()))).unwrap(),
+            t.naive_utc()
+        );
+
+        let value = SqlValue::from(t.date_naive());
+        assert_eq!(NaiveDate::decode(&value).unwrap(), t.date_naive());
+        assert!(NaiveDate::decode(&SqlValue::Integer(1)).is_err());
+        assert!(UnixTimestamp::<DateTime<Utc>>::decode(&SqlValue::from(t)).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+use ::rust_decimal::Decimal;
+
+use crate::{decode::decode_with_deserialize, SqlValue};
+
+// Stored as text, as converting to a real would lose precision
+impl From<Decimal> for SqlValue {
+    fn from(value: Decimal) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+decode_with_deserialize!(Decimal);
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::Decode;
+
+    use super::*;
+
+    #[test]
+    fn unit_decimal_round_trip() {
+        let decimal = Decimal::new(123_456_789, 4);
+
+        let value = SqlValue::from(decimal);
+        assert_eq!(value, SqlValue::Text("12345.6789".to_string()));
+        assert_eq!(
+            <Decimal as Deserialize>::deserialize(&value).unwrap(),
+            decimal
+        );
+
+        // Numbers written by other clients can be read as well
+        assert_eq!(
+            <Decimal as Deserialize>::deserialize(&SqlValue::Integer(12)).unwrap(),
+            Decimal::from(12)
+        );
+        assert_eq!(Decimal::decode(&value).unwrap(), decimal);
+    }
+}
@@ -0,0 +1,126 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, DeserializeOwned, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use serde_json::Value;
+
+use super::Json;
+use crate::{decode, Decode, IntoTypedError, SqlValue};
+
+impl From<Value> for SqlValue {
+    fn from(value: Value) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl<T: Serialize> Json<T> {
+    /// Converts the value into JSON text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value can not be serialized, e.g. maps with non-string keys.
+    pub fn to_sql_value(&self) -> Result<SqlValue, serde_json::Error> {
+        serde_json::to_string(&self.0).map(SqlValue::Text)
+    }
+}
+
+struct JsonVisitor<T>(PhantomData<T>);
+
+impl<T: DeserializeOwned> Visitor<'_> for JsonVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("JSON text")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        serde_json::from_str(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        serde_json::from_slice(v).map_err(E::custom)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Json<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_str(JsonVisitor(PhantomData))
+            .map(Self)
+    }
+}
+
+impl<T: DeserializeOwned> Decode<'_> for Json<T> {
+    fn decode(value: &SqlValue) -> Result<Self, IntoTypedError> {
+        decode::deserialize(value)
+    }
+}
+
+// Text is parsed, so values round-trip through their JSON text
+impl Decode<'_> for Value {
+    fn decode(value: &SqlValue) -> Result<Self, IntoTypedError> {
+        match value {
+            SqlValue::Text(s) => serde_json::from_str(s).map_err(IntoTypedError::ConversionError),
+            _ => decode::deserialize(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::SqlValue;
+
+    use super::*;
+
+    #[test]
+    fn unit_json_to_sql_value() {
+        assert_eq!(
+            SqlValue::from(json!({"a": 1})),
+            SqlValue::Text(r#"{"a":1}"#.to_string())
+        );
+        assert_eq!(
+            Json(vec![1, 2]).to_sql_value().unwrap(),
+            SqlValue::Text("[1,2]".to_string())
+        );
+    }
+
+    #[test]
+    fn unit_json() {
+        let value = SqlValue::Text(r#"{"a": [1, 2]}"#.to_string());
+        assert_eq!(
+            Json::<Value>::deserialize(&value).unwrap(),
+            Json(json!({"a": [1, 2]}))
+        );
+
+        let value = SqlValue::Text("[1, 2]".to_string());
+        assert_eq!(
+            Json::<Vec<i64>>::deserialize(&value).unwrap(),
+            Json(vec![1, 2])
+        );
+
+        let value = SqlValue::Text("{".to_string());
+        assert!(Json::<Value>::deserialize(&value).is_err());
+    }
+
+    #[test]
+    fn unit_json_decode() {
+        let json = json!({"a": [1, 2]});
+
+        // Values are read back from the JSON text they are written as
+        assert_eq!(Value::decode(&SqlValue::from(json.clone())).unwrap(), json);
+        assert_eq!(
+            Json::<Value>::decode(&SqlValue::from(json.clone())).unwrap(),
+            Json(json)
+        );
+        assert_eq!(Value::decode(&SqlValue::Integer(1)).unwrap(), json!(1));
+        assert_eq!(Value::decode(&SqlValue::Null).unwrap(), Value::Null);
+        assert!(Value::decode(&SqlValue::Text("a".to_string())).is_err());
+
+        let value = Json(vec![1, 2]).to_sql_value().unwrap();
+        assert_eq!(Json::<Vec<i64>>::decode(&value).unwrap(), Json(vec![1, 2]));
+    }
+}
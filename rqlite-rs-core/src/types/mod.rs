@@ -0,0 +1,164 @@
+//! Wrappers selecting how values are stored in `SQLite`.
+//!
+//! `SQLite` has no dedicated types for timestamps, UUIDs or JSON, so they have to be stored as
+//! text, integers or blobs. Without a wrapper the following formats are used:
+//!
+//! | Type | Feature | Format |
+//! |------|---------|--------|
+//! | `chrono::DateTime<Utc>`, `chrono::DateTime<FixedOffset>` | `chrono` | RFC 3339 text, e.g. `2024-01-31T12:30:00Z` |
+//! | `chrono::NaiveDateTime` | `chrono` | ISO-8601 text, e.g. `2024-01-31T12:30:00` |
+//! | `chrono::NaiveDate`, `chrono::NaiveTime` | `chrono` | ISO-8601 text, e.g. `2024-01-31` and `12:30:00` |
+//! | `time::OffsetDateTime` | `time` | RFC 3339 text |
+//! | `time::PrimitiveDateTime`, `time::Date`, `time::Time` | `time` | ISO-8601 text |
+//! | `uuid::Uuid` | `uuid` | Hyphenated text, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8` |
+//! | `serde_json::Value` | `serde_json` | JSON text |
+//! | `rust_decimal::Decimal` | `rust_decimal` | Text, which keeps the exact value |
+//!
+//! All of these types convert into a [`SqlValue`](crate::SqlValue) using their format, which is
+//! also how they are encoded as query arguments, and implement [`Decode`](crate::Decode) to read
+//! it back with `Row::get`. Timestamps are also decoded from text written by `SQLite` itself,
+//! like `2024-01-31 12:30:00`, and `DateTime<Utc>`, `NaiveDateTime`, `OffsetDateTime` and
+//! `PrimitiveDateTime` from Unix timestamps. The wrappers in this module select a different
+//! format, or only accept a single one:
+//!
+//! - [`Iso8601`] only reads timestamps stored as text.
+//! - [`UnixTimestamp`] stores timestamps as the number of seconds since the Unix epoch.
+//! - [`UuidBlob`] stores UUIDs as 16 byte blobs.
+//! - [`Json`] stores any serializable value as JSON text and parses it when decoding.
+
+#[cfg(feature = "chrono")]
+mod chrono;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "time")]
+mod time;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "uuid")]
+pub use self::uuid::UuidBlob;
+
+/// A timestamp stored as ISO-8601 text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Iso8601<T>(pub T);
+
+/// A timestamp stored as the number of seconds since the Unix epoch.
+/// Fractional seconds are truncated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct UnixTimestamp<T>(pub T);
+
+/// A value stored as JSON text.
+///
+/// Serialization can fail, so arguments are created using [`Json::to_sql_value`].
+#[cfg(feature = "serde_json")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Json<T>(pub T);
+
+/// Implements `Deserialize` and `Decode` for a wrapper by parsing text.
+#[cfg(any(feature = "chrono", feature = "time"))]
+macro_rules! deserialize_from_str {
+    ($wrapper:ident < $ty:ty >, $expecting:literal, $parse:expr) => {
+        impl<'de> serde::Deserialize<'de> for $crate::types::$wrapper<$ty> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct TextVisitor;
+
+                impl serde::de::Visitor<'_> for TextVisitor {
+                    type Value = $ty;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.write_str($expecting)
+                    }
+
+                    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<$ty, E> {
+                        let parse: fn(&str) -> Option<$ty> = $parse;
+                        parse(v)
+                            .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+                    }
+                }
+
+                deserializer
+                    .deserialize_str(TextVisitor)
+                    .map($crate::types::$wrapper)
+            }
+        }
+
+        $crate::decode::decode_with_deserialize!($crate::types::$wrapper<$ty>);
+    };
+}
+
+/// Implements `Deserialize` and `Decode` for a wrapper by converting an integer.
+#[cfg(any(feature = "chrono", feature = "time"))]
+macro_rules! deserialize_from_i64 {
+    ($wrapper:ident < $ty:ty >, $expecting:literal, $convert:expr) => {
+        impl<'de> serde::Deserialize<'de> for $crate::types::$wrapper<$ty> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = <i64 as serde::Deserialize>::deserialize(deserializer)?;
+                let convert: fn(i64) -> Option<$ty> = $convert;
+
+                convert(value).map($crate::types::$wrapper).ok_or_else(|| {
+                    serde::de::Error::invalid_value(
+                        serde::de::Unexpected::Signed(value),
+                        &$expecting,
+                    )
+                })
+            }
+        }
+
+        $crate::decode::decode_with_deserialize!($crate::types::$wrapper<$ty>);
+    };
+}
+
+/// Implements `Decode` for a timestamp by reading text through its [`Iso8601`] wrapper and,
+/// with `unix`, integers through its [`UnixTimestamp`] wrapper.
+#[cfg(any(feature = "chrono", feature = "time"))]
+macro_rules! decode_timestamp {
+    ($ty:ty) => {
+        impl $crate::decode::Decode<'_> for $ty {
+            fn decode(value: &$crate::SqlValue) -> Result<Self, $crate::IntoTypedError> {
+                <$crate::types::Iso8601<$ty> as $crate::decode::Decode>::decode(value).map(|t| t.0)
+            }
+        }
+    };
+    ($ty:ty, unix) => {
+        impl $crate::decode::Decode<'_> for $ty {
+            fn decode(value: &$crate::SqlValue) -> Result<Self, $crate::IntoTypedError> {
+                match value {
+                    $crate::SqlValue::Integer(_) => {
+                        <$crate::types::UnixTimestamp<$ty> as $crate::decode::Decode>::decode(value)
+                            .map(|t| t.0)
+                    }
+                    _ => <$crate::types::Iso8601<$ty> as $crate::decode::Decode>::decode(value)
+                        .map(|t| t.0),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(crate) use {decode_timestamp, deserialize_from_i64, deserialize_from_str};
+
+/// Implements `From` for [`SqlValue`](crate::SqlValue) for a type and its [`Iso8601`] wrapper
+/// by formatting it as text.
+#[cfg(any(feature = "chrono", feature = "time"))]
+macro_rules! text_value {
+    ($ty:ty, $format:expr) => {
+        impl From<$ty> for $crate::SqlValue {
+            fn from(value: $ty) -> Self {
+                let format: fn($ty) -> String = $format;
+                Self::Text(format(value))
+            }
+        }
+
+        impl From<$crate::types::Iso8601<$ty>> for $crate::SqlValue {
+            fn from(value: $crate::types::Iso8601<$ty>) -> Self {
+                Self::from(value.0)
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(crate) use text_value;
@@ -0,0 +1,210 @@
+use ::time::{
+    format_description::{well_known::Rfc3339, BorrowedFormatItem},
+    macros::format_description,
+    Date, OffsetDateTime, PrimitiveDateTime, Time,
+};
+
+use super::{
+    decode_timestamp, deserialize_from_i64, deserialize_from_str, text_value, UnixTimestamp,
+};
+use crate::SqlValue;
+
+const DATE_TIME_FORMAT: &[BorrowedFormatItem<'_>] =
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]");
+const DATE_FORMAT: &[BorrowedFormatItem<'_>] = format_description!("[year]-[month]-[day]");
+const TIME_FORMAT: &[BorrowedFormatItem<'_>] =
+    format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]");
+// Optional items are always formatted, so whole seconds are written using separate formats
+const WHOLE_DATE_TIME_FORMAT: &[BorrowedFormatItem<'_>] =
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]");
+const WHOLE_TIME_FORMAT: &[BorrowedFormatItem<'_>] =
+    format_description!("[hour]:[minute]:[second]");
+
+// Formatting only fails for years that can not be represented with four digits,
+// which fall back to the `Display` implementation
+text_value!(OffsetDateTime, |t| t
+    .format(&Rfc3339)
+    .unwrap_or_else(|_unrepresentable| t.to_string()));
+text_value!(PrimitiveDateTime, |t| {
+    let format = if t.nanosecond() == 0 {
+        WHOLE_DATE_TIME_FORMAT
+    } else {
+        DATE_TIME_FORMAT
+    };
+    t.format(format)
+        .unwrap_or_else(|_unrepresentable| t.to_string())
+});
+text_value!(Date, |t| t
+    .format(DATE_FORMAT)
+    .unwrap_or_else(|_unrepresentable| t.to_string()));
+text_value!(Time, |t| {
+    let format = if t.nanosecond() == 0 {
+        WHOLE_TIME_FORMAT
+    } else {
+        TIME_FORMAT
+    };
+    t.format(format)
+        .unwrap_or_else(|_unrepresentable| t.to_string())
+});
+
+impl From<UnixTimestamp<OffsetDateTime>> for SqlValue {
+    fn from(value: UnixTimestamp<OffsetDateTime>) -> Self {
+        Self::Integer(value.0.unix_timestamp())
+    }
+}
+
+impl From<UnixTimestamp<PrimitiveDateTime>> for SqlValue {
+    fn from(value: UnixTimestamp<PrimitiveDateTime>) -> Self {
+        Self::Integer(value.0.assume_utc().unix_timestamp())
+    }
+}
+
+// SQLite itself writes timestamps with a space instead of a `T`, both are accepted
+fn parse_primitive_date_time(s: &str) -> Option<PrimitiveDateTime> {
+    PrimitiveDateTime::parse(&s.replacen(' ', "T", 1), DATE_TIME_FORMAT).ok()
+}
+
+deserialize_from_str!(Iso8601<OffsetDateTime>, "an RFC 3339 timestamp", |s| {
+    OffsetDateTime::parse(s, &Rfc3339).ok()
+});
+deserialize_from_str!(
+    Iso8601<PrimitiveDateTime>,
+    "an ISO-8601 timestamp",
+    parse_primitive_date_time
+);
+deserialize_from_str!(Iso8601<Date>, "an ISO-8601 date", |s| {
+    Date::parse(s, DATE_FORMAT).ok()
+});
+deserialize_from_str!(Iso8601<Time>, "an ISO-8601 time", |s| {
+    Time::parse(s, TIME_FORMAT).ok()
+});
+
+deserialize_from_i64!(UnixTimestamp<OffsetDateTime>, "a Unix timestamp", |secs| {
+    OffsetDateTime::from_unix_timestamp(secs).ok()
+});
+deserialize_from_i64!(
+    UnixTimestamp<PrimitiveDateTime>,
+    "a Unix timestamp",
+    |secs| OffsetDateTime::from_unix_timestamp(secs)
+        .ok()
+        .map(|t| PrimitiveDateTime::new(t.date(), t.time()))
+);
+
+decode_timestamp!(OffsetDateTime, unix);
+decode_timestamp!(PrimitiveDateTime, unix);
+decode_timestamp!(Date);
+decode_timestamp!(Time);
+
+#[cfg(test)]
+mod tests {
+    use ::time::macros::datetime;
+
+    use serde::Deserialize;
+
+    use crate::{
+        types::{Iso8601, UnixTimestamp},
+        Decode, SqlValue,
+    };
+
+    use super::*;
+
+    #[test]
+    fn unit_time_round_trip() {
+        let t = datetime!(2024-01-31 12:30:00.5 UTC);
+
+        let value = SqlValue::from(t);
+        assert_eq!(value, SqlValue::Text("2024-01-31T12:30:00.5Z".to_string()));
+        assert_eq!(Iso8601::<OffsetDateTime>::deserialize(&value).unwrap().0, t);
+
+        let t = datetime!(2024-01-31 12:30);
+        let value = SqlValue::from(t);
+        assert_eq!(value, SqlValue::Text("2024-01-31T12:30:00".to_string()));
+        assert_eq!(
+            Iso8601::<PrimitiveDateTime>::deserialize(&value).unwrap().0,
+            t
+        );
+
+        let value = SqlValue::from(t.date());
+        assert_eq!(Iso8601::<Date>::deserialize(&value).unwrap().0, t.date());
+
+        let value = SqlValue::from(t.time());
+        assert_eq!(value, SqlValue::Text("12:30:00".to_string()));
+        assert_eq!(Iso8601::<Time>::deserialize(&value).unwrap().0, t.time());
+
+        let precise = datetime!(2024-01-31 12:30:00.25);
+        let value = SqlValue::from(precise);
+        assert_eq!(value, SqlValue::Text("2024-01-31T12:30:00.25".to_string()));
+        assert_eq!(
+            Iso8601::<PrimitiveDateTime>::deserialize(&value).unwrap().0,
+            precise
+        );
+        assert_eq!(
+            SqlValue::from(precise.time()),
+            SqlValue::Text("12:30:00.25".to_string())
+        );
+
+        let value = SqlValue::from(UnixTimestamp(t));
+        assert_eq!(value, SqlValue::Integer(1_706_704_200));
+        assert_eq!(
+            UnixTimestamp::<PrimitiveDateTime>::deserialize(&value)
+                .unwrap()
+                .0,
+            t
+        );
+    }
+
+    #[test]
+    fn unit_time_iso8601() {
+        let value = SqlValue::Text("2024-01-31T12:30:00Z".to_string());
+        let Iso8601(t) = Iso8601::<OffsetDateTime>::deserialize(&value).unwrap();
+        assert_eq!(t, datetime!(2024-01-31 12:30 UTC));
+
+        for text in ["2024-01-31 12:30:00", "2024-01-31T12:30:00.0"] {
+            let value = SqlValue::Text(text.to_string());
+            let Iso8601(t) = Iso8601::<PrimitiveDateTime>::deserialize(&value).unwrap();
+            assert_eq!(t, datetime!(2024-01-31 12:30));
+        }
+
+        let value = SqlValue::Integer(1);
+        assert!(Iso8601::<Date>::deserialize(&value).is_err());
+    }
+
+    #[test]
+    fn unit_time_unix_timestamp() {
+        let value = SqlValue::Integer(1_706_704_200);
+        let UnixTimestamp(t) = UnixTimestamp::<OffsetDateTime>::deserialize(&value).unwrap();
+        assert_eq!(t, datetime!(2024-01-31 12:30 UTC));
+    }
+
+    #[test]
+    fn unit_time_decode() {
+        let t = datetime!(2024-01-31 12:30 UTC);
+
+        // Values are read back in the format they are written in
+        for value in [SqlValue::from(t), SqlValue::from(UnixTimestamp(t))] {
+            assert_eq!(OffsetDateTime::decode(&value).unwrap(), t);
+        }
+
+        let naive = datetime!(2024-01-31 12:30:00.5);
+        assert_eq!(
+            PrimitiveDateTime::decode(&SqlValue::from(naive)).unwrap(),
+            naive
+        );
+        assert_eq!(Date::decode(&SqlValue::from(t.date())).unwrap(), t.date());
+        assert_eq!(Time::decode(&SqlValue::from(t.time())).unwrap(), t.time());
+
+        // Written by `SQLite`'s `CURRENT_TIMESTAMP`
+        let value = SqlValue::Text("2024-01-31 12:30:00".to_string());
+        assert_eq!(
+            PrimitiveDateTime::decode(&value).unwrap(),
+            datetime!(2024-01-31 12:30)
+        );
+        assert_eq!(
+            Iso8601::<PrimitiveDateTime>::decode(&value).unwrap(),
+            Iso8601(datetime!(2024-01-31 12:30))
+        );
+
+        assert!(Time::decode(&SqlValue::Integer(1)).is_err());
+        assert!(Iso8601::<OffsetDateTime>::decode(&SqlValue::from(UnixTimestamp(t))).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+
+use ::uuid::Uuid;
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+use crate::{
+    decode::{self, decode_with_deserialize, Decode},
+    IntoTypedError, SqlValue,
+};
+
+/// A UUID stored as a 16 byte blob instead of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct UuidBlob(pub Uuid);
+
+impl From<Uuid> for SqlValue {
+    fn from(value: Uuid) -> Self {
+        Self::Text(value.hyphenated().to_string())
+    }
+}
+
+impl From<UuidBlob> for SqlValue {
+    fn from(value: UuidBlob) -> Self {
+        Self::Blob(value.0.as_bytes().to_vec())
+    }
+}
+
+struct BlobVisitor;
+
+impl<'de> Visitor<'de> for BlobVisitor {
+    type Value = Uuid;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a 16 byte blob")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Uuid, E> {
+        Uuid::from_slice(v).map_err(|_invalid_length| E::invalid_length(v.len(), &self))
+    }
+
+    // Blobs are returned as arrays of bytes if `blob_array` is set
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Uuid, A::Error> {
+        let mut bytes = Vec::with_capacity(16);
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+
+        self.visit_bytes(&bytes)
+    }
+}
+
+impl<'de> Deserialize<'de> for UuidBlob {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(BlobVisitor).map(Self)
+    }
+}
+
+decode_with_deserialize!(UuidBlob);
+
+// UUIDs written as blobs are read as well
+impl Decode<'_> for Uuid {
+    fn decode(value: &SqlValue) -> Result<Self, IntoTypedError> {
+        match value {
+            SqlValue::Blob(_) => UuidBlob::decode(value).map(|uuid| uuid.0),
+            _ => decode::deserialize(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SqlValue;
+
+    use super::*;
+
+    #[test]
+    fn unit_uuid_blob() {
+        let uuid = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+
+        let value = SqlValue::from(UuidBlob(uuid));
+        assert_eq!(value, SqlValue::Blob(uuid.as_bytes().to_vec()));
+        assert_eq!(UuidBlob::deserialize(&value).unwrap(), UuidBlob(uuid));
+
+        let value = SqlValue::Blob(vec![1, 2, 3]);
+        assert!(UuidBlob::deserialize(&value).is_err());
+
+        // Without a wrapper UUIDs are stored as text
+        let value = SqlValue::from(uuid);
+        assert_eq!(
+            value,
+            SqlValue::Text("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string())
+        );
+        assert_eq!(Uuid::deserialize(&value).unwrap(), uuid);
+    }
+
+    #[test]
+    fn unit_uuid_decode() {
+        let uuid = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+
+        assert_eq!(Uuid::decode(&SqlValue::from(uuid)).unwrap(), uuid);
+        assert_eq!(Uuid::decode(&SqlValue::from(UuidBlob(uuid))).unwrap(), uuid);
+        assert_eq!(
+            UuidBlob::decode(&SqlValue::from(UuidBlob(uuid))).unwrap(),
+            UuidBlob(uuid)
+        );
+        assert!(Uuid::decode(&SqlValue::Integer(1)).is_err());
+    }
+}
@@ -0,0 +1,324 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The type affinity of a column, derived from its declared type.
+/// See <https://www.sqlite.org/datatype3.html#determination_of_column_affinity>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Affinity {
+    Integer,
+    Text,
+    Blob,
+    Real,
+    Numeric,
+}
+
+impl Affinity {
+    fn from_type_data(type_data: &str) -> Self {
+        let type_data = type_data.to_uppercase();
+
+        if type_data.contains("INT") {
+            Self::Integer
+        } else if ["CHAR", "CLOB", "TEXT"]
+            .iter()
+            .any(|t| type_data.contains(t))
+        {
+            Self::Text
+        } else if type_data.contains("BLOB") || type_data.is_empty() {
+            Self::Blob
+        } else if ["REAL", "FLOA", "DOUB"]
+            .iter()
+            .any(|t| type_data.contains(t))
+        {
+            Self::Real
+        } else {
+            Self::Numeric
+        }
+    }
+}
+
+/// A value as stored by `SQLite`.
+///
+/// Blobs returned as base64 encoded strings are only decoded when the `fast-blob` feature is
+/// enabled, otherwise they are kept as [`SqlValue::Text`]. Blobs returned as arrays of bytes
+/// (`blob_array`) are always decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl SqlValue {
+    /// Converts a JSON value returned by rqlite into a [`SqlValue`], using the declared type
+    /// of its column to tell integers from reals and text from blobs.
+    #[must_use]
+    pub fn from_json(value: Value, type_data: &str) -> Self {
+        // Every JSON value has a representation, so deserializing from a `Value` can not fail
+        Self::deserialize(value)
+            .unwrap_or(Self::Null)
+            .with_type_data(type_data)
+    }
+
+    /// Applies the declared type of the column a value belongs to.
+    ///
+    /// rqlite encodes whole reals without a fractional part, so integers in columns with REAL
+    /// affinity are converted to reals. With the `fast-blob` feature enabled, base64 encoded text
+    /// in columns declared as blobs is decoded.
+    #[must_use]
+    pub fn with_type_data(self, type_data: &str) -> Self {
+        match self {
+            #[expect(
+                clippy::cast_precision_loss,
+                reason = "SQLite converts integers to reals the same way"
+            )]
+            Self::Integer(i) if Affinity::from_type_data(type_data) == Affinity::Real => {
+                Self::Real(i as f64)
+            }
+            Self::Text(s) => Self::from_text(s, type_data),
+            value => value,
+        }
+    }
+
+    /// Blobs are returned as base64 encoded strings unless `blob_array` is set.
+    /// Only columns declared as blobs are decoded, as any other text could be valid base64.
+    #[cfg(feature = "fast-blob")]
+    fn from_text(s: String, type_data: &str) -> Self {
+        if type_data.to_uppercase().contains("BLOB") {
+            if let Ok(blob) = crate::decode::decode_blob(&s) {
+                return Self::Blob(blob);
+            }
+        }
+
+        Self::Text(s)
+    }
+
+    #[cfg(not(feature = "fast-blob"))]
+    #[expect(
+        clippy::missing_const_for_fn,
+        reason = "not const when the fast-blob feature is enabled"
+    )]
+    fn from_text(s: String, _type_data: &str) -> Self {
+        Self::Text(s)
+    }
+
+    /// Converts the value back into the JSON representation used by rqlite.
+    /// Blobs are encoded the same way rqlite returns them.
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Null => Value::Null,
+            Self::Integer(i) => Value::from(*i),
+            Self::Real(f) => serde_json::Number::from_f64(*f).map_or(Value::Null, Value::Number),
+            Self::Text(s) => Value::String(s.clone()),
+            #[cfg(feature = "fast-blob")]
+            Self::Blob(b) => {
+                use base64::{engine::general_purpose, Engine};
+
+                Value::String(general_purpose::STANDARD.encode(b))
+            }
+            #[cfg(not(feature = "fast-blob"))]
+            Self::Blob(b) => Value::from(b.as_slice()),
+        }
+    }
+
+    /// Returns `true` if the value is `NULL`.
+    #[must_use]
+    pub const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns the value if it is an integer.
+    #[must_use]
+    pub const fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a real or an integer.
+    #[must_use]
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "SQLite converts integers to reals the same way"
+    )]
+    pub const fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Real(f) => Some(*f),
+            Self::Integer(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is text.
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a blob.
+    #[must_use]
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Blob(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the storage class of the value.
+    #[must_use]
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            Self::Null => "NULL",
+            Self::Integer(_) => "INTEGER",
+            Self::Real(_) => "REAL",
+            Self::Text(_) => "TEXT",
+            Self::Blob(_) => "BLOB",
+        }
+    }
+}
+
+impl From<i64> for SqlValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<f64> for SqlValue {
+    fn from(value: f64) -> Self {
+        Self::Real(value)
+    }
+}
+
+impl From<String> for SqlValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for SqlValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<Vec<u8>> for SqlValue {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Blob(value)
+    }
+}
+
+impl<T> From<Option<T>> for SqlValue
+where
+    T: Into<Self>,
+{
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn unit_affinity() {
+        assert_eq!(Affinity::from_type_data("integer"), Affinity::Integer);
+        assert_eq!(Affinity::from_type_data("BIGINT"), Affinity::Integer);
+        assert_eq!(Affinity::from_type_data("varchar(255)"), Affinity::Text);
+        assert_eq!(Affinity::from_type_data("blob"), Affinity::Blob);
+        assert_eq!(Affinity::from_type_data(""), Affinity::Blob);
+        assert_eq!(Affinity::from_type_data("double precision"), Affinity::Real);
+        assert_eq!(Affinity::from_type_data("decimal(10,5)"), Affinity::Numeric);
+    }
+
+    #[test]
+    fn unit_sql_value_from_json() {
+        assert_eq!(SqlValue::from_json(json!(null), "integer"), SqlValue::Null);
+        assert_eq!(
+            SqlValue::from_json(json!(1), "integer"),
+            SqlValue::Integer(1)
+        );
+        assert_eq!(SqlValue::from_json(json!(1), "real"), SqlValue::Real(1.0));
+        assert_eq!(
+            SqlValue::from_json(json!(1.5), "numeric"),
+            SqlValue::Real(1.5)
+        );
+        assert_eq!(
+            SqlValue::from_json(json!(true), "boolean"),
+            SqlValue::Integer(1)
+        );
+        assert_eq!(
+            SqlValue::from_json(json!("foo"), "text"),
+            SqlValue::Text("foo".to_string())
+        );
+        assert_eq!(
+            SqlValue::from_json(json!([1, 2, 255]), "blob"),
+            SqlValue::Blob(vec![1, 2, 255])
+        );
+        assert_eq!(
+            SqlValue::from_json(json!([1, 256]), "blob"),
+            SqlValue::Text("[1,256]".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "fast-blob")]
+    fn unit_sql_value_from_json_base64() {
+        assert_eq!(
+            SqlValue::from_json(json!("SGVsbG8="), "blob"),
+            SqlValue::Blob(b"Hello".to_vec())
+        );
+        assert_eq!(
+            SqlValue::from_json(json!("SGVsbG8="), "text"),
+            SqlValue::Text("SGVsbG8=".to_string())
+        );
+    }
+
+    #[test]
+    fn unit_sql_value_with_type_data() {
+        assert_eq!(
+            SqlValue::Integer(2).with_type_data("double"),
+            SqlValue::Real(2.0)
+        );
+        assert_eq!(
+            SqlValue::Integer(2).with_type_data("numeric"),
+            SqlValue::Integer(2)
+        );
+        assert_eq!(SqlValue::Null.with_type_data("real"), SqlValue::Null);
+    }
+
+    #[test]
+    fn unit_sql_value_to_json() {
+        for value in [json!(null), json!(1), json!(1.5), json!("foo")] {
+            assert_eq!(SqlValue::from_json(value.clone(), "").to_json(), value);
+        }
+
+        let blob = SqlValue::Blob(b"Hello".to_vec());
+
+        #[cfg(feature = "fast-blob")]
+        assert_eq!(blob.to_json(), json!("SGVsbG8="));
+        #[cfg(not(feature = "fast-blob"))]
+        assert_eq!(blob.to_json(), json!([72, 101, 108, 108, 111]));
+    }
+
+    #[test]
+    fn unit_sql_value_accessors() {
+        assert!(SqlValue::Null.is_null());
+        assert_eq!(SqlValue::Integer(2).as_i64(), Some(2));
+        assert_eq!(SqlValue::Integer(2).as_f64(), Some(2.0));
+        assert_eq!(SqlValue::Real(2.5).as_f64(), Some(2.5));
+        assert_eq!(SqlValue::Real(2.5).as_i64(), None);
+        assert_eq!(SqlValue::from("foo").as_str(), Some("foo"));
+        assert_eq!(SqlValue::from(vec![1u8]).as_bytes(), Some([1u8].as_slice()));
+        assert_eq!(SqlValue::from(None::<i64>), SqlValue::Null);
+        assert_eq!(SqlValue::Text(String::new()).type_name(), "TEXT");
+    }
+}
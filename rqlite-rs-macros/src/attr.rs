@@ -0,0 +1,353 @@
+//! Parsing of `#[rqlite(...)]` attributes.
+
+use proc_macro2::Span;
+use syn::{spanned::Spanned, Attribute, Expr, Ident, LitStr, Path, Type};
+
+const ATTRIBUTE: &str = "rqlite";
+
+/// How field names are converted into column names.
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    const RULES: [(&'static str, Self); 8] = [
+        ("lowercase", Self::Lower),
+        ("UPPERCASE", Self::Upper),
+        ("PascalCase", Self::Pascal),
+        ("camelCase", Self::Camel),
+        ("snake_case", Self::Snake),
+        ("SCREAMING_SNAKE_CASE", Self::ScreamingSnake),
+        ("kebab-case", Self::Kebab),
+        ("SCREAMING-KEBAB-CASE", Self::ScreamingKebab),
+    ];
+
+    fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        let value = lit.value();
+
+        Self::RULES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| {
+                let names = Self::RULES.map(|(name, _)| format!("\"{name}\""));
+                syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "unknown rename rule `{value}`, expected one of {}",
+                        names.join(", ")
+                    ),
+                )
+            })
+    }
+
+    /// Applies the rule to a `snake_case` field name.
+    pub fn apply(self, field: &str) -> String {
+        let field = field.trim_start_matches("r#");
+
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+            Self::Pascal | Self::Camel => {
+                let mut renamed = String::with_capacity(field.len());
+                let mut capitalize = matches!(self, Self::Pascal);
+
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        renamed.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        renamed.push(c);
+                    }
+                }
+
+                renamed
+            }
+        }
+    }
+}
+
+/// Attributes on the struct itself.
+#[derive(Default)]
+pub struct ContainerAttrs {
+    pub rename_all: Option<RenameRule>,
+}
+
+impl ContainerAttrs {
+    pub fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut container = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident(ATTRIBUTE)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    if container.rename_all.is_some() {
+                        return Err(meta.error("duplicate `rename_all` attribute"));
+                    }
+                    let lit: LitStr = meta.value()?.parse()?;
+                    container.rename_all = Some(RenameRule::from_lit(&lit)?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown container attribute, expected `rename_all`"))
+                }
+            })?;
+        }
+
+        Ok(container)
+    }
+}
+
+/// Attributes on a single field.
+#[derive(Default)]
+pub struct FieldAttrs {
+    pub rename: Option<LitStr>,
+    pub default: bool,
+    pub skip: bool,
+    pub try_from: Option<Type>,
+    pub with: Option<Path>,
+    default_span: Option<Span>,
+}
+
+impl FieldAttrs {
+    pub fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut field = Self::default();
+        let mut skip_span = None;
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident(ATTRIBUTE)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    if field.rename.is_some() {
+                        return Err(meta.error("duplicate `rename` attribute"));
+                    }
+                    field.rename = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("default") {
+                    if field.default {
+                        return Err(meta.error("duplicate `default` attribute"));
+                    }
+                    field.default = true;
+                    field.default_span = Some(meta.path.span());
+                } else if meta.path.is_ident("skip") {
+                    if field.skip {
+                        return Err(meta.error("duplicate `skip` attribute"));
+                    }
+                    field.skip = true;
+                    skip_span = Some(meta.path.span());
+                } else if meta.path.is_ident("try_from") {
+                    if field.try_from.is_some() {
+                        return Err(meta.error("duplicate `try_from` attribute"));
+                    }
+                    let lit: LitStr = meta.value()?.parse()?;
+                    field.try_from = Some(lit.parse()?);
+                } else if meta.path.is_ident("with") {
+                    if field.with.is_some() {
+                        return Err(meta.error("duplicate `with` attribute"));
+                    }
+                    field.with = Some(parse_path(meta.value()?.parse()?)?);
+                } else if meta.path.is_ident("rename_all") {
+                    return Err(meta.error("`rename_all` can only be used on the struct"));
+                } else {
+                    return Err(meta.error(
+                        "unknown field attribute, expected one of `rename`, `default`, `skip`, `try_from`, `with`",
+                    ));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        if let (Some(try_from), Some(_)) = (&field.try_from, &field.with) {
+            return Err(syn::Error::new(
+                try_from.span(),
+                "`try_from` and `with` can not be used together",
+            ));
+        }
+
+        if let Some(span) = skip_span {
+            if field.rename.is_some()
+                || field.default
+                || field.try_from.is_some()
+                || field.with.is_some()
+            {
+                return Err(syn::Error::new(
+                    span,
+                    "`skip` can not be combined with other attributes",
+                ));
+            }
+        }
+
+        Ok(field)
+    }
+}
+
+impl FieldAttrs {
+    /// Rejects the attributes that only affect decoding, for derives that encode values.
+    pub fn reject_decode_attrs(&self, derive: &str) -> syn::Result<()> {
+        let attrs = [
+            ("with", self.with.as_ref().map(Spanned::span)),
+            ("try_from", self.try_from.as_ref().map(Spanned::span)),
+            ("default", self.default_span),
+        ];
+
+        collect_errors(attrs.into_iter().map(|(name, span)| {
+            span.map_or(Ok(()), |span| {
+                Err(syn::Error::new(
+                    span,
+                    format!("`{name}` is not supported by `#[derive({derive})]`"),
+                ))
+            })
+        }))
+        .map(drop)
+    }
+
+    /// Returns the name of the column a named field maps to.
+    pub fn column_name(&self, ident: &Ident, container: &ContainerAttrs) -> String {
+        self.rename.as_ref().map_or_else(
+            || {
+                let name = ident.to_string();
+                container
+                    .rename_all
+                    .map_or_else(|| name.clone(), |rule| rule.apply(&name))
+            },
+            LitStr::value,
+        )
+    }
+}
+
+/// Collects the results of all fields, so every invalid attribute is reported at once.
+pub fn collect_errors<T>(results: impl Iterator<Item = syn::Result<T>>) -> syn::Result<Vec<T>> {
+    let mut values = Vec::new();
+    let mut error: Option<syn::Error> = None;
+
+    for result in results {
+        match (result, &mut error) {
+            (Ok(value), _) => values.push(value),
+            (Err(e), Some(error)) => error.combine(e),
+            (Err(e), None) => error = Some(e),
+        }
+    }
+
+    error.map_or(Ok(values), Err)
+}
+
+/// Accepts both `with = path` and `with = "path"`.
+fn parse_path(expr: Expr) -> syn::Result<Path> {
+    match expr {
+        Expr::Path(path) => Ok(path.path),
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => lit.parse(),
+        expr => Err(syn::Error::new(
+            expr.span(),
+            "expected a path to a function",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn unit_rename_rule_apply() {
+        let field = "created_at_utc";
+
+        assert_eq!(RenameRule::Lower.apply(field), "created_at_utc");
+        assert_eq!(RenameRule::Upper.apply(field), "CREATED_AT_UTC");
+        assert_eq!(RenameRule::Pascal.apply(field), "CreatedAtUtc");
+        assert_eq!(RenameRule::Camel.apply(field), "createdAtUtc");
+        assert_eq!(RenameRule::ScreamingSnake.apply(field), "CREATED_AT_UTC");
+        assert_eq!(RenameRule::Kebab.apply(field), "created-at-utc");
+        assert_eq!(RenameRule::ScreamingKebab.apply(field), "CREATED-AT-UTC");
+        assert_eq!(RenameRule::Camel.apply("r#type"), "type");
+    }
+
+    #[test]
+    fn unit_field_attrs() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[rqlite(rename = "col", default)]),
+            parse_quote!(#[rqlite(try_from = "i64")]),
+        ];
+
+        let field = FieldAttrs::from_attrs(&attrs).unwrap();
+        assert_eq!(field.rename.unwrap().value(), "col");
+        assert!(field.default);
+        assert!(!field.skip);
+        assert!(field.try_from.is_some());
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[rqlite(with = my::decode)])];
+        assert!(FieldAttrs::from_attrs(&attrs).unwrap().with.is_some());
+    }
+
+    #[test]
+    fn unit_field_attrs_errors() {
+        let error = |attr: Attribute| {
+            FieldAttrs::from_attrs(&[attr])
+                .err()
+                .map(|e| e.to_string())
+                .unwrap_or_default()
+        };
+
+        assert!(error(parse_quote!(#[rqlite(renamed = "col")])).starts_with("unknown field"));
+        assert_eq!(
+            error(parse_quote!(#[rqlite(skip, rename = "col")])),
+            "`skip` can not be combined with other attributes"
+        );
+        assert_eq!(
+            error(parse_quote!(#[rqlite(try_from = "i64", with = decode)])),
+            "`try_from` and `with` can not be used together"
+        );
+        assert_eq!(
+            error(parse_quote!(#[rqlite(default, default)])),
+            "duplicate `default` attribute"
+        );
+    }
+
+    #[test]
+    fn unit_field_attrs_reject_decode_attrs() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[rqlite(rename = "col")])];
+        assert!(FieldAttrs::from_attrs(&attrs)
+            .unwrap()
+            .reject_decode_attrs("ToArgs")
+            .is_ok());
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[rqlite(default, with = decode)])];
+        let error = FieldAttrs::from_attrs(&attrs)
+            .unwrap()
+            .reject_decode_attrs("ToArgs")
+            .unwrap_err();
+
+        let messages = error.into_iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "`with` is not supported by `#[derive(ToArgs)]`",
+                "`default` is not supported by `#[derive(ToArgs)]`",
+            ]
+        );
+    }
+
+    #[test]
+    fn unit_container_attrs() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[rqlite(rename_all = "camelCase")])];
+        let container = ContainerAttrs::from_attrs(&attrs).unwrap();
+        assert_eq!(container.rename_all.unwrap().apply("user_id"), "userId");
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[rqlite(rename_all = "camel")])];
+        let error = ContainerAttrs::from_attrs(&attrs).err().unwrap();
+        assert!(error.to_string().starts_with("unknown rename rule `camel`"));
+    }
+}
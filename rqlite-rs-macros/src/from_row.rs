@@ -0,0 +1,179 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use syn::{DeriveInput, Field, Fields, Type};
+
+use crate::{
+    attr::{collect_errors, ContainerAttrs, FieldAttrs},
+    field_type::FieldType,
+};
+
+/// Where the value of a field is read from.
+enum Column {
+    Name(String),
+    Index(usize),
+}
+
+impl Column {
+    fn key(&self) -> TokenStream {
+        match self {
+            Self::Name(name) => quote!(#name),
+            Self::Index(index) => {
+                let index = Literal::usize_unsuffixed(*index);
+                quote!(#index)
+            }
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Self::Name(name) => name.clone(),
+            Self::Index(index) => index.to_string(),
+        }
+    }
+
+    fn getters(&self) -> (TokenStream, TokenStream, TokenStream) {
+        match self {
+            Self::Name(_) => (quote!(get), quote!(get_opt), quote!(get_value)),
+            Self::Index(_) => (
+                quote!(get_by_index),
+                quote!(get_by_index_opt),
+                quote!(get_value_by_index),
+            ),
+        }
+    }
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "Only structs with named fields are supported for `#[derive(FromRow)]`",
+        ));
+    };
+
+    let container = ContainerAttrs::from_attrs(&input.attrs)?;
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &data.fields {
+        Fields::Named(fields) => {
+            let values = collect_errors(fields.named.iter().map(|field| {
+                let attrs = FieldAttrs::from_attrs(&field.attrs)?;
+                let Some(ident) = field.ident.as_ref() else {
+                    return Err(syn::Error::new_spanned(field, "Expected named field"));
+                };
+
+                let column = attrs.column_name(ident, &container);
+
+                let value = field_value(field, &attrs, &Column::Name(column));
+                Ok(quote!(#ident: #value))
+            }))?;
+
+            quote!(#struct_name { #(#values),* })
+        }
+        Fields::Unnamed(fields) => {
+            if container.rename_all.is_some() {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "`rename_all` is only supported on structs with named fields",
+                ));
+            }
+
+            let values =
+                collect_errors(fields.unnamed.iter().enumerate().map(|(index, field)| {
+                    let attrs = FieldAttrs::from_attrs(&field.attrs)?;
+
+                    if let Some(rename) = &attrs.rename {
+                        return Err(syn::Error::new(
+                            rename.span(),
+                            "`rename` is only supported on named fields",
+                        ));
+                    }
+
+                    Ok(field_value(field, &attrs, &Column::Index(index)))
+                }))?;
+
+            quote!(#struct_name(#(#values),*))
+        }
+        Fields::Unit => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "Only structs with named fields are supported for `#[derive(FromRow)]`",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics rqlite_rs::FromRow for #struct_name #ty_generics #where_clause {
+            fn from_row(row: rqlite_rs::Row) -> Result<Self, rqlite_rs::IntoTypedError> {
+                Ok(#body)
+            }
+        }
+    })
+}
+
+fn field_value(field: &Field, attrs: &FieldAttrs, column: &Column) -> TokenStream {
+    if attrs.skip {
+        return quote!(::core::default::Default::default());
+    }
+
+    let ty = &field.ty;
+    let key = column.key();
+    let (get, get_opt, get_value) = column.getters();
+
+    if let Some(with) = &attrs.with {
+        return if attrs.default {
+            quote! {
+                match row.#get_value(#key) {
+                    Ok(value) if !value.is_null() => #with(value)?,
+                    _ => ::core::default::Default::default(),
+                }
+            }
+        } else {
+            quote!(#with(row.#get_value(#key)?)?)
+        };
+    }
+
+    if let Some(source) = &attrs.try_from {
+        let label = column.label();
+        let convert = quote! {
+            |value: #source| <#ty as ::core::convert::TryFrom<#source>>::try_from(value)
+                .map_err(|e| rqlite_rs::IntoTypedError::TryFromError(#label.to_string(), e.to_string()))
+        };
+
+        return if attrs.default {
+            quote! {
+                match row.#get_opt::<#source>(#key)? {
+                    Some(value) => (#convert)(value)?,
+                    None => ::core::default::Default::default(),
+                }
+            }
+        } else {
+            quote!((#convert)(row.#get::<#source>(#key)?)?)
+        };
+    }
+
+    if attrs.default {
+        return quote!(row.#get_opt(#key)?.unwrap_or_default());
+    }
+
+    let field_type = match ty {
+        Type::Path(type_path) => FieldType::from_type_path(type_path),
+        _ => FieldType::Normal,
+    };
+
+    match field_type {
+        FieldType::Option => quote!(row.#get_opt(#key)?),
+        FieldType::Blob => {
+            #[cfg(feature = "fast-blob")]
+            quote! {
+                rqlite_rs::decode::decode_blob(&row.#get::<String>(#key)?)?
+            }
+            #[cfg(not(feature = "fast-blob"))]
+            quote! {
+                row.#get(#key)?
+            }
+        }
+        FieldType::Normal => quote!(row.#get(#key)?),
+    }
+}
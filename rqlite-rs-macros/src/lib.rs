@@ -1,117 +1,130 @@
 #![warn(clippy::pedantic, clippy::all)]
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Type};
+use syn::{parse_macro_input, DeriveInput};
 
+mod attr;
 mod field_type;
+mod from_row;
+mod migrate;
+#[cfg(feature = "query-checked")]
+mod query_checked;
+mod to_args;
 
-#[proc_macro_derive(FromRow)]
-/// Derives the `FromRow` trait for a struct.
+/// Embeds the migrations of a directory into the binary and returns a
+/// `rqlite_rs::migrate::Migrator`.
 ///
-/// # Panics
+/// The path is relative to the crate's `Cargo.toml` and defaults to `./migrations`.
+/// Files are named `<version>_<description>.sql` or `<version>_<description>.up.sql` and
+/// `<version>_<description>.down.sql`. Invalid file names result in a compile error.
 ///
-/// This function will panic if the field name cannot be converted to a string.
-pub fn derive_from_row(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+/// ```ignore
+/// let migrator = rqlite_rs::migrate!("./migrations");
+/// migrator.run(&client).await?;
+/// ```
+#[proc_macro]
+pub fn migrate(input: TokenStream) -> TokenStream {
+    let path = if input.is_empty() {
+        None
+    } else {
+        Some(parse_macro_input!(input as syn::LitStr))
+    };
 
-    if let syn::Data::Struct(data) = &input.data {
-        if let syn::Fields::Named(fields) = &data.fields {
-            let field_vals = fields.named.iter().map(|field| {
-                let Some(field_name) = field.ident.as_ref() else {
-                    return syn::Error::new_spanned(
-                        field,
-                        "Expected named field"
-                    ).to_compile_error();
-                };
-                let field_name_string = field_name.to_string();
-
-                if let Type::Path(type_path) = &field.ty {
-                    match field_type::FieldType::from_type_path(type_path) {
-                        field_type::FieldType::Option => quote! {
-                            #field_name: row.get_opt(#field_name_string)?
-                        },
-                        field_type::FieldType::Blob => {
-                            #[cfg(feature = "fast-blob")]
-                            quote! {
-                                #field_name: rqlite_rs::decode::decode_blob(&row.get::<String>(#field_name_string)?)?
-                            }
-                            #[cfg(not(feature = "fast-blob"))]
-                            quote! {
-                                #field_name: row.get(#field_name_string)?
-                            }
-                        },
-                        field_type::FieldType::Normal => quote! {
-                            #field_name: row.get(#field_name_string)?
-                        },
-                    }
-                } else { quote! {
-                    #field_name: row.get(#field_name_string)?
-                } }
-            });
-
-            let struct_name = &input.ident;
+    migrate::expand(path.as_ref())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-            return TokenStream::from(quote!(
-                impl rqlite_rs::FromRow for #struct_name {
-                    fn from_row(row: rqlite_rs::Row) -> Result<Self, rqlite_rs::IntoTypedError> {
-                        Ok(#struct_name {
-                            #(#field_vals),*
-                        })
-                    }
-                }
-            ));
-        }
+/// Creates a query like `rqlite_rs::query!`, but checks it at compile time against a schema.
+///
+/// The schema is a file with SQL statements, like `CREATE TABLE` statements or a dump created
+/// with `RqliteClient::backup`. It is loaded into an in-memory `SQLite` database, which is used
+/// to prepare the query, so no connection to rqlite is needed. Unknown tables and columns,
+/// syntax errors and arguments that don't match the placeholders result in compile errors.
+///
+/// The schema is read from `rqlite-schema.sql` next to the crate's `Cargo.toml`, or from the
+/// path given as `schema = "..."` before the query, relative to the same directory.
+///
+/// ```ignore
+/// let query = rqlite_rs::query_checked!("SELECT name FROM users WHERE id = ?", 1)?;
+/// let query = rqlite_rs::query_checked!(
+///     schema = "db/schema.sql",
+///     "INSERT INTO users (id, name) VALUES (:id, :name)",
+///     id = 1,
+///     name = "test",
+/// )?;
+/// ```
+#[cfg(feature = "query-checked")]
+#[proc_macro]
+pub fn query_checked(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as query_checked::QueryInput);
 
-        if let syn::Fields::Unnamed(fields) = &data.fields {
-            let field_vals = fields.unnamed.iter().enumerate().map(|(index, field)| {
-                let index = syn::Index::from(index);
+    query_checked::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-                if let Type::Path(type_path) = &field.ty {
-                    match field_type::FieldType::from_type_path(type_path) {
-                        field_type::FieldType::Option => quote! {
-                            row.get_by_index_opt(#index)?
-                        },
-                        field_type::FieldType::Blob => {
-                            #[cfg(feature = "fast-blob")]
-                            quote! {
-                                rqlite_rs::decode::decode_blob(&row.get_by_index::<String>(#index)?)?
-                            }
-                            #[cfg(not(feature = "fast-blob"))]
-                            quote! {
-                                row.get_by_index(#index)?
-                            }
-                        },
-                        field_type::FieldType::Normal => quote! {
-                            row.get_by_index(#index)?
-                        },
-                    }
-                } else {
-                    quote! {
-                        row.get_by_index(#index)?
-                    }
-                }
-            });
+/// Derives the `FromRow` trait for a struct.
+///
+/// Fields are read from the column with the same name, or by index for tuple structs, and
+/// decoded with `rqlite_rs::decode::Decode`. This can be customized with `#[rqlite(...)]`
+/// attributes:
+///
+/// - `#[rqlite(rename_all = "camelCase")]` on the struct converts all field names using one of
+///   `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`,
+///   `kebab-case` or `SCREAMING-KEBAB-CASE`.
+/// - `#[rqlite(rename = "column")]` reads the field from a differently named column.
+/// - `#[rqlite(default)]` uses `Default::default()` if the column is missing or `NULL`.
+/// - `#[rqlite(skip)]` does not read the field at all and uses `Default::default()`.
+/// - `#[rqlite(try_from = "i64")]` reads the column as the given type and converts it with
+///   `TryFrom`.
+/// - `#[rqlite(with = path)]` decodes the column with a function taking a `&SqlValue` and
+///   returning a `Result` whose error converts into `IntoTypedError`.
+///   `rqlite_rs::decode::deserialize` decodes types that only implement `Deserialize`.
+///
+/// ```ignore
+/// #[derive(FromRow)]
+/// #[rqlite(rename_all = "camelCase")]
+/// struct User {
+///     user_id: i64,
+///     #[rqlite(rename = "display_name")]
+///     name: String,
+///     #[rqlite(try_from = "i64")]
+///     age: u8,
+///     #[rqlite(skip)]
+///     cached: Option<String>,
+/// }
+/// ```
+#[proc_macro_derive(FromRow, attributes(rqlite))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
 
-            let struct_name = &input.ident;
+    from_row::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-            return TokenStream::from(quote!(
-                impl rqlite_rs::FromRow for #struct_name {
-                    fn from_row(row: rqlite_rs::Row) -> Result<Self, rqlite_rs::IntoTypedError> {
-                        Ok(#struct_name(
-                            #(#field_vals),*
-                        ))
-                    }
-                }
-            ));
-        }
-    }
+/// Derives the `ToArgs` trait for a struct with named fields.
+///
+/// Every field becomes an argument for the column with the same name, encoded with
+/// `RqliteArgumentRaw`. Columns are named using the same `rename_all` and `rename` attributes
+/// as `#[derive(FromRow)]` and fields marked with `#[rqlite(skip)]` are left out.
+/// The `default`, `try_from` and `with` attributes only affect decoding and are rejected.
+///
+/// ```ignore
+/// #[derive(ToArgs)]
+/// struct User {
+///     id: i64,
+///     name: String,
+/// }
+///
+/// client.exec(user.insert_query("users")).await?;
+/// ```
+#[proc_macro_derive(ToArgs, attributes(rqlite))]
+pub fn derive_to_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
 
-    TokenStream::from(
-        syn::Error::new(
-            input.ident.span(),
-            "Only structs with named fields are supported for `#[derive(FromRow)]`",
-        )
-        .to_compile_error(),
-    )
+    to_args::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
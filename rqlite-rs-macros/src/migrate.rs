@@ -0,0 +1,102 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+const DEFAULT_PATH: &str = "./migrations";
+
+struct MigrationFiles {
+    description: String,
+    up: PathBuf,
+    down: Option<PathBuf>,
+}
+
+pub fn expand(path: Option<&LitStr>) -> syn::Result<TokenStream> {
+    let span = path.map_or_else(proc_macro2::Span::call_site, LitStr::span);
+    let relative = path.map_or_else(|| DEFAULT_PATH.to_string(), LitStr::value);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|e| syn::Error::new(span, format!("CARGO_MANIFEST_DIR is not set: {e}")))?;
+    let dir = Path::new(&manifest_dir).join(relative);
+
+    let migrations = read_migrations(&dir).map_err(|e| syn::Error::new(span, e))?;
+
+    let migrations = migrations.into_iter().map(|(version, files)| {
+        let description = files.description;
+        let up = files.up.to_string_lossy().to_string();
+        let down = files.down.map(|down| {
+            let down = down.to_string_lossy().to_string();
+            quote! { .with_down(include_str!(#down)) }
+        });
+
+        quote! {
+            rqlite_rs::migrate::Migration::new(#version, #description, include_str!(#up)) #down
+        }
+    });
+
+    Ok(quote! {
+        rqlite_rs::migrate::Migrator::new(vec![#(#migrations),*])
+    })
+}
+
+fn read_migrations(dir: &Path) -> Result<BTreeMap<i64, MigrationFiles>, String> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read migrations from {}: {e}", dir.display()))?;
+
+    let mut ups = BTreeMap::new();
+    let mut downs = HashMap::new();
+
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+
+        let Some(stem) = file_name.strip_suffix(".sql") else {
+            continue;
+        };
+
+        let (stem, is_down) = stem.strip_suffix(".down").map_or_else(
+            || (stem.strip_suffix(".up").unwrap_or(stem), false),
+            |stem| (stem, true),
+        );
+
+        let invalid = || format!("Invalid migration file name: {file_name}");
+        let (version, description) = stem.split_once('_').ok_or_else(invalid)?;
+        let version = version.parse::<i64>().map_err(|_parse_error| invalid())?;
+
+        let duplicate = if is_down {
+            downs.insert(version, path).is_some()
+        } else {
+            ups.insert(version, (description.replace('_', " "), path))
+                .is_some()
+        };
+
+        if duplicate {
+            return Err(format!("Duplicate migration version: {version}"));
+        }
+    }
+
+    if let Some(version) = downs.keys().find(|version| !ups.contains_key(version)) {
+        return Err(format!(
+            "Down migration {version} has no matching up migration"
+        ));
+    }
+
+    Ok(ups
+        .into_iter()
+        .map(|(version, (description, up))| {
+            let files = MigrationFiles {
+                description,
+                up,
+                down: downs.remove(&version),
+            };
+            (version, files)
+        })
+        .collect())
+}
@@ -0,0 +1,290 @@
+//! Compile-time checking of queries against a schema file.
+
+use std::{
+    cell::RefCell,
+    collections::{hash_map::Entry, HashMap},
+    path::Path,
+};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use rusqlite::Connection;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Expr, Ident, LitStr, Token,
+};
+
+const DEFAULT_SCHEMA: &str = "rqlite-schema.sql";
+
+thread_local! {
+    // The proc-macro server is reused for every invocation, so each schema is only loaded once.
+    // Keying by the contents picks up changes to the file.
+    static SCHEMAS: RefCell<HashMap<String, Connection>> = RefCell::new(HashMap::new());
+}
+
+pub struct QueryInput {
+    schema: Option<LitStr>,
+    sql: LitStr,
+    args: Punctuated<Expr, Token![,]>,
+}
+
+impl Parse for QueryInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let schema = if input.peek(Ident) && input.peek2(Token![=]) {
+            let ident: Ident = input.parse()?;
+            if ident != "schema" {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected `schema = \"path\"` or a query string",
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            let schema = input.parse()?;
+            input.parse::<Token![,]>()?;
+            Some(schema)
+        } else {
+            None
+        };
+
+        let sql = input.parse()?;
+        let args = if input.is_empty() {
+            Punctuated::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            Punctuated::parse_terminated(input)?
+        };
+
+        Ok(Self { schema, sql, args })
+    }
+}
+
+/// The arguments passed to the macro, either all positional or all named.
+enum Arguments {
+    Positional(usize),
+    Named(Vec<Ident>),
+}
+
+impl Arguments {
+    fn from_exprs(args: &Punctuated<Expr, Token![,]>) -> syn::Result<Self> {
+        let named = args
+            .iter()
+            .map(|arg| match arg {
+                Expr::Assign(assign) => match &*assign.left {
+                    Expr::Path(path) => path.path.get_ident().cloned(),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if named.iter().all(Option::is_none) {
+            return Ok(Self::Positional(args.len()));
+        }
+
+        named
+            .into_iter()
+            .zip(args)
+            .map(|(name, arg)| {
+                name.ok_or_else(|| {
+                    syn::Error::new(
+                        arg.span(),
+                        "positional and named arguments can not be mixed",
+                    )
+                })
+            })
+            .collect::<syn::Result<_>>()
+            .map(Self::Named)
+    }
+}
+
+pub fn expand(input: &QueryInput) -> syn::Result<TokenStream> {
+    let schema_span = input
+        .schema
+        .as_ref()
+        .map_or_else(|| input.sql.span(), LitStr::span);
+    let relative = input
+        .schema
+        .as_ref()
+        .map_or_else(|| DEFAULT_SCHEMA.to_string(), LitStr::value);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|e| syn::Error::new(schema_span, format!("CARGO_MANIFEST_DIR is not set: {e}")))?;
+    let path = Path::new(&manifest_dir).join(relative);
+
+    let arguments = Arguments::from_exprs(&input.args)?;
+
+    with_schema(&path, |conn| check(conn, &input.sql, &arguments))
+        .map_err(|e| syn::Error::new(schema_span, e))??;
+
+    let path = path.to_string_lossy();
+    let sql = &input.sql;
+    let args = input.args.iter();
+
+    Ok(quote! {{
+        // Rebuild when the schema changes
+        const _: &str = include_str!(#path);
+        rqlite_rs::query!(#sql #(, #args)*)
+    }})
+}
+
+fn with_schema<T>(path: &Path, f: impl FnOnce(&Connection) -> T) -> Result<T, String> {
+    let schema = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read schema from {}: {e}", path.display()))?;
+
+    SCHEMAS.with(|schemas| {
+        let mut schemas = schemas.borrow_mut();
+
+        let conn = match schemas.entry(schema) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let conn = load_schema(entry.key())
+                    .map_err(|e| format!("Failed to load schema {}: {e}", path.display()))?;
+                entry.insert(conn)
+            }
+        };
+
+        Ok(f(conn))
+    })
+}
+
+/// Loads the schema into an in-memory database. Dumps created by a backup can be used as is.
+fn load_schema(schema: &str) -> rusqlite::Result<Connection> {
+    let conn = Connection::open_in_memory()?;
+    conn.execute_batch(schema)?;
+    Ok(conn)
+}
+
+/// Prepares the statement, which fails for unknown tables and columns, and checks the arguments
+/// against its parameters.
+fn check(conn: &Connection, sql: &LitStr, arguments: &Arguments) -> syn::Result<()> {
+    let stmt = conn
+        .prepare(&sql.value())
+        .map_err(|e| syn::Error::new(sql.span(), format!("Invalid query: {e}")))?;
+
+    let parameters = (1..=stmt.parameter_count())
+        .map(|index| stmt.parameter_name(index))
+        .collect::<Vec<_>>();
+
+    match arguments {
+        Arguments::Positional(count) => {
+            if *count != parameters.len() {
+                return Err(syn::Error::new(
+                    sql.span(),
+                    format!("Expected {} arguments, found {count}", parameters.len()),
+                ));
+            }
+        }
+        Arguments::Named(names) => {
+            let mut expected = Vec::with_capacity(parameters.len());
+
+            for parameter in parameters {
+                match parameter.and_then(|name| name.strip_prefix([':', '@', '$'])) {
+                    Some(name) => expected.push(name),
+                    None => {
+                        return Err(syn::Error::new(
+                            sql.span(),
+                            "Positional placeholders can not be used with named arguments",
+                        ))
+                    }
+                }
+            }
+
+            if let Some(name) = names
+                .iter()
+                .find(|name| !expected.contains(&&*name.to_string()))
+            {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Unknown named argument `{name}`"),
+                ));
+            }
+
+            if let Some(name) = expected
+                .iter()
+                .find(|name| !names.iter().any(|n| n == name))
+            {
+                return Err(syn::Error::new(
+                    sql.span(),
+                    format!("Missing named argument `{name}`"),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);";
+
+    fn check_query(input: &str) -> Result<(), String> {
+        let input: QueryInput = syn::parse_str(input).map_err(|e| e.to_string())?;
+        let conn = load_schema(SCHEMA).map_err(|e| e.to_string())?;
+        let arguments = Arguments::from_exprs(&input.args).map_err(|e| e.to_string())?;
+
+        check(&conn, &input.sql, &arguments).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn unit_query_checked_valid() {
+        assert_eq!(check_query(r#""SELECT id, name FROM users""#), Ok(()));
+        assert_eq!(
+            check_query(r#""SELECT name FROM users WHERE id = ?", id"#),
+            Ok(())
+        );
+        assert_eq!(
+            check_query(
+                r#""INSERT INTO users (id, name) VALUES (:id, :name)", name = "a", id = 1"#
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn unit_query_checked_unknown_names() {
+        assert_eq!(
+            check_query(r#""SELECT * FROM user""#),
+            Err("Invalid query: no such table: user".to_string())
+        );
+        assert!(check_query(r#""SELECT nme FROM users""#)
+            .unwrap_err()
+            .starts_with("Invalid query: no such column: nme"));
+    }
+
+    #[test]
+    fn unit_query_checked_arguments() {
+        assert_eq!(
+            check_query(r#""SELECT * FROM users WHERE id = ?", 1, 2"#),
+            Err("Expected 1 arguments, found 2".to_string())
+        );
+        assert_eq!(
+            check_query(r#""SELECT * FROM users WHERE id = :id", user_id = 1"#),
+            Err("Unknown named argument `user_id`".to_string())
+        );
+        assert_eq!(
+            check_query(r#""SELECT * FROM users WHERE id = :id AND name = :name", id = 1"#),
+            Err("Missing named argument `name`".to_string())
+        );
+        assert_eq!(
+            check_query(r#""SELECT * FROM users WHERE id = ?", id = 1"#),
+            Err("Positional placeholders can not be used with named arguments".to_string())
+        );
+        assert_eq!(
+            check_query(r#""SELECT * FROM users WHERE id = :id", id = 1, 2"#),
+            Err("positional and named arguments can not be mixed".to_string())
+        );
+    }
+
+    #[test]
+    fn unit_query_checked_schema_argument() {
+        let input: QueryInput = syn::parse_str(r#"schema = "db/schema.sql", "SELECT 1""#).unwrap();
+        assert_eq!(input.schema.unwrap().value(), "db/schema.sql");
+
+        assert!(syn::parse_str::<QueryInput>(r#"path = "db/schema.sql", "SELECT 1""#).is_err());
+    }
+}
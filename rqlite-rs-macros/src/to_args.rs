@@ -0,0 +1,47 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Fields};
+
+use crate::attr::{collect_errors, ContainerAttrs, FieldAttrs};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let syn::Data::Struct(syn::DataStruct {
+        fields: Fields::Named(fields),
+        ..
+    }) = &input.data
+    else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "Only structs with named fields are supported for `#[derive(ToArgs)]`",
+        ));
+    };
+
+    let container = ContainerAttrs::from_attrs(&input.attrs)?;
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = collect_errors(fields.named.iter().map(|field| {
+        let attrs = FieldAttrs::from_attrs(&field.attrs)?;
+        attrs.reject_decode_attrs("ToArgs")?;
+
+        let Some(ident) = field.ident.as_ref() else {
+            return Err(syn::Error::new_spanned(field, "Expected named field"));
+        };
+
+        Ok((!attrs.skip).then(|| (attrs.column_name(ident, &container), ident)))
+    }))?;
+
+    let (columns, idents): (Vec<_>, Vec<_>) = fields.into_iter().flatten().unzip();
+
+    Ok(quote! {
+        impl #impl_generics rqlite_rs::ToArgs for #struct_name #ty_generics #where_clause {
+            fn columns() -> &'static [&'static str] {
+                &[#(#columns),*]
+            }
+
+            fn to_args(&self) -> Vec<rqlite_rs::query::arguments::RqliteArgument> {
+                vec![#(rqlite_rs::arg!(self.#idents)),*]
+            }
+        }
+    })
+}
@@ -0,0 +1,465 @@
+//! Execution of statements against the embedded `SQLite` database, producing results in the
+//! format of the rqlite HTTP API.
+
+use std::time::Instant;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rusqlite::{
+    types::{Value as SqliteValue, ValueRef},
+    Connection, Statement as PreparedStatement,
+};
+use serde_json::{json, Map, Number, Value};
+
+/// How the statements of a request are executed, depending on the endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// `/db/query`: only statements that do not modify the database are allowed.
+    Query,
+    /// `/db/execute`: statements are executed and the affected rows are returned.
+    Execute,
+    /// `/db/request`: statements returning rows are treated as queries, others as executions.
+    Request,
+}
+
+/// The query parameters of a request that change how statements are executed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options {
+    pub transaction: bool,
+    pub blob_array: bool,
+    pub timings: bool,
+}
+
+/// The parameters bound to a statement.
+#[derive(Debug, PartialEq)]
+enum Params {
+    Positional(Vec<Value>),
+    Named(Map<String, Value>),
+}
+
+/// A single statement as sent by the client, either as a plain string or as
+/// `[sql, args...]` or `[sql, {named}]`.
+#[derive(Debug, PartialEq)]
+pub struct Statement {
+    sql: String,
+    params: Params,
+}
+
+/// Parses the body of a request to one of the `/db` endpoints.
+pub fn parse_statements(body: &str) -> Result<Vec<Statement>, String> {
+    let statements: Vec<Value> =
+        serde_json::from_str(body).map_err(|e| format!("invalid request body: {e}"))?;
+
+    statements.into_iter().map(parse_statement).collect()
+}
+
+fn parse_statement(statement: Value) -> Result<Statement, String> {
+    match statement {
+        Value::String(sql) => Ok(Statement {
+            sql,
+            params: Params::Positional(Vec::new()),
+        }),
+        Value::Array(parts) => {
+            let mut parts = parts.into_iter();
+
+            let Some(Value::String(sql)) = parts.next() else {
+                return Err("invalid request body: statement must start with SQL".to_string());
+            };
+
+            let args: Vec<Value> = parts.collect();
+            let params = match <[Value; 1]>::try_from(args) {
+                Ok([Value::Object(named)]) => Params::Named(named),
+                Ok(arg) => Params::Positional(arg.into()),
+                Err(args) => Params::Positional(args),
+            };
+
+            Ok(Statement { sql, params })
+        }
+        _ => Err("invalid request body: statement must be a string or an array".to_string()),
+    }
+}
+
+/// Converts a JSON argument into a `SQLite` value.
+/// Arrays of bytes are bound as blobs, booleans as integers.
+fn to_sqlite(value: &Value) -> Result<SqliteValue, String> {
+    match value {
+        Value::Null => Ok(SqliteValue::Null),
+        Value::Bool(b) => Ok(SqliteValue::Integer(i64::from(*b))),
+        Value::Number(n) => n
+            .as_i64()
+            .map(SqliteValue::Integer)
+            .or_else(|| n.as_f64().map(SqliteValue::Real))
+            .ok_or_else(|| format!("unsupported number: {n}")),
+        Value::String(s) => Ok(SqliteValue::Text(s.clone())),
+        Value::Array(bytes) => bytes
+            .iter()
+            .map(|byte| {
+                byte.as_u64()
+                    .and_then(|byte| u8::try_from(byte).ok())
+                    .ok_or_else(|| "unsupported parameter: arrays must contain bytes".to_string())
+            })
+            .collect::<Result<Vec<u8>, String>>()
+            .map(SqliteValue::Blob),
+        Value::Object(_) => Err("unsupported parameter: nested object".to_string()),
+    }
+}
+
+/// Converts a value read from `SQLite` into JSON, the way rqlite encodes it.
+/// Whole reals are encoded without a fractional part, blobs as base64 strings unless
+/// `blob_array` is set.
+fn to_json(value: ValueRef<'_>, blob_array: bool) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "only whole reals within the range of i64 are converted"
+        )]
+        ValueRef::Real(f) if f.fract() == 0.0 && f.abs() < 9.0e15 => Value::from(f as i64),
+        ValueRef::Real(f) => Number::from_f64(f).map_or(Value::Null, Value::Number),
+        ValueRef::Text(text) => Value::String(String::from_utf8_lossy(text).into_owned()),
+        ValueRef::Blob(blob) if blob_array => Value::from(blob),
+        ValueRef::Blob(blob) => Value::String(STANDARD.encode(blob)),
+    }
+}
+
+/// Returns the type rqlite reports for a value in a column without a declared type.
+const fn type_of(value: ValueRef<'_>) -> &'static str {
+    match value {
+        ValueRef::Null => "",
+        ValueRef::Integer(_) => "integer",
+        ValueRef::Real(_) => "real",
+        ValueRef::Text(_) => "text",
+        ValueRef::Blob(_) => "blob",
+    }
+}
+
+fn bind(prepared: &mut PreparedStatement<'_>, params: &Params) -> Result<(), String> {
+    let count = prepared.parameter_count();
+
+    match params {
+        Params::Positional(args) => {
+            if args.len() != count {
+                return Err(format!(
+                    "wrong number of arguments: expected {count}, got {}",
+                    args.len()
+                ));
+            }
+
+            for (index, arg) in args.iter().enumerate() {
+                prepared
+                    .raw_bind_parameter(index + 1, to_sqlite(arg)?)
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        Params::Named(args) => {
+            for index in 1..=count {
+                // Named arguments are sent without the `:`, `@` or `$` prefix
+                let name = prepared
+                    .parameter_name(index)
+                    .and_then(|name| name.get(1..))
+                    .ok_or_else(|| "positional parameters require an array".to_string())?;
+                let arg = args
+                    .get(name)
+                    .ok_or_else(|| format!("missing named argument: {name}"))?;
+
+                prepared
+                    .raw_bind_parameter(index, to_sqlite(arg)?)
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads all rows of a prepared and bound statement.
+fn rows(prepared: &mut PreparedStatement<'_>, blob_array: bool) -> Result<Value, String> {
+    let columns: Vec<String> = prepared
+        .column_names()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let mut types: Vec<String> = prepared
+        .columns()
+        .iter()
+        .map(|column| column.decl_type().unwrap_or_default().to_lowercase())
+        .collect();
+
+    let mut values = Vec::new();
+    let mut rows = prepared.raw_query();
+
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        let mut record = Vec::with_capacity(columns.len());
+
+        for (index, column_type) in types.iter_mut().enumerate() {
+            let value = row.get_ref(index).map_err(|e| e.to_string())?;
+
+            // Columns without a declared type take the type of their first value
+            if column_type.is_empty() && values.is_empty() {
+                type_of(value).clone_into(column_type);
+            }
+
+            record.push(to_json(value, blob_array));
+        }
+
+        values.push(Value::Array(record));
+    }
+
+    let mut result = Map::new();
+    result.insert("columns".to_string(), json!(columns));
+    result.insert("types".to_string(), json!(types));
+
+    // rqlite leaves out the values of empty results
+    if !values.is_empty() {
+        result.insert("values".to_string(), Value::Array(values));
+    }
+
+    Ok(Value::Object(result))
+}
+
+/// Executes a prepared and bound statement, draining any rows it returns.
+fn execute(conn: &Connection, prepared: &mut PreparedStatement<'_>) -> Result<Value, String> {
+    let changes_before = conn.total_changes();
+
+    let mut rows = prepared.raw_query();
+    while rows.next().map_err(|e| e.to_string())?.is_some() {}
+
+    let mut result = Map::new();
+
+    let last_insert_id = conn.last_insert_rowid();
+    if last_insert_id != 0 {
+        result.insert("last_insert_id".to_string(), Value::from(last_insert_id));
+    }
+
+    // `changes` is not reset by statements that do not modify rows
+    let rows_affected = if conn.total_changes() == changes_before {
+        0
+    } else {
+        conn.changes()
+    };
+    if rows_affected != 0 {
+        result.insert("rows_affected".to_string(), Value::from(rows_affected));
+    }
+
+    Ok(Value::Object(result))
+}
+
+fn run(
+    conn: &Connection,
+    statement: &Statement,
+    mode: Mode,
+    options: Options,
+) -> Result<Value, String> {
+    let mut prepared = conn.prepare(&statement.sql).map_err(|e| e.to_string())?;
+    bind(&mut prepared, &statement.params)?;
+
+    match mode {
+        Mode::Query if !prepared.readonly() => {
+            Err("attempt to change database via query operation".to_string())
+        }
+        Mode::Query => rows(&mut prepared, options.blob_array),
+        Mode::Request if prepared.column_count() > 0 => rows(&mut prepared, options.blob_array),
+        Mode::Execute | Mode::Request => execute(conn, &mut prepared),
+    }
+}
+
+/// Executes the statements and returns one result per statement.
+///
+/// Failing statements produce an `{"error": ...}` result. Within a transaction, execution stops
+/// at the first error and all changes are rolled back.
+pub fn run_statements(
+    conn: &Connection,
+    statements: &[Statement],
+    mode: Mode,
+    options: Options,
+) -> Result<Vec<Value>, String> {
+    let tx = if options.transaction {
+        Some(conn.unchecked_transaction().map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    let mut results = Vec::with_capacity(statements.len());
+    let mut failed = false;
+
+    for statement in statements {
+        let start = Instant::now();
+
+        match run(conn, statement, mode, options) {
+            Ok(mut result) => {
+                if let Some(result) = result.as_object_mut().filter(|_| options.timings) {
+                    result.insert("time".to_string(), json!(start.elapsed().as_secs_f64()));
+                }
+                results.push(result);
+            }
+            Err(error) => {
+                failed = true;
+                results.push(json!({ "error": error }));
+            }
+        }
+
+        if failed && options.transaction {
+            break;
+        }
+    }
+
+    if let Some(tx) = tx {
+        if failed {
+            tx.rollback().map_err(|e| e.to_string())?;
+        } else {
+            tx.commit().map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_json(conn: &Connection, body: &str, mode: Mode, options: Options) -> Vec<Value> {
+        let statements = parse_statements(body).unwrap();
+        run_statements(conn, &statements, mode, options).unwrap()
+    }
+
+    fn database() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE foo (id INTEGER PRIMARY KEY, name TEXT, data BLOB)")
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn unit_parse_statements() {
+        let statements =
+            parse_statements(r#"["SELECT 1", ["SELECT ?", 1], ["SELECT :a", {"a": 2}]]"#).unwrap();
+
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[0].params, Params::Positional(vec![]));
+        assert_eq!(statements[1].params, Params::Positional(vec![json!(1)]));
+        assert!(matches!(&statements[2].params, Params::Named(named) if named["a"] == json!(2)));
+
+        assert!(parse_statements("{}").is_err());
+        assert!(parse_statements("[[1]]").is_err());
+    }
+
+    #[test]
+    fn unit_run_statements_execute_and_query() {
+        let conn = database();
+
+        let results = run_json(
+            &conn,
+            r#"[["INSERT INTO foo (name) VALUES (?)", "a"], ["INSERT INTO foo (name) VALUES (:name)", {"name": "b"}], "INSERT INTO bar VALUES (1)"]"#,
+            Mode::Execute,
+            Options::default(),
+        );
+
+        assert_eq!(results[0], json!({"last_insert_id": 1, "rows_affected": 1}));
+        assert_eq!(results[1], json!({"last_insert_id": 2, "rows_affected": 1}));
+        assert_eq!(results[2], json!({"error": "no such table: bar"}));
+
+        let results = run_json(
+            &conn,
+            r#"["SELECT id, name, 1.0 AS one FROM foo", "SELECT * FROM foo WHERE id = 3", "DELETE FROM foo"]"#,
+            Mode::Query,
+            Options::default(),
+        );
+
+        assert_eq!(
+            results[0],
+            json!({
+                "columns": ["id", "name", "one"],
+                "types": ["integer", "text", "real"],
+                "values": [[1, "a", 1], [2, "b", 1]]
+            })
+        );
+        assert_eq!(
+            results[1],
+            json!({"columns": ["id", "name", "data"], "types": ["integer", "text", "blob"]})
+        );
+        assert!(results[2]["error"].is_string());
+    }
+
+    #[test]
+    fn unit_run_statements_request() {
+        let conn = database();
+
+        let results = run_json(
+            &conn,
+            r#"[["INSERT INTO foo (name) VALUES (?) RETURNING id", "a"], "SELECT COUNT(*) FROM foo", "UPDATE foo SET name = 'b'"]"#,
+            Mode::Request,
+            Options::default(),
+        );
+
+        assert_eq!(results[0]["values"], json!([[1]]));
+        assert_eq!(results[1]["values"], json!([[1]]));
+        assert_eq!(results[2]["rows_affected"], json!(1));
+    }
+
+    #[test]
+    fn unit_run_statements_blobs() {
+        let conn = database();
+
+        run_json(
+            &conn,
+            r#"[["INSERT INTO foo (data) VALUES (?)", [1, 2, 3]]]"#,
+            Mode::Execute,
+            Options::default(),
+        );
+
+        let query = r#"["SELECT data FROM foo"]"#;
+
+        let results = run_json(&conn, query, Mode::Query, Options::default());
+        assert_eq!(results[0]["values"], json!([["AQID"]]));
+
+        let options = Options {
+            blob_array: true,
+            ..Options::default()
+        };
+        let results = run_json(&conn, query, Mode::Query, options);
+        assert_eq!(results[0]["values"], json!([[[1, 2, 3]]]));
+    }
+
+    #[test]
+    fn unit_run_statements_transaction() {
+        let conn = database();
+        let options = Options {
+            transaction: true,
+            ..Options::default()
+        };
+
+        let results = run_json(
+            &conn,
+            r#"["INSERT INTO foo (name) VALUES ('a')", "INSERT INTO bar VALUES (1)", "INSERT INTO foo (name) VALUES ('b')"]"#,
+            Mode::Execute,
+            options,
+        );
+
+        // Execution stops at the first error and the transaction is rolled back
+        assert_eq!(results.len(), 2);
+        assert!(results[1]["error"].is_string());
+
+        let results = run_json(
+            &conn,
+            r#"["SELECT COUNT(*) FROM foo"]"#,
+            Mode::Query,
+            Options::default(),
+        );
+        assert_eq!(results[0]["values"], json!([[0]]));
+    }
+
+    #[test]
+    fn unit_run_statements_argument_errors() {
+        let conn = database();
+
+        let results = run_json(
+            &conn,
+            r#"[["SELECT ?, ?", 1], ["SELECT :a", {"b": 1}], ["SELECT ?", {"a": 1}]]"#,
+            Mode::Query,
+            Options::default(),
+        );
+
+        assert!(results.iter().all(|result| result["error"].is_string()));
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+/// The endpoints served by the [`MockServer`](crate::MockServer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// `POST /db/query`
+    Query,
+    /// `POST /db/execute`
+    Execute,
+    /// `POST /db/request`
+    Request,
+    /// `GET /nodes`
+    Nodes,
+    /// `GET /readyz`
+    Readyz,
+    /// `DELETE /remove`
+    Remove,
+}
+
+impl Endpoint {
+    /// Returns the path of the endpoint.
+    #[must_use]
+    pub const fn path(self) -> &'static str {
+        match self {
+            Self::Query => "/db/query",
+            Self::Execute => "/db/execute",
+            Self::Request => "/db/request",
+            Self::Nodes => "/nodes",
+            Self::Readyz => "/readyz",
+            Self::Remove => "/remove",
+        }
+    }
+}
+
+/// An error returned by the [`MockServer`](crate::MockServer) instead of handling the request,
+/// see [`MockServer::inject_error`](crate::MockServer::inject_error).
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use rqlite_rs_test::InjectedError;
+///
+/// // The next two requests fail with a 503 after 50ms
+/// let error = InjectedError::new(503)
+///     .body("leadership lost while committing log")
+///     .delay(Duration::from_millis(50))
+///     .times(2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct InjectedError {
+    pub(crate) status: u16,
+    pub(crate) body: String,
+    pub(crate) delay: Option<Duration>,
+    pub(crate) remaining: Option<usize>,
+}
+
+impl InjectedError {
+    /// Creates an error responding with `status` and an empty body to every request, until the
+    /// errors are cleared. Invalid status codes are replaced by `500`.
+    #[must_use]
+    pub const fn new(status: u16) -> Self {
+        Self {
+            status,
+            body: String::new(),
+            delay: None,
+            remaining: None,
+        }
+    }
+
+    /// Creates an error that fails a single statement, the way rqlite reports errors of
+    /// statements: the request succeeds with a single `{"error": message}` result.
+    #[must_use]
+    pub fn statement(message: &str) -> Self {
+        let body = serde_json::json!({ "results": [{ "error": message }] });
+
+        Self::new(200).body(body.to_string())
+    }
+
+    /// Sets the body of the response.
+    #[must_use]
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Delays the response, e.g. to trigger timeouts of the client.
+    #[must_use]
+    pub const fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Only fails the next `times` requests.
+    #[must_use]
+    pub const fn times(mut self, times: usize) -> Self {
+        self.remaining = Some(times);
+        self
+    }
+}
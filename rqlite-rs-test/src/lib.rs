@@ -0,0 +1,45 @@
+//! An in-process mock of the rqlite HTTP API for testing applications built on
+//! [rqlite-rs](https://docs.rs/rqlite-rs/), without running `rqlited`.
+//!
+//! The [`MockServer`] executes statements against an in-memory `SQLite` database and responds
+//! in the format of rqlite. It implements the `/db/query`, `/db/execute`, `/db/request`,
+//! `/nodes`, `/readyz` and `/remove` endpoints and understands the `transaction`, `blob_array`,
+//! `timings`, `queue` and `raft_index` query parameters. Other parameters, such as read
+//! consistency levels, are accepted and ignored.
+//!
+//! Failures can be simulated by injecting errors into single endpoints using
+//! [`MockServer::inject_error`].
+//!
+//! # Example
+//! ```
+//! use rqlite_rs::RqliteClientBuilder;
+//! use rqlite_rs_test::{Endpoint, InjectedError, MockServer};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = MockServer::start().await?;
+//! server.execute_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)")?;
+//!
+//! let client = RqliteClientBuilder::new()
+//!     .known_host(server.host())
+//!     .build()?;
+//!
+//! client.exec(rqlite_rs::query!("INSERT INTO users (name) VALUES (?)", "alice")?).await?;
+//! assert_eq!(client.fetch("SELECT * FROM users").await?.len(), 1);
+//!
+//! // The next request fails with a server error
+//! server.inject_error(Endpoint::Query, InjectedError::new(503).times(1));
+//! assert!(client.fetch("SELECT * FROM users").await.is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+mod database;
+mod inject;
+mod node;
+mod server;
+
+pub use inject::{Endpoint, InjectedError};
+pub use node::MockNode;
+pub use rusqlite;
+pub use server::MockServer;
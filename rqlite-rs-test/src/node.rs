@@ -0,0 +1,52 @@
+use serde::Serialize;
+
+/// A node reported by the `/nodes` endpoint of the [`MockServer`](crate::MockServer).
+///
+/// The server reports itself as the leader. Other nodes are only listed and can be removed
+/// using the `/remove` endpoint, requests are not forwarded to them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MockNode {
+    /// The unique identifier of the node.
+    pub id: String,
+    /// The address of the node's API, including the scheme.
+    pub api_addr: String,
+    /// The address of the node's Raft service.
+    pub addr: String,
+    /// If the node is a voter.
+    pub voter: bool,
+    /// If the node is reachable.
+    pub reachable: bool,
+    /// If the node is the leader.
+    pub leader: bool,
+    /// The latency to the node in seconds.
+    pub time: f64,
+    /// The error reaching the node, if it is unreachable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl MockNode {
+    /// Creates a reachable voter that is not the leader, listening on `host` in the format
+    /// `hostname:port`.
+    #[must_use]
+    pub fn new(id: &str, host: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            api_addr: format!("http://{host}"),
+            addr: host.to_string(),
+            voter: true,
+            reachable: true,
+            leader: false,
+            time: 0.0,
+            error: None,
+        }
+    }
+
+    /// Marks the node as unreachable with the given error.
+    #[must_use]
+    pub fn unreachable(mut self, error: &str) -> Self {
+        self.reachable = false;
+        self.error = Some(error.to_string());
+        self
+    }
+}
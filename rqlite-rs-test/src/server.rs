@@ -0,0 +1,557 @@
+use std::{
+    collections::HashMap,
+    io,
+    net::{Ipv4Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::Instant,
+};
+
+use axum::{
+    extract::{RawQuery, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Json, Router,
+};
+use rusqlite::Connection;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use tokio::{net::TcpListener, sync::oneshot};
+
+use crate::{
+    database::{parse_statements, run_statements, Mode, Options},
+    inject::{Endpoint, InjectedError},
+    node::MockNode,
+};
+
+/// Locks a mutex, ignoring poisoning caused by a panicking test.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The query parameters understood by the mock server.
+/// Flags are set if they are present, unless their value is `false`.
+#[derive(Debug, Default)]
+struct QueryParams {
+    options: Options,
+    queue: bool,
+    raft_index: bool,
+    ver: Option<String>,
+}
+
+impl QueryParams {
+    fn parse(query: Option<&str>) -> Self {
+        let mut params = Self::default();
+
+        for pair in query.unwrap_or_default().split('&') {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let flag = value != "false";
+
+            match key {
+                "transaction" => params.options.transaction = flag,
+                "blob_array" => params.options.blob_array = flag,
+                "timings" => params.options.timings = flag,
+                "queue" => params.queue = flag,
+                "raft_index" => params.raft_index = flag,
+                "ver" => params.ver = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        params
+    }
+}
+
+#[derive(Deserialize)]
+struct RemoveNodeRequest {
+    id: String,
+}
+
+struct ServerState {
+    db: Mutex<Connection>,
+    nodes: Mutex<Vec<MockNode>>,
+    errors: Mutex<Vec<(Endpoint, InjectedError)>>,
+    requests: Mutex<HashMap<Endpoint, usize>>,
+    sequence_number: AtomicU64,
+    raft_index: AtomicU64,
+}
+
+impl ServerState {
+    /// Counts the request and returns the response of an injected error, if there is one.
+    async fn intercept(&self, endpoint: Endpoint) -> Option<Response> {
+        *lock(&self.requests).entry(endpoint).or_default() += 1;
+
+        let error = self.take_error(endpoint)?;
+
+        if let Some(delay) = error.delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        let status =
+            StatusCode::from_u16(error.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+        Some((status, error.body).into_response())
+    }
+
+    /// Returns the first error injected for the endpoint, forgetting it once it has been used
+    /// the configured number of times.
+    fn take_error(&self, endpoint: Endpoint) -> Option<InjectedError> {
+        let mut errors = lock(&self.errors);
+        let index = errors.iter().position(|(e, _error)| *e == endpoint)?;
+        let (_endpoint, error) = errors.get_mut(index)?;
+        let taken = error.clone();
+
+        match &mut error.remaining {
+            Some(remaining) if *remaining > 1 => *remaining -= 1,
+            Some(_) => {
+                errors.remove(index);
+            }
+            None => {}
+        }
+
+        drop(errors);
+        Some(taken)
+    }
+}
+
+async fn statements(
+    state: &ServerState,
+    endpoint: Endpoint,
+    mode: Mode,
+    query: Option<&str>,
+    body: &str,
+) -> Response {
+    if let Some(response) = state.intercept(endpoint).await {
+        return response;
+    }
+
+    let params = QueryParams::parse(query);
+    let statements = match parse_statements(body) {
+        Ok(statements) => statements,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    let start = Instant::now();
+    let results = run_statements(&lock(&state.db), &statements, mode, params.options);
+    let results = match results {
+        Ok(results) => results,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    };
+
+    let mut response = Map::new();
+
+    // Queued writes are applied right away, but only the sequence number is returned
+    if params.queue && mode == Mode::Execute {
+        let sequence_number = state.sequence_number.fetch_add(1, Ordering::Relaxed) + 1;
+        response.insert("results".to_string(), json!([]));
+        response.insert("sequence_number".to_string(), json!(sequence_number));
+    } else {
+        response.insert("results".to_string(), Value::Array(results));
+    }
+
+    if params.options.timings {
+        response.insert("time".to_string(), json!(start.elapsed().as_secs_f64()));
+    }
+
+    if mode != Mode::Query {
+        let raft_index = state.raft_index.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if params.raft_index {
+            response.insert("raft_index".to_string(), json!(raft_index));
+        }
+    }
+
+    Json(response).into_response()
+}
+
+async fn query(
+    State(state): State<Arc<ServerState>>,
+    RawQuery(query): RawQuery,
+    body: String,
+) -> Response {
+    statements(
+        &state,
+        Endpoint::Query,
+        Mode::Query,
+        query.as_deref(),
+        &body,
+    )
+    .await
+}
+
+async fn execute(
+    State(state): State<Arc<ServerState>>,
+    RawQuery(query): RawQuery,
+    body: String,
+) -> Response {
+    statements(
+        &state,
+        Endpoint::Execute,
+        Mode::Execute,
+        query.as_deref(),
+        &body,
+    )
+    .await
+}
+
+async fn request(
+    State(state): State<Arc<ServerState>>,
+    RawQuery(query): RawQuery,
+    body: String,
+) -> Response {
+    statements(
+        &state,
+        Endpoint::Request,
+        Mode::Request,
+        query.as_deref(),
+        &body,
+    )
+    .await
+}
+
+async fn nodes(State(state): State<Arc<ServerState>>, RawQuery(query): RawQuery) -> Response {
+    if let Some(response) = state.intercept(Endpoint::Nodes).await {
+        return response;
+    }
+
+    let nodes = lock(&state.nodes).clone();
+
+    if QueryParams::parse(query.as_deref()).ver.as_deref() == Some("2") {
+        return Json(json!({ "nodes": nodes })).into_response();
+    }
+
+    // Without `ver=2`, nodes are returned as an object keyed by their id
+    let mut legacy = Map::new();
+
+    for node in nodes {
+        let id = node.id.clone();
+
+        match serde_json::to_value(node) {
+            Ok(Value::Object(mut node)) => {
+                node.remove("id");
+                legacy.insert(id, Value::Object(node));
+            }
+            Ok(_) | Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+
+    Json(legacy).into_response()
+}
+
+async fn readyz(State(state): State<Arc<ServerState>>) -> Response {
+    if let Some(response) = state.intercept(Endpoint::Readyz).await {
+        return response;
+    }
+
+    "[+]node ok\n[+]leader ok\n[+]store ok\n".into_response()
+}
+
+async fn remove(State(state): State<Arc<ServerState>>, body: String) -> Response {
+    if let Some(response) = state.intercept(Endpoint::Remove).await {
+        return response;
+    }
+
+    let request: RemoveNodeRequest = match serde_json::from_str(&body) {
+        Ok(request) => request,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+
+    let mut nodes = lock(&state.nodes);
+    let Some(index) = nodes.iter().position(|node| node.id == request.id) else {
+        return (StatusCode::NOT_FOUND, "node not found").into_response();
+    };
+    nodes.remove(index);
+    drop(nodes);
+
+    StatusCode::OK.into_response()
+}
+
+/// An in-process rqlite server backed by an in-memory `SQLite` database.
+///
+/// The server listens on a random port of `127.0.0.1` and stops when it is dropped. It must be
+/// started from within a Tokio runtime.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<ServerState>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Starts a new server with an empty database. The server lists itself as the only node
+    /// and the leader of the cluster, with the id `1`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the listener could not be bound or the database
+    /// could not be opened.
+    pub async fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let addr = listener.local_addr()?;
+        let db = Connection::open_in_memory().map_err(io::Error::other)?;
+
+        let leader = MockNode {
+            leader: true,
+            ..MockNode::new("1", &addr.to_string())
+        };
+
+        let state = Arc::new(ServerState {
+            db: Mutex::new(db),
+            nodes: Mutex::new(vec![leader]),
+            errors: Mutex::new(Vec::new()),
+            requests: Mutex::new(HashMap::new()),
+            sequence_number: AtomicU64::new(0),
+            raft_index: AtomicU64::new(0),
+        });
+
+        let app = Router::new()
+            .route(Endpoint::Query.path(), post(query))
+            .route(Endpoint::Execute.path(), post(execute))
+            .route(Endpoint::Request.path(), post(request))
+            .route(Endpoint::Nodes.path(), get(nodes))
+            .route(Endpoint::Readyz.path(), get(readyz))
+            .route(Endpoint::Remove.path(), delete(remove))
+            .with_state(Arc::clone(&state));
+
+        let (shutdown, stopped) = oneshot::channel::<()>();
+
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    stopped.await.ok();
+                })
+                .await
+                .ok();
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// Returns the address of the server, in the format `hostname:port` expected by
+    /// `RqliteClientBuilder::known_host`.
+    #[must_use]
+    pub fn host(&self) -> String {
+        self.addr.to_string()
+    }
+
+    /// Returns the socket address the server listens on.
+    #[must_use]
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Executes SQL directly against the database, e.g. to set up fixtures.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any of the statements fail.
+    pub fn execute_batch(&self, sql: &str) -> rusqlite::Result<()> {
+        lock(&self.state.db).execute_batch(sql)
+    }
+
+    /// Runs `f` with the database, e.g. to inspect its contents after a request.
+    pub fn with_connection<T>(&self, f: impl FnOnce(&Connection) -> T) -> T {
+        f(&lock(&self.state.db))
+    }
+
+    /// Makes requests to `endpoint` fail with `error`.
+    /// Errors are used in the order they were injected, each until its number of
+    /// [`InjectedError::times`] is used up.
+    pub fn inject_error(&self, endpoint: Endpoint, error: InjectedError) {
+        if error.remaining != Some(0) {
+            lock(&self.state.errors).push((endpoint, error));
+        }
+    }
+
+    /// Removes all injected errors.
+    pub fn clear_errors(&self) {
+        lock(&self.state.errors).clear();
+    }
+
+    /// Adds a node to the list returned by the `/nodes` endpoint.
+    pub fn add_node(&self, node: MockNode) {
+        lock(&self.state.nodes).push(node);
+    }
+
+    /// Returns the nodes of the cluster, including the server itself.
+    #[must_use]
+    pub fn nodes(&self) -> Vec<MockNode> {
+        lock(&self.state.nodes).clone()
+    }
+
+    /// Returns the number of requests received by `endpoint`, including failed ones.
+    #[must_use]
+    pub fn request_count(&self, endpoint: Endpoint) -> usize {
+        lock(&self.state.requests)
+            .get(&endpoint)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    async fn send(
+        server: &MockServer,
+        method: reqwest::Method,
+        path: &str,
+        body: &str,
+    ) -> (u16, String) {
+        let res = reqwest::Client::new()
+            .request(method, format!("http://{}{path}", server.host()))
+            .body(body.to_string())
+            .send()
+            .await
+            .unwrap();
+
+        (res.status().as_u16(), res.text().await.unwrap())
+    }
+
+    async fn post_json(server: &MockServer, path: &str, body: &str) -> Value {
+        let (status, body) = send(server, reqwest::Method::POST, path, body).await;
+        assert_eq!(status, 200, "unexpected response: {body}");
+        serde_json::from_str(&body).unwrap()
+    }
+
+    #[test]
+    fn unit_query_params_parse() {
+        let params = QueryParams::parse(Some("transaction=true&blob_array&timings=false&ver=2"));
+
+        assert!(params.options.transaction);
+        assert!(params.options.blob_array);
+        assert!(!params.options.timings);
+        assert_eq!(params.ver.as_deref(), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn unit_mock_server_statements() {
+        let server = MockServer::start().await.unwrap();
+        server
+            .execute_batch("CREATE TABLE foo (id INTEGER PRIMARY KEY, name TEXT)")
+            .unwrap();
+
+        let res = post_json(
+            &server,
+            "/db/execute?raft_index=true&timings=true",
+            r#"[["INSERT INTO foo (name) VALUES (?)", "a"]]"#,
+        )
+        .await;
+        assert_eq!(res["results"][0]["last_insert_id"], 1);
+        assert!(res["results"][0]["time"].is_f64());
+        assert_eq!(res["raft_index"], 1);
+
+        let res = post_json(&server, "/db/query", r#"["SELECT name FROM foo"]"#).await;
+        assert_eq!(res["results"][0]["values"], json!([["a"]]));
+
+        let res = post_json(&server, "/db/execute?queue", r#"["DELETE FROM foo"]"#).await;
+        assert_eq!(res["sequence_number"], 1);
+
+        let count = server.with_connection(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM foo", [], |row| row.get::<_, i64>(0))
+                .unwrap()
+        });
+        assert_eq!(count, 0);
+
+        let (status, _body) = send(&server, reqwest::Method::POST, "/db/query", "{").await;
+        assert_eq!(status, 400);
+    }
+
+    #[tokio::test]
+    async fn unit_mock_server_inject_error() {
+        let server = MockServer::start().await.unwrap();
+
+        server.inject_error(
+            Endpoint::Query,
+            InjectedError::new(503).body("unavailable").times(2),
+        );
+        server.inject_error(
+            Endpoint::Query,
+            InjectedError::new(500)
+                .delay(Duration::from_millis(10))
+                .times(1),
+        );
+
+        for expected in [503, 503, 500, 200] {
+            let (status, _body) = send(
+                &server,
+                reqwest::Method::POST,
+                "/db/query",
+                r#"["SELECT 1"]"#,
+            )
+            .await;
+            assert_eq!(status, expected);
+        }
+        assert_eq!(server.request_count(Endpoint::Query), 4);
+
+        server.inject_error(
+            Endpoint::Request,
+            InjectedError::statement("database is locked"),
+        );
+        let res = post_json(&server, "/db/request", r#"["SELECT 1"]"#).await;
+        assert_eq!(res["results"][0]["error"], "database is locked");
+
+        server.clear_errors();
+        let res = post_json(&server, "/db/request", r#"["SELECT 1"]"#).await;
+        assert_eq!(res["results"][0]["values"], json!([[1]]));
+    }
+
+    #[tokio::test]
+    async fn unit_mock_server_nodes() {
+        let server = MockServer::start().await.unwrap();
+        server.add_node(MockNode::new("2", "127.0.0.1:1").unreachable("connection refused"));
+
+        let (status, body) = send(&server, reqwest::Method::GET, "/nodes?ver=2", "").await;
+        assert_eq!(status, 200);
+        let res: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            res["nodes"][0]["api_addr"],
+            format!("http://{}", server.host())
+        );
+        assert_eq!(res["nodes"][0]["leader"], true);
+        assert_eq!(res["nodes"][1]["error"], "connection refused");
+
+        let (_status, body) = send(&server, reqwest::Method::GET, "/nodes", "").await;
+        let res: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(res["2"]["reachable"], false);
+
+        let (status, _body) = send(
+            &server,
+            reqwest::Method::DELETE,
+            "/remove",
+            r#"{"id": "2"}"#,
+        )
+        .await;
+        assert_eq!(status, 200);
+        assert_eq!(server.nodes().len(), 1);
+
+        let (status, _body) = send(
+            &server,
+            reqwest::Method::DELETE,
+            "/remove",
+            r#"{"id": "2"}"#,
+        )
+        .await;
+        assert_eq!(status, 404);
+
+        let (status, _body) = send(&server, reqwest::Method::GET, "/readyz", "").await;
+        assert_eq!(status, 200);
+    }
+}
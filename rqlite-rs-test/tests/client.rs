@@ -0,0 +1,258 @@
+//! Runs the rqlite-rs client against the mock server, so the responses stay compatible.
+//! These tests do not need a running rqlite cluster and are named `unit_` accordingly.
+use std::{num::NonZeroU16, time::Duration};
+
+use rqlite_rs::{
+    batch::StatementOutcome,
+    error::RequestError,
+    prelude::*,
+    response::RqliteResult,
+    retry::{ExponentialBackoff, RetryContext, RetryDecision, RetryPolicy},
+};
+use rqlite_rs_test::{Endpoint, InjectedError, MockNode, MockServer};
+
+#[derive(FromRow, Debug, PartialEq)]
+struct User {
+    id: i64,
+    name: String,
+    score: f64,
+    avatar: Vec<u8>,
+}
+
+#[expect(clippy::unwrap_used, reason = "test code - panics are acceptable")]
+async fn setup() -> (MockServer, RqliteClient) {
+    let server = MockServer::start().await.unwrap();
+    server
+        .execute_batch(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, score REAL, avatar BLOB)",
+        )
+        .unwrap();
+
+    let client = RqliteClientBuilder::new()
+        .known_host(server.host())
+        .build()
+        .unwrap();
+
+    (server, client)
+}
+
+#[tokio::test]
+async fn unit_client_exec_and_fetch() {
+    let (server, client) = setup().await;
+
+    let query = rqlite_rs::query!(
+        "INSERT INTO users (name, score, avatar) VALUES (?, ?, ?)",
+        "alice",
+        2.0,
+        vec![1u8, 2, 3]
+    )
+    .unwrap();
+    let result = client.exec(query).await.unwrap();
+    assert_eq!(result.last_insert_id(), Some(1));
+    assert!(result.changed());
+
+    let users = client
+        .fetch("SELECT * FROM users")
+        .await
+        .unwrap()
+        .into_typed::<User>()
+        .unwrap();
+
+    assert_eq!(
+        users,
+        vec![User {
+            id: 1,
+            name: "alice".to_string(),
+            score: 2.0,
+            avatar: vec![1, 2, 3],
+        }]
+    );
+
+    assert!(client.fetch("SELECT * FROM missing").await.is_err());
+
+    // Only fetching the rows of a `RETURNING` clause uses the unified endpoint
+    let result = client
+        .exec("INSERT INTO users (name) VALUES ('bob') RETURNING id")
+        .await
+        .unwrap();
+    assert_eq!(result.last_insert_id(), Some(2));
+    assert_eq!(server.request_count(Endpoint::Request), 0);
+
+    let rows = client
+        .fetch("INSERT INTO users (name) VALUES ('carol') RETURNING id")
+        .await
+        .unwrap();
+    assert_eq!(rows[0].get::<i64>("id").unwrap(), 3);
+    assert_eq!(server.request_count(Endpoint::Request), 1);
+}
+
+#[tokio::test]
+async fn unit_client_request_and_transaction() {
+    let (server, client) = setup().await;
+
+    let results = client
+        .request(vec![
+            "INSERT INTO users (name) VALUES ('alice') RETURNING id",
+            "SELECT name FROM users",
+            "DELETE FROM missing",
+        ])
+        .await
+        .unwrap();
+
+    assert!(matches!(&results[0], StatementOutcome::Rows(rows) if rows.len() == 1));
+    assert!(matches!(&results[1], StatementOutcome::Rows(rows) if rows.len() == 1));
+    assert!(matches!(
+        &results[2],
+        StatementOutcome::Error { index: 2, .. }
+    ));
+
+    let results = client
+        .transaction(vec![
+            "INSERT INTO users (name) VALUES ('bob')",
+            "INSERT INTO missing VALUES (1)",
+        ])
+        .await
+        .unwrap();
+
+    assert!(matches!(results.last(), Some(RqliteResult::Error(_))));
+    let count = server.with_connection(|conn| {
+        conn.query_row("SELECT COUNT(*) FROM users", [], |row| row.get::<_, i64>(0))
+            .unwrap()
+    });
+    assert_eq!(count, 1);
+
+    let sequence_number = client
+        .queue(vec!["INSERT INTO users (name) VALUES ('carol')"])
+        .await
+        .unwrap();
+    assert_eq!(sequence_number, 1);
+}
+
+#[tokio::test]
+async fn unit_client_retries_injected_errors() {
+    let server = MockServer::start().await.unwrap();
+    let client = RqliteClientBuilder::new()
+        .known_host(server.host())
+        .retry_policy(
+            ExponentialBackoff::new()
+                .initial_delay(Duration::from_millis(1))
+                .max_retries(2),
+        )
+        .build()
+        .unwrap();
+
+    server.inject_error(Endpoint::Query, InjectedError::new(503).times(2));
+
+    let rows = client.fetch("SELECT 1").await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(server.request_count(Endpoint::Query), 3);
+
+    server.inject_error(Endpoint::Query, InjectedError::new(401));
+    assert!(matches!(
+        client.fetch("SELECT 1").await,
+        Err(RequestError::Unauthorized)
+    ));
+}
+
+/// A policy that never gives up.
+struct Forever(RetryDecision);
+
+impl RetryPolicy for Forever {
+    fn decide(&self, _ctx: &RetryContext) -> RetryDecision {
+        self.0
+    }
+}
+
+#[tokio::test]
+async fn unit_client_retry_policy_is_capped() {
+    let server = MockServer::start().await.unwrap();
+    let client = RqliteClientBuilder::new()
+        .known_host(server.host())
+        .retry_policy(Forever(RetryDecision::RetrySameHost(Duration::ZERO)))
+        .build()
+        .unwrap();
+
+    server.inject_error(Endpoint::Query, InjectedError::new(503));
+    assert!(matches!(
+        client.fetch("SELECT 1").await,
+        Err(RequestError::ReqwestError { .. })
+    ));
+    assert_eq!(server.request_count(Endpoint::Query), 17);
+
+    // Unreachable hosts are only tried as often as the fallback count allows
+    let client = RqliteClientBuilder::new()
+        .known_host("127.0.0.1:1")
+        .known_host("127.0.0.1:2")
+        .retry_policy(Forever(RetryDecision::SwitchHost(Duration::ZERO)))
+        .build()
+        .unwrap();
+
+    assert!(matches!(
+        client.fetch("SELECT 1").await,
+        Err(RequestError::RequestFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn unit_client_queue_and_wait() {
+    let (server, client) = setup().await;
+    let timeout = NonZeroU16::new(5).unwrap();
+
+    let sequence_number = client
+        .queue_and_wait(vec!["INSERT INTO users (name) VALUES ('alice')"], timeout)
+        .await
+        .unwrap();
+    client
+        .wait_for_sequence(sequence_number, timeout)
+        .await
+        .unwrap();
+
+    // Waiting for the queue does not write anything
+    let count = server.with_connection(|conn| {
+        conn.query_row("SELECT COUNT(*) FROM users", [], |row| row.get::<_, i64>(0))
+            .unwrap()
+    });
+    assert_eq!(count, 1);
+
+    assert!(matches!(
+        client.wait_for_sequence(100, timeout).await,
+        Err(RequestError::SequenceNumberNotAssigned {
+            sequence_number: 100,
+            ..
+        })
+    ));
+
+    // Queued writes are not sent again, even if the policy retries them
+    let client = RqliteClientBuilder::new()
+        .known_host(server.host())
+        .retry_policy(Forever(RetryDecision::RetrySameHost(Duration::ZERO)))
+        .build()
+        .unwrap();
+
+    server.inject_error(Endpoint::Execute, InjectedError::new(500).times(1));
+    let requests = server.request_count(Endpoint::Execute);
+    assert!(client
+        .queue_and_wait(vec!["INSERT INTO users (name) VALUES ('bob')"], timeout)
+        .await
+        .is_err());
+    assert_eq!(server.request_count(Endpoint::Execute), requests + 1);
+}
+
+#[tokio::test]
+async fn unit_client_nodes() {
+    let (server, client) = setup().await;
+    server.add_node(MockNode::new("2", "127.0.0.1:1").unreachable("connection refused"));
+
+    assert!(client.ready().await);
+
+    let leader = client.leader().await.unwrap().unwrap();
+    assert_eq!(leader.api_addr, format!("http://{}", server.host()));
+
+    let nodes = client.nodes().await.unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert!(!nodes[1].reachable);
+
+    client.remove_node("2").await.unwrap();
+    assert_eq!(server.nodes().len(), 1);
+    assert!(client.remove_node("2").await.is_err());
+}
@@ -0,0 +1,182 @@
+//! Options for downloading backups of the database.
+//!
+//! See [`RqliteClient::backup`](crate::RqliteClient::backup) and the
+//! [rqlite documentation](https://rqlite.io/docs/guides/backup/).
+
+use std::time::Duration;
+
+use crate::request::{RequestQueryParam, RequestQueryParams};
+
+/// The header every `SQLite` database file starts with.
+pub(crate) const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// The magic bytes every gzip stream starts with.
+pub(crate) const GZIP_MAGIC: &[u8; 2] = &[0x1f, 0x8b];
+
+/// The default timeout for downloading a backup.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// The format of a backup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BackupFormat {
+    /// A binary `SQLite` database file.
+    #[default]
+    Sqlite,
+    /// A SQL text dump of the database.
+    Sql,
+}
+
+/// Options for [`RqliteClient::backup`](crate::RqliteClient::backup).
+///
+/// # Example
+/// ```
+/// use rqlite_rs::backup::{BackupFormat, BackupOptions};
+///
+/// let options = BackupOptions::new().vacuum(true).compress(true);
+/// let dump = BackupOptions::new().format(BackupFormat::Sql);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BackupOptions {
+    format: BackupFormat,
+    compress: bool,
+    vacuum: bool,
+    noleader: bool,
+    timeout: Duration,
+}
+
+impl Default for BackupOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackupOptions {
+    /// Creates options for an uncompressed `SQLite` backup, served by the leader.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            format: BackupFormat::Sqlite,
+            compress: false,
+            vacuum: false,
+            noleader: false,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets the format of the backup.
+    #[must_use]
+    pub const fn format(mut self, format: BackupFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Compresses the backup using gzip.
+    #[must_use]
+    pub const fn compress(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Vacuums the database before creating the backup, resulting in a smaller file.
+    /// Only applies to `SQLite` backups.
+    #[must_use]
+    pub const fn vacuum(mut self, vacuum: bool) -> Self {
+        self.vacuum = vacuum;
+        self
+    }
+
+    /// Lets the receiving node create the backup instead of forwarding the request to the leader.
+    /// The backup may not contain the most recent writes.
+    #[must_use]
+    pub const fn noleader(mut self, noleader: bool) -> Self {
+        self.noleader = noleader;
+        self
+    }
+
+    /// Sets the timeout for downloading the backup. Defaults to 5 minutes.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub(crate) const fn request_timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns the header the downloaded file has to start with, if it can be verified.
+    pub(crate) const fn expected_header(&self) -> Option<&'static [u8]> {
+        match (self.format, self.compress) {
+            (_, true) => Some(GZIP_MAGIC),
+            (BackupFormat::Sqlite, false) => Some(SQLITE_HEADER),
+            (BackupFormat::Sql, false) => None,
+        }
+    }
+
+    pub(crate) fn query_params(&self) -> RequestQueryParams {
+        let mut params = vec![];
+
+        if self.format == BackupFormat::Sql {
+            params.push(RequestQueryParam::KV("fmt".to_string(), "sql".to_string()));
+        }
+
+        for (enabled, name) in [
+            (self.compress, "compress"),
+            (self.vacuum, "vacuum"),
+            (self.noleader, "noleader"),
+        ] {
+            if enabled {
+                params.push(RequestQueryParam::Bool(name.to_string()));
+            }
+        }
+
+        RequestQueryParams(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_backup_options_query_params() {
+        let params = BackupOptions::new().query_params().into_reqwest_query();
+        assert!(params.is_empty());
+
+        let params = BackupOptions::new()
+            .format(BackupFormat::Sql)
+            .compress(true)
+            .vacuum(true)
+            .noleader(true)
+            .query_params()
+            .into_reqwest_query();
+
+        assert_eq!(
+            params,
+            vec![
+                ("fmt".to_string(), "sql".to_string()),
+                ("compress".to_string(), "true".to_string()),
+                ("vacuum".to_string(), "true".to_string()),
+                ("noleader".to_string(), "true".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unit_backup_options_expected_header() {
+        assert_eq!(
+            BackupOptions::new().expected_header(),
+            Some(SQLITE_HEADER.as_slice())
+        );
+        assert_eq!(
+            BackupOptions::new().compress(true).expected_header(),
+            Some(GZIP_MAGIC.as_slice())
+        );
+        assert_eq!(
+            BackupOptions::new()
+                .format(BackupFormat::Sql)
+                .expected_header(),
+            None
+        );
+    }
+}
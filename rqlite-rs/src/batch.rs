@@ -1,6 +1,10 @@
-use crate::{prelude::QueryResult, select::RqliteSelectResults};
+use crate::{
+    error::RequestError, prelude::QueryResult, response::RqliteResult, select::RqliteSelectResults,
+};
 
+use rqlite_rs_core::Row;
 use serde::Deserialize;
+use serde_json::Value;
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -8,3 +12,140 @@ pub enum BatchResult {
     SelectResults(RqliteSelectResults),
     QueryResult(QueryResult),
 }
+
+/// The outcome of a single statement sent through [`RqliteClient::request`](crate::RqliteClient::request).
+#[derive(Debug)]
+pub enum StatementOutcome {
+    /// The rows returned by a read statement.
+    Rows(Vec<Row>),
+    /// The result of a write statement.
+    Exec(QueryResult),
+    /// The statement at `index` failed with `message`.
+    Error { index: usize, message: String },
+}
+
+impl StatementOutcome {
+    /// Returns the rows if the statement was a successful read.
+    #[must_use]
+    pub fn into_rows(self) -> Option<Vec<Row>> {
+        match self {
+            Self::Rows(rows) => Some(rows),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`QueryResult`] if the statement was a successful write.
+    #[must_use]
+    pub fn into_exec(self) -> Option<QueryResult> {
+        match self {
+            Self::Exec(result) => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the statement failed.
+    #[must_use]
+    pub const fn is_error(&self) -> bool {
+        matches!(self, Self::Error { .. })
+    }
+
+    /// Converts the raw results of a `db/request` call into outcomes.
+    /// `reads` holds for every statement whether it returns rows.
+    pub(crate) fn from_results(
+        results: Vec<RqliteResult<Value>>,
+        reads: &[bool],
+    ) -> Result<Vec<Self>, RequestError> {
+        if results.len() != reads.len() {
+            return Err(RequestError::ResultCountMismatch {
+                expected: reads.len(),
+                actual: results.len(),
+            });
+        }
+
+        results
+            .into_iter()
+            .zip(reads)
+            .enumerate()
+            .map(|(index, (result, is_read))| match result {
+                RqliteResult::Error(e) => Ok(Self::Error {
+                    index,
+                    message: e.error,
+                }),
+                RqliteResult::Success(value) if *is_read => {
+                    serde_json::from_value::<RqliteSelectResults>(value)
+                        .map(|results| Self::Rows(results.rows()))
+                        .map_err(RequestError::FailedParseResponseBody)
+                }
+                RqliteResult::Success(value) => serde_json::from_value::<QueryResult>(value)
+                    .map(Self::Exec)
+                    .map_err(RequestError::FailedParseResponseBody),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::response::RqliteResponseRaw;
+
+    #[test]
+    fn unit_statement_outcome_from_results() {
+        let body = r#"{"results": [
+            {"columns": ["id", "name"], "types": ["integer", "text"]},
+            {"columns": ["id"], "types": ["integer"], "values": [[1], [2]]},
+            {"last_insert_id": 3, "rows_affected": 1},
+            {"error": "no such table: bar"}
+        ]}"#;
+
+        let results = serde_json::from_str::<RqliteResponseRaw<Value>>(body)
+            .unwrap()
+            .results;
+
+        let outcomes =
+            StatementOutcome::from_results(results, &[true, true, false, false]).unwrap();
+
+        assert_eq!(outcomes.len(), 4);
+        assert!(matches!(&outcomes[0], StatementOutcome::Rows(rows) if rows.is_empty()));
+        assert!(matches!(&outcomes[1], StatementOutcome::Rows(rows) if rows.len() == 2));
+        assert!(
+            matches!(&outcomes[2], StatementOutcome::Exec(result) if result.last_insert_id() == Some(3))
+        );
+        assert!(matches!(
+            &outcomes[3],
+            StatementOutcome::Error { index: 3, message } if message == "no such table: bar"
+        ));
+    }
+
+    #[test]
+    fn unit_statement_outcome_from_results_count_mismatch() {
+        let results = vec![RqliteResult::Success(
+            serde_json::json!({"rows_affected": 1}),
+        )];
+
+        let err = StatementOutcome::from_results(results, &[false, true]).unwrap_err();
+
+        assert!(matches!(
+            err,
+            RequestError::ResultCountMismatch {
+                expected: 2,
+                actual: 1
+            }
+        ));
+    }
+
+    #[test]
+    fn unit_statement_outcome_accessors() {
+        let outcome = StatementOutcome::Rows(vec![]);
+        assert!(!outcome.is_error());
+        assert!(outcome.into_rows().is_some());
+
+        let outcome = StatementOutcome::Error {
+            index: 0,
+            message: "error".to_string(),
+        };
+        assert!(outcome.is_error());
+        assert!(outcome.into_exec().is_none());
+    }
+}
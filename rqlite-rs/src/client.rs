@@ -1,26 +1,73 @@
-use std::sync::{Arc, RwLock};
+use std::{
+    num::NonZeroU16,
+    path::Path,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 
 use crate::{
-    batch::BatchResult,
+    backup::BackupOptions,
+    batch::{BatchResult, StatementOutcome},
     config::{self, RqliteClientConfig, RqliteClientConfigBuilder},
-    error::{ClientBuilderError, RequestError},
-    fallback::{FallbackCount, FallbackStrategy},
+    discovery::{self, BackgroundTask, Discovery, DiscoveryConfig, Spawn},
+    error::{BackupError, ClientBuilderError, LoadError, RequestError},
+    fallback::{FallbackContext, FallbackCount, FallbackStrategy, HostHistory, SuccessContext},
+    health::{HealthCheck, HealthChecker, HealthRegistry, HostHealth},
+    load::{FileBody, LoadOptions, LoadResult},
     node::{Node, NodeResponse, RemoveNodeRequest},
-    query::{self, QueryArgs, RqliteQuery},
+    query::{self, Operation, QueryArgs, RqliteQuery},
     query_result::QueryResult,
-    request::{RequestOptions, RqliteQueryParam, RqliteQueryParams},
-    response::{RqliteResponseRaw, RqliteResult},
+    request::{
+        RequestOptions, RequestOpts, RqliteQueryParam, RqliteQueryParams, DEFAULT_TIMEOUT,
+        TIMEOUT_MARGIN,
+    },
+    response::{
+        DetailedResponse, QueuedResponseRaw, ResponseMeta, RqliteResponseRaw, RqliteResult,
+    },
+    retry::{
+        is_leadership_error, RetryContext, RetryDecision, RetryErrorKind, RetryPolicy,
+        MAX_SAME_HOST_RETRIES,
+    },
     select::RqliteSelectResults,
+    status::{Expvar, ReplicationLag, Status},
 };
 use base64::{engine::general_purpose, Engine};
 use reqwest::header;
 use rqlite_rs_core::Row;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 /// A client for interacting with a rqlite cluster.
 pub struct RqliteClient {
     client: reqwest::Client,
     hosts: Arc<RwLock<Vec<String>>>,
     config: RqliteClientConfig,
+    /// The address of the leader, if leader routing is enabled and it has been discovered.
+    leader: RwLock<Option<String>>,
+    /// The hosts passed to the builder, kept when refreshing the hosts.
+    seed_hosts: Vec<String>,
+    /// The health of the hosts, if health checks are enabled.
+    health: Arc<HealthRegistry>,
+    /// The results of previous requests, per host, passed to the [`FallbackStrategy`].
+    history: RwLock<HostHistory>,
+}
+
+/// Strips the scheme from an `api_addr` as returned by the `/nodes` endpoint.
+pub(crate) fn api_host(api_addr: &str) -> &str {
+    api_addr
+        .strip_prefix("http://")
+        .or_else(|| api_addr.strip_prefix("https://"))
+        .unwrap_or(api_addr)
+        .trim_end_matches('/')
+}
+
+/// Returns the `host:port` a response was received from.
+fn url_host(url: &reqwest::Url) -> Option<String> {
+    let host = url.host_str()?;
+
+    Some(
+        url.port()
+            .map_or_else(|| host.to_string(), |port| format!("{host}:{port}")),
+    )
 }
 
 /// A builder for creating a [`RqliteClient`].
@@ -32,6 +79,10 @@ pub struct RqliteClientBuilder {
     config: RqliteClientConfigBuilder,
     // The base64 encoded credentials used to make authorized requests to the Rqlite cluster
     basic_auth: Option<String>,
+    /// Refreshes the hosts in the background, if enabled.
+    discovery: Option<DiscoveryConfig>,
+    /// Checks the health of the hosts in the background, if enabled.
+    health_check: Option<(HealthCheck, Spawn)>,
 }
 
 impl RqliteClientBuilder {
@@ -79,6 +130,7 @@ impl RqliteClientBuilder {
 
     /// Sets the fallback count for the client.
     /// The fallback count is the number of times the client will try to switch to another host if the current host fails.
+    /// It also limits the hosts a [`RetryPolicy`] can switch to.
     #[must_use]
     pub fn fallback_count(mut self, count: FallbackCount) -> Self {
         self.config = self.config.fallback_count(count);
@@ -103,6 +155,86 @@ impl RqliteClientBuilder {
         self
     }
 
+    /// Enables leader-aware routing of writes.
+    /// If enabled, writes are sent directly to the leader, which is discovered using the `/nodes`
+    /// endpoint, instead of relying on the receiving node to forward them.
+    /// The leader is rediscovered when the leadership changes.
+    /// If the leader is unreachable, the request falls back to the known hosts using the
+    /// configured fallback strategy.
+    /// Disabled by default.
+    #[must_use]
+    pub fn leader_routing(mut self, enabled: bool) -> Self {
+        self.config = self.config.leader_routing(enabled);
+        self
+    }
+
+    /// Sets the retry policy for the client.
+    /// The retry policy decides whether a failed request is retried on the same host, on another
+    /// host or not at all, and how long to wait before retrying.
+    /// Without a retry policy, only connection errors and timeouts are retried on another host,
+    /// up to the configured fallback count.
+    /// See [`ExponentialBackoff`](crate::retry::ExponentialBackoff) for the built-in policy.
+    #[must_use]
+    pub fn retry_policy(mut self, policy: impl RetryPolicy) -> Self {
+        self.config = self.config.retry_policy(policy);
+        self
+    }
+
+    /// Enables the discovery of hosts in the background.
+    /// Every `interval`, the nodes of the cluster are fetched from the `/nodes` endpoint and the
+    /// known hosts are updated: nodes that joined the cluster are added and nodes that were
+    /// removed are dropped. The hosts passed to the builder are always kept as a last resort.
+    ///
+    /// The discovery runs as a task which is passed to `spawn` when the client is built, so any
+    /// async runtime can be used. The task finishes once the client is dropped.
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use rqlite_rs::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RqliteClientBuilder::new()
+    ///     .known_host("localhost:4001")
+    ///     .host_discovery(Duration::from_secs(30), |task| {
+    ///         tokio::spawn(task);
+    ///     })
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// See [`RqliteClient::refresh_hosts`] to refresh the hosts manually.
+    #[must_use]
+    pub fn host_discovery(
+        mut self,
+        interval: Duration,
+        spawn: impl FnOnce(BackgroundTask) + Send + 'static,
+    ) -> Self {
+        self.discovery = Some(DiscoveryConfig {
+            interval,
+            spawn: Box::new(spawn),
+        });
+        self
+    }
+
+    /// Enables health checks of the known hosts.
+    /// The `/readyz` endpoint of every host is probed periodically, as configured by `check`.
+    /// Hosts failing the checks are quarantined: requests and the fallback strategy skip them
+    /// until they pass again, unless all hosts are quarantined.
+    ///
+    /// The checks run as a task which is passed to `spawn` when the client is built, so any
+    /// async runtime can be used. The task finishes once the client is dropped.
+    ///
+    /// See [`RqliteClient::host_health`] for the current health of the hosts.
+    #[must_use]
+    pub fn health_check(
+        mut self,
+        check: HealthCheck,
+        spawn: impl FnOnce(BackgroundTask) + Send + 'static,
+    ) -> Self {
+        self.health_check = Some((check, Box::new(spawn)));
+        self
+    }
+
     /// Sets the scheme for the client.
     #[must_use]
     pub fn scheme(mut self, scheme: config::Scheme) -> Self {
@@ -140,18 +272,50 @@ impl RqliteClientBuilder {
         }
 
         let mut client = reqwest::ClientBuilder::new()
-            .timeout(std::time::Duration::from_secs(5))
+            .timeout(DEFAULT_TIMEOUT)
             .default_headers(headers);
 
         if matches!(self.config.scheme, Some(config::Scheme::Https)) {
             client = client.https_only(true);
         }
 
-        Ok(RqliteClient {
+        let client = RqliteClient {
             client: client.build()?,
-            hosts: Arc::new(RwLock::new(hosts)),
+            hosts: Arc::new(RwLock::new(hosts.clone())),
             config: self.config.build(),
-        })
+            leader: RwLock::new(None),
+            seed_hosts: hosts,
+            health: Arc::new(HealthRegistry::default()),
+            history: RwLock::new(HostHistory::default()),
+        };
+
+        if let Some((check, spawn)) = self.health_check {
+            let task = HealthChecker {
+                client: client.client.clone(),
+                scheme: client.config.scheme.clone(),
+                hosts: Arc::downgrade(&client.hosts),
+                health: Arc::downgrade(&client.health),
+                check,
+            }
+            .into_task();
+
+            spawn(task);
+        }
+
+        if let Some(discovery) = self.discovery {
+            let task = Discovery {
+                client: client.client.clone(),
+                scheme: client.config.scheme.clone(),
+                hosts: Arc::downgrade(&client.hosts),
+                seeds: client.seed_hosts.clone(),
+                interval: discovery.interval,
+            }
+            .into_task();
+
+            (discovery.spawn)(task);
+        }
+
+        Ok(client)
     }
 }
 
@@ -160,89 +324,411 @@ impl RqliteClient {
         &self,
         mut options: RequestOptions,
     ) -> Result<reqwest::Response, RequestError> {
-        let (mut host, host_count) = {
-            let hosts = self
-                .hosts
-                .read()
-                .map_err(|_poisoned| RequestError::LockPoisoned)?;
-            let first_host = hosts.first().ok_or(RequestError::NoAvailableHosts)?;
-            (first_host.clone(), hosts.len())
-        };
-
-        let retry_count = self.config.fallback_count.count(host_count);
-
         if let Some(default_params) = &self.config.default_query_params {
             options.merge_default_query_params(default_params);
         }
 
-        for _ in 0..retry_count {
+        self.try_request_without_defaults(options).await
+    }
+
+    /// Sends the request without adding the default query parameters of the client,
+    /// for endpoints that do not accept them.
+    async fn try_request_without_defaults(
+        &self,
+        options: RequestOptions,
+    ) -> Result<reqwest::Response, RequestError> {
+        if self.config.leader_routing && options.is_write() {
+            return self.try_leader_request(&options).await;
+        }
+
+        self.try_request_on(&options, None)
+            .await
+            .map(|(res, _host)| res)
+    }
+
+    /// Sends a write directly to the cached leader, discovering it first if necessary.
+    /// If the leadership changed in the meantime, the leader is discovered again and the
+    /// request is retried once.
+    async fn try_leader_request(
+        &self,
+        options: &RequestOptions,
+    ) -> Result<reqwest::Response, RequestError> {
+        let mut refreshed = false;
+
+        loop {
+            let mut leader = self.cached_leader()?;
+
+            if leader.is_none() {
+                refreshed = true;
+                leader = self.refresh_leader().await;
+            }
+
+            match self.try_request_on(options, leader.as_deref()).await {
+                Ok((res, host)) => {
+                    // The request was redirected to the actual leader
+                    if let Some(redirected) = url_host(res.url()).filter(|h| *h != host) {
+                        tracing::debug!(
+                            "Request was redirected from {host} to leader {redirected}"
+                        );
+                        self.set_cached_leader(Some(redirected))?;
+                    }
+                    return Ok(res);
+                }
+                Err(RequestError::ReqwestError { body, status })
+                    if is_leadership_error(status, &body) =>
+                {
+                    tracing::info!("Leadership changed, rediscovering leader");
+                    self.set_cached_leader(None)?;
+
+                    if refreshed {
+                        return Err(RequestError::ReqwestError { body, status });
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Sends the request to `preferred_host` if given, otherwise to the host selected by the
+    /// [`FallbackStrategy`], which is the first known host unless the strategy overrides it.
+    /// On connection errors the configured [`FallbackStrategy`] picks the next host.
+    /// If a [`RetryPolicy`](crate::retry::RetryPolicy) is configured, it decides how to handle
+    /// connection errors, timeouts and server errors instead, while the [`FallbackCount`] still
+    /// limits the number of hosts tried.
+    /// If the preferred host is unreachable, the request continues with the known hosts.
+    /// Returns the response together with the host that answered it.
+    async fn try_request_on(
+        &self,
+        options: &RequestOptions,
+        preferred_host: Option<&str>,
+    ) -> Result<(reqwest::Response, String), RequestError> {
+        let (first_host, host_count) = self.select_host()?;
+
+        let mut retry_count = self.config.fallback_count.count(host_count);
+
+        // The preferred host is tried in addition to the regular fallback attempts
+        if preferred_host.is_some() {
+            retry_count = retry_count.saturating_add(1);
+        }
+
+        let mut host = preferred_host.map_or_else(|| first_host.clone(), str::to_string);
+        let mut on_preferred_host = preferred_host.is_some();
+        let mut attempt: u32 = 0;
+        let mut hosts_tried: usize = 1;
+        let mut same_host_retries: u32 = 0;
+        let request_started = Instant::now();
+
+        loop {
+            attempt = attempt.saturating_add(1);
             tracing::debug!("Trying host: {host}");
             let req = options.to_reqwest_request(&self.client, host.as_str(), &self.config.scheme);
 
-            match req.send().await {
-                Ok(res) if res.status().is_success() => return Ok(res),
-                Ok(res) => match res.status() {
-                    reqwest::StatusCode::UNAUTHORIZED => {
-                        return Err(RequestError::Unauthorized);
-                    }
-                    status => {
-                        return Err(RequestError::ReqwestError {
-                            body: res.text().await?,
-                            status,
-                        });
+            let attempt_started = Instant::now();
+
+            let (kind, error) = match req.send().await {
+                Ok(res) if res.status().is_success() => {
+                    self.notify_success(
+                        &host,
+                        attempt_started.elapsed(),
+                        attempt,
+                        request_started.elapsed(),
+                        options,
+                    );
+                    return Ok((res, host));
+                }
+                Ok(res) if res.status() == reqwest::StatusCode::UNAUTHORIZED => {
+                    return Err(RequestError::Unauthorized);
+                }
+                Ok(res) => {
+                    let status = res.status();
+                    let body = res.text().await?;
+
+                    // Error responses are only retried if a retry policy is configured
+                    let kind = RetryErrorKind::from_status(status, &body)
+                        .filter(|_kind| self.config.retry_policy.is_some());
+
+                    match kind {
+                        Some(kind) => (kind, RequestError::ReqwestError { body, status }),
+                        None => return Err(RequestError::ReqwestError { body, status }),
                     }
+                }
+                Err(e) => match RetryErrorKind::from_reqwest_error(&e) {
+                    Some(kind) => (kind, RequestError::RequestFailed(e)),
+                    None => return Err(RequestError::SwitchoverWrongError(e.to_string())),
                 },
-                Err(e) => self.handle_request_error(&e, &mut host)?,
+            };
+
+            self.history
+                .write()
+                .map_err(|_poisoned| RequestError::LockPoisoned)?
+                .record_failure(&host, kind);
+
+            // Queued writes stay queued on the node, sending them again would apply them twice
+            if options.queued && kind.may_have_been_applied() {
+                return Err(error);
+            }
+
+            let delay = match self.retry_decision(kind, attempt, options) {
+                RetryDecision::GiveUp => return Err(error),
+                RetryDecision::RetrySameHost(_delay)
+                    if same_host_retries >= MAX_SAME_HOST_RETRIES =>
+                {
+                    return Err(error);
+                }
+                RetryDecision::RetrySameHost(delay) => {
+                    same_host_retries = same_host_retries.saturating_add(1);
+
+                    // The cached leader is likely stale, so it is discovered again by the next write
+                    if kind == RetryErrorKind::LeadershipLost && self.config.leader_routing {
+                        self.set_cached_leader(None)?;
+                    }
+                    delay
+                }
+                // The fallback count limits the hosts tried, with and without a retry policy
+                RetryDecision::SwitchHost(_delay) if hosts_tried >= retry_count => {
+                    return match self.config.retry_policy {
+                        Some(_) => Err(error),
+                        None => Err(RequestError::NoAvailableHosts),
+                    };
+                }
+                RetryDecision::SwitchHost(delay) => {
+                    hosts_tried = hosts_tried.saturating_add(1);
+                    same_host_retries = 0;
+
+                    if on_preferred_host {
+                        tracing::info!("Request to leader {host} failed, trying {first_host}");
+                        on_preferred_host = false;
+                        self.set_cached_leader(None)?;
+                        host.clone_from(&first_host);
+                    } else {
+                        self.switch_host(
+                            &mut host,
+                            kind,
+                            attempt,
+                            request_started.elapsed(),
+                            options,
+                        )?;
+                    }
+                    delay
+                }
+            };
+
+            if !delay.is_zero() {
+                tracing::debug!("Retrying in {delay:?}");
+                futures_timer::Delay::new(delay).await;
+            }
+        }
+    }
+
+    /// Asks the configured [`RetryPolicy`] how to handle a failed attempt.
+    /// Without a retry policy, hosts are switched immediately.
+    fn retry_decision(
+        &self,
+        error: RetryErrorKind,
+        attempt: u32,
+        options: &RequestOptions,
+    ) -> RetryDecision {
+        self.config.retry_policy.as_ref().map_or(
+            RetryDecision::SwitchHost(Duration::ZERO),
+            |policy| {
+                policy.decide(&RetryContext {
+                    error,
+                    attempt,
+                    is_write: options.is_write(),
+                })
+            },
+        )
+    }
+
+    /// Selects the host a new request is sent to using the configured [`FallbackStrategy`].
+    /// Returns the host together with the number of known hosts.
+    fn select_host(&self) -> Result<(String, usize), RequestError> {
+        let hosts = self
+            .hosts
+            .read()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?;
+
+        // Quarantined hosts are skipped, unless all hosts are quarantined
+        let host = self
+            .config
+            .fallback_strategy
+            .write()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?
+            .select(&hosts)
+            .filter(|host| !self.health.is_quarantined(host))
+            .or_else(|| hosts.iter().find(|host| !self.health.is_quarantined(host)))
+            .or_else(|| hosts.first())
+            .ok_or(RequestError::NoAvailableHosts)?
+            .clone();
+
+        Ok((host, hosts.len()))
+    }
+
+    fn cached_leader(&self) -> Result<Option<String>, RequestError> {
+        Ok(self
+            .leader
+            .read()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?
+            .clone())
+    }
+
+    fn set_cached_leader(&self, leader: Option<String>) -> Result<(), RequestError> {
+        *self
+            .leader
+            .write()
+            .map_err(|_poisoned| RequestError::LockPoisoned)? = leader;
+        Ok(())
+    }
+
+    /// Discovers the current leader and caches its address.
+    /// Returns `None` if the leader could not be determined.
+    async fn refresh_leader(&self) -> Option<String> {
+        // Boxed, because discovering the leader issues a request itself
+        let leader = match Box::pin(self.leader()).await {
+            Ok(leader) => leader.map(|node| api_host(&node.api_addr).to_string()),
+            Err(e) => {
+                tracing::warn!("Failed to discover leader: {e}");
+                None
             }
+        };
+
+        if let Some(leader) = &leader {
+            tracing::debug!("Discovered leader: {leader}");
         }
 
-        Err(RequestError::NoAvailableHosts)
+        self.set_cached_leader(leader.clone()).ok()?;
+
+        leader
     }
 
-    /// Handles the error returned by the request.
-    /// If the error is a connection error or a timeout, it will try to switch to another host.
-    /// If the error is not a connection error or a timeout, it will return an error.
-    fn handle_request_error(
+    /// Records a successful request and notifies the configured [`FallbackStrategy`].
+    fn notify_success(
         &self,
-        e: &reqwest::Error,
-        host: &mut String, // warum wird host gepasst? wird nicht alles über self.hosts gemacht?
-    ) -> Result<(), RequestError> {
-        if e.is_connect() || e.is_timeout() {
-            let previous_host = host.clone();
-            let mut writable_hosts = self
-                .hosts
-                .write()
-                .map_err(|_poisoned| RequestError::LockPoisoned)?;
+        host: &str,
+        latency: Duration,
+        attempt: u32,
+        elapsed: Duration,
+        options: &RequestOptions,
+    ) {
+        // A poisoned lock must not fail a request that succeeded
+        if let Ok(mut history) = self.history.write() {
+            history.record_success(host, latency);
+        }
 
-            let new_host = self
-                .config
-                .fallback_strategy
-                .write()
-                .map_err(|_poisoned| RequestError::LockPoisoned)?
-                .fallback(&mut writable_hosts, host, self.config.fallback_persistence)
-                .ok_or(RequestError::NoAvailableHosts)?;
+        // The strategy is always locked before the history
+        let Ok(mut strategy) = self.config.fallback_strategy.write() else {
+            return;
+        };
+        let Ok(history) = self.history.read() else {
+            return;
+        };
 
-            host.clone_from(new_host);
-            tracing::info!("Connection to {} failed, trying {}", previous_host, *host);
-            Ok(())
-        } else {
-            Err(RequestError::SwitchoverWrongError(e.to_string()))
+        strategy.on_success_with_context(&SuccessContext {
+            host,
+            latency,
+            attempt,
+            elapsed,
+            operation: options.operation,
+            is_write: options.is_write(),
+            history: &history,
+        });
+    }
+
+    /// Switches to another host using the configured [`FallbackStrategy`].
+    /// Quarantined hosts are skipped, unless the strategy only returns quarantined hosts.
+    fn switch_host(
+        &self,
+        host: &mut String,
+        error: RetryErrorKind,
+        attempt: u32,
+        elapsed: Duration,
+        options: &RequestOptions,
+    ) -> Result<(), RequestError> {
+        let previous_host = host.clone();
+        let mut writable_hosts = self
+            .hosts
+            .write()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?;
+        let mut strategy = self
+            .config
+            .fallback_strategy
+            .write()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?;
+        let history = self
+            .history
+            .read()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?;
+
+        let mut candidate = previous_host.clone();
+        let mut first_candidate = None;
+
+        for skipped in 0..writable_hosts.len().max(1) {
+            let next = strategy
+                .fallback_with_context(
+                    &mut writable_hosts,
+                    &FallbackContext {
+                        current_host: &candidate,
+                        persist: self.config.fallback_persistence,
+                        // Only the first host was actually tried
+                        error: (skipped == 0).then_some(error),
+                        attempt,
+                        elapsed,
+                        operation: options.operation,
+                        is_write: options.is_write(),
+                        history: &history,
+                    },
+                )
+                .ok_or(RequestError::NoAvailableHosts)?
+                .clone();
+
+            if !self.health.is_quarantined(&next) {
+                first_candidate = None;
+                candidate = next;
+                break;
+            }
+
+            tracing::debug!("Skipping quarantined host {next}");
+            first_candidate.get_or_insert_with(|| next.clone());
+            candidate = next;
         }
+
+        drop((history, strategy, writable_hosts));
+
+        *host = first_candidate.unwrap_or(candidate);
+        tracing::info!("Request to {} failed, trying {}", previous_host, *host);
+        Ok(())
     }
 
-    async fn exec_query<T>(&self, q: query::RqliteQuery) -> Result<RqliteResult<T>, RequestError>
+    /// Sends a single query. If `fetch` is set, the rows returned by writes with a `RETURNING`
+    /// clause are requested as well.
+    async fn exec_query<T>(
+        &self,
+        q: query::RqliteQuery,
+        opts: RequestOpts,
+        fetch: bool,
+    ) -> Result<RqliteResult<T>, RequestError>
     where
         T: serde::de::DeserializeOwned + Clone,
     {
+        let endpoint = if fetch {
+            q.fetch_endpoint()
+        } else {
+            q.endpoint()
+        };
+
         let res = self
-            .try_request(RequestOptions {
-                endpoint: q.endpoint(),
-                body: Some(
-                    q.into_json()
-                        .map_err(RequestError::FailedParseRequestBody)?,
-                ),
-                ..Default::default()
-            })
+            .try_request(
+                RequestOptions {
+                    endpoint,
+                    operation: Some(q.op),
+                    body: Some(
+                        q.into_json()
+                            .map_err(RequestError::FailedParseRequestBody)?,
+                    ),
+                    ..Default::default()
+                }
+                .with_opts(opts),
+            )
             .await?;
 
         let body = res.text().await?;
@@ -257,27 +743,6 @@ impl RqliteClient {
             .ok_or(RequestError::NoRowsReturned)
     }
 
-    // To be implemented for different types of queries such as batch or qeued queries
-    //async fn exec_many<T>(
-    //    &self,
-    //    qs: Vec<query::RqliteQuery>,
-    //    params: impl Into<Option<Vec<RequestQueryParam>>>,
-    //) -> anyhow::Result<Vec<RqliteResult<T>>>
-    //where
-    //    T: serde::de::DeserializeOwned + Clone,
-    //{
-    //    let args = QueryArgs::from(qs);
-    //    let body = serde_json::to_string(&args)?;
-    //
-    //    let res = self.try_request("request", body, None).await?;
-    //
-    //    let body = res.text().await?;
-    //
-    //    let response = serde_json::from_str::<RqliteResponseRaw<T>>(&body)?;
-    //
-    //    Ok(response.results)
-    //}
-
     /// Executes a query that returns results.
     /// Returns a vector of [`Row`]s if the query was successful, otherwise an error.
     ///
@@ -289,12 +754,30 @@ impl RqliteClient {
     /// - The response could not be parsed
     /// - The database returned an error
     pub async fn fetch<Q>(&self, q: Q) -> Result<Vec<Row>, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        self.fetch_with(q, RequestOpts::default()).await
+    }
+
+    /// Executes a query that returns results, using the given [`RequestOpts`].
+    /// See [`RqliteClient::fetch`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    /// - The database returned an error
+    pub async fn fetch_with<Q>(&self, q: Q, opts: RequestOpts) -> Result<Vec<Row>, RequestError>
     where
         Q: TryInto<RqliteQuery>,
         RequestError: From<Q::Error>,
     {
         let result = self
-            .exec_query::<RqliteSelectResults>(q.try_into()?)
+            .exec_query::<RqliteSelectResults>(q.try_into()?, opts, true)
             .await?;
 
         match result {
@@ -319,7 +802,27 @@ impl RqliteClient {
         Q: TryInto<RqliteQuery>,
         RequestError: From<Q::Error>,
     {
-        let query_result = self.exec_query::<QueryResult>(q.try_into()?).await?;
+        self.exec_with(q, RequestOpts::default()).await
+    }
+
+    /// Executes a query that does not return any results, using the given [`RequestOpts`].
+    /// See [`RqliteClient::exec`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    /// - The database returned an error
+    pub async fn exec_with<Q>(&self, q: Q, opts: RequestOpts) -> Result<QueryResult, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        let query_result = self
+            .exec_query::<QueryResult>(q.try_into()?, opts, false)
+            .await?;
 
         match query_result {
             RqliteResult::Success(qr) => Ok(qr),
@@ -327,6 +830,118 @@ impl RqliteClient {
         }
     }
 
+    /// Sends the request and returns the response body together with the node that served it.
+    async fn request_detailed(
+        &self,
+        options: RequestOptions,
+    ) -> Result<(String, ResponseMeta, String), RequestError> {
+        let res = self.try_request(options).await?;
+        let node = url_host(res.url()).unwrap_or_default();
+        let body = res.text().await?;
+
+        let meta = serde_json::from_str::<ResponseMeta>(&body)
+            .map_err(RequestError::FailedParseResponseBody)?;
+
+        Ok((body, meta, node))
+    }
+
+    /// Builds the request options for a single query of a `*_detailed` method.
+    /// Timings and the Raft index are always requested.
+    fn detailed_query_options(
+        q: RqliteQuery,
+        opts: RequestOpts,
+        fetch: bool,
+    ) -> Result<RequestOptions, RequestError> {
+        let endpoint = if fetch {
+            q.fetch_endpoint()
+        } else {
+            q.endpoint()
+        };
+
+        Ok(RequestOptions {
+            endpoint,
+            operation: Some(q.op),
+            body: Some(
+                q.into_json()
+                    .map_err(RequestError::FailedParseRequestBody)?,
+            ),
+            ..Default::default()
+        }
+        .with_opts(opts.timings().raft_index()))
+    }
+
+    /// Executes a query that returns results, like [`RqliteClient::fetch_with`].
+    /// The rows are returned in a [`DetailedResponse`], which includes the execution time,
+    /// the Raft index and the node that served the request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    /// - The database returned an error
+    pub async fn fetch_detailed<Q>(
+        &self,
+        q: Q,
+        opts: RequestOpts,
+    ) -> Result<DetailedResponse<Vec<Row>>, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        let options = Self::detailed_query_options(q.try_into()?, opts, true)?;
+        let (body, meta, node) = self.request_detailed(options).await?;
+
+        let result = serde_json::from_str::<RqliteResponseRaw<RqliteSelectResults>>(&body)
+            .map_err(RequestError::FailedParseResponseBody)?
+            .results
+            .into_iter()
+            .next()
+            .ok_or(RequestError::NoRowsReturned)?;
+
+        match result {
+            RqliteResult::Success(qr) => Ok(meta.into_detailed(qr.rows(), node)),
+            RqliteResult::Error(qe) => Err(RequestError::DatabaseError(qe.error)),
+        }
+    }
+
+    /// Executes a query that does not return any results, like [`RqliteClient::exec_with`].
+    /// The [`QueryResult`] is returned in a [`DetailedResponse`], which includes the execution
+    /// time, the Raft index and the node that served the request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    /// - The database returned an error
+    pub async fn exec_detailed<Q>(
+        &self,
+        q: Q,
+        opts: RequestOpts,
+    ) -> Result<DetailedResponse<QueryResult>, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        let options = Self::detailed_query_options(q.try_into()?, opts, false)?;
+        let (body, meta, node) = self.request_detailed(options).await?;
+
+        let result = serde_json::from_str::<RqliteResponseRaw<QueryResult>>(&body)
+            .map_err(RequestError::FailedParseResponseBody)?
+            .results
+            .into_iter()
+            .next()
+            .ok_or(RequestError::NoRowsReturned)?;
+
+        match result {
+            RqliteResult::Success(qr) => Ok(meta.into_detailed(qr, node)),
+            RqliteResult::Error(qe) => Err(RequestError::DatabaseError(qe.error)),
+        }
+    }
+
     /// Executes a batch of queries.
     /// It allows sending multiple queries in a single request.
     /// This can be more efficient and reduces round-trips to the database.
@@ -344,6 +959,28 @@ impl RqliteClient {
     /// - The response could not be parsed
     /// - The database returned an error
     pub async fn batch<Q>(&self, qs: Vec<Q>) -> Result<Vec<RqliteResult<BatchResult>>, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        self.batch_with(qs, RequestOpts::default()).await
+    }
+
+    /// Executes a batch of queries, using the given [`RequestOpts`].
+    /// See [`RqliteClient::batch`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    /// - The database returned an error
+    pub async fn batch_with<Q>(
+        &self,
+        qs: Vec<Q>,
+        opts: RequestOpts,
+    ) -> Result<Vec<RqliteResult<BatchResult>>, RequestError>
     where
         Q: TryInto<RqliteQuery>,
         RequestError: From<Q::Error>,
@@ -353,15 +990,20 @@ impl RqliteClient {
             .map(std::convert::TryInto::try_into)
             .collect::<Result<Vec<RqliteQuery>, _>>()?;
 
+        let operation = Operation::of_queries(&queries);
         let batch = QueryArgs::from(queries);
         let body = serde_json::to_string(&batch).map_err(RequestError::FailedParseRequestBody)?;
 
         let res = self
-            .try_request(RequestOptions {
-                endpoint: "db/request".to_string(),
-                body: Some(body),
-                ..Default::default()
-            })
+            .try_request(
+                RequestOptions {
+                    endpoint: "db/request".to_string(),
+                    body: Some(body),
+                    operation,
+                    ..Default::default()
+                }
+                .with_opts(opts),
+            )
             .await?;
 
         let body = res.text().await?;
@@ -373,12 +1015,9 @@ impl RqliteClient {
         Ok(results)
     }
 
-    /// Executes a transaction.
-    /// A transaction is a set of queries that are executed as a single unit.
-    /// If any of the queries fail, the entire transaction is rolled back.
-    /// Returns a vector of [`RqliteResult`]s.
-    ///
-    /// For more information on transactions, see the [rqlite documentation](https://rqlite.io/docs/api/api/#transactions).
+    /// Executes a batch of queries, like [`RqliteClient::batch_with`].
+    /// The results are returned in a [`DetailedResponse`], which includes the execution time of
+    /// every statement, the Raft index and the node that served the request.
     ///
     /// # Errors
     ///
@@ -386,12 +1025,11 @@ impl RqliteClient {
     /// - The query could not be converted to a `RqliteQuery`
     /// - The request to the rqlite server failed
     /// - The response could not be parsed
-    /// - The database returned an error
-    /// - The transaction could not be executed
-    pub async fn transaction<Q>(
+    pub async fn batch_detailed<Q>(
         &self,
         qs: Vec<Q>,
-    ) -> Result<Vec<RqliteResult<QueryResult>>, RequestError>
+        opts: RequestOpts,
+    ) -> Result<DetailedResponse<Vec<RqliteResult<BatchResult>>>, RequestError>
     where
         Q: TryInto<RqliteQuery>,
         RequestError: From<Q::Error>,
@@ -401,20 +1039,173 @@ impl RqliteClient {
             .map(std::convert::TryInto::try_into)
             .collect::<Result<Vec<RqliteQuery>, _>>()?;
 
+        let operation = Operation::of_queries(&queries);
         let batch = QueryArgs::from(queries);
         let body = serde_json::to_string(&batch).map_err(RequestError::FailedParseRequestBody)?;
 
-        let res = self
-            .try_request(RequestOptions {
-                endpoint: "db/execute".to_string(),
-                body: Some(body),
-                params: Some(
-                    RqliteQueryParams::new()
-                        .transaction()
-                        .into_request_query_params(),
-                ),
-                ..Default::default()
-            })
+        let (body, meta, node) = self
+            .request_detailed(
+                RequestOptions {
+                    endpoint: "db/request".to_string(),
+                    body: Some(body),
+                    operation,
+                    ..Default::default()
+                }
+                .with_opts(opts.timings().raft_index()),
+            )
+            .await?;
+
+        let results = serde_json::from_str::<RqliteResponseRaw<BatchResult>>(&body)
+            .map_err(RequestError::FailedParseResponseBody)?
+            .results;
+
+        Ok(meta.into_detailed(results, node))
+    }
+
+    /// Executes a mix of read and write queries in a single request using the unified endpoint.
+    /// Each query is classified by its [`Operation`](crate::query::Operation), so the result of
+    /// a read is always returned as rows, even if no rows matched.
+    /// Returns one [`StatementOutcome`] per query, in the order the queries were passed.
+    ///
+    /// For more information on the unified endpoint, see the [rqlite documentation](https://rqlite.io/docs/api/api/#unified-endpoint).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    pub async fn request<Q>(&self, qs: Vec<Q>) -> Result<Vec<StatementOutcome>, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        self.request_with(qs, RequestOpts::default()).await
+    }
+
+    /// Executes a mix of read and write queries using the given [`RequestOpts`].
+    /// See [`RqliteClient::request`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    pub async fn request_with<Q>(
+        &self,
+        qs: Vec<Q>,
+        opts: RequestOpts,
+    ) -> Result<Vec<StatementOutcome>, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        let queries = qs
+            .into_iter()
+            .map(std::convert::TryInto::try_into)
+            .collect::<Result<Vec<RqliteQuery>, _>>()?;
+
+        let reads = queries
+            .iter()
+            .map(RqliteQuery::returns_rows)
+            .collect::<Vec<_>>();
+
+        let operation = Operation::of_queries(&queries);
+        let batch = QueryArgs::from(queries);
+        let body = serde_json::to_string(&batch).map_err(RequestError::FailedParseRequestBody)?;
+
+        let res = self
+            .try_request(
+                RequestOptions {
+                    endpoint: "db/request".to_string(),
+                    body: Some(body),
+                    operation,
+                    ..Default::default()
+                }
+                .with_opts(opts),
+            )
+            .await?;
+
+        let body = res.text().await?;
+
+        let results = serde_json::from_str::<RqliteResponseRaw<serde_json::Value>>(&body)
+            .map_err(RequestError::FailedParseResponseBody)?
+            .results;
+
+        StatementOutcome::from_results(results, &reads)
+    }
+
+    /// Executes a transaction.
+    /// A transaction is a set of queries that are executed as a single unit.
+    /// If any of the queries fail, the entire transaction is rolled back.
+    /// Returns a vector of [`RqliteResult`]s.
+    ///
+    /// For more information on transactions, see the [rqlite documentation](https://rqlite.io/docs/api/api/#transactions).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    /// - The database returned an error
+    /// - The transaction could not be executed
+    pub async fn transaction<Q>(
+        &self,
+        qs: Vec<Q>,
+    ) -> Result<Vec<RqliteResult<QueryResult>>, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        self.transaction_with(qs, RequestOpts::default()).await
+    }
+
+    /// Executes a transaction, using the given [`RequestOpts`].
+    /// See [`RqliteClient::transaction`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    /// - The database returned an error
+    /// - The transaction could not be executed
+    pub async fn transaction_with<Q>(
+        &self,
+        qs: Vec<Q>,
+        opts: RequestOpts,
+    ) -> Result<Vec<RqliteResult<QueryResult>>, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        let queries = qs
+            .into_iter()
+            .map(std::convert::TryInto::try_into)
+            .collect::<Result<Vec<RqliteQuery>, _>>()?;
+
+        let operation = Operation::of_queries(&queries);
+        let batch = QueryArgs::from(queries);
+        let body = serde_json::to_string(&batch).map_err(RequestError::FailedParseRequestBody)?;
+
+        let res = self
+            .try_request(
+                RequestOptions {
+                    endpoint: "db/execute".to_string(),
+                    body: Some(body),
+                    operation,
+                    params: Some(
+                        RqliteQueryParams::new()
+                            .transaction()
+                            .into_request_query_params(),
+                    ),
+                    ..Default::default()
+                }
+                .with_opts(opts),
+            )
             .await?;
 
         let body = res.text().await?;
@@ -429,6 +1220,9 @@ impl RqliteClient {
     /// Asynchronously executes multiple queries.
     /// This results in much higher write performance.
     ///
+    /// Returns the sequence number rqlite assigned to the request, which can be passed to
+    /// [`RqliteClient::wait_for_sequence`] to wait until the queries have been applied.
+    ///
     /// For more information on queued queries, see the [rqlite documentation](https://rqlite.io/docs/api/queued-writes/).
     ///
     /// # Errors
@@ -438,7 +1232,57 @@ impl RqliteClient {
     /// - The request to the rqlite server failed
     /// - The response could not be parsed
     /// - The database returned an error
-    pub async fn queue<Q>(&self, qs: Vec<Q>) -> Result<(), RequestError>
+    pub async fn queue<Q>(&self, qs: Vec<Q>) -> Result<u64, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        self.queue_with(qs, RequestOpts::default()).await
+    }
+
+    /// Asynchronously executes multiple queries, using the given [`RequestOpts`].
+    /// See [`RqliteClient::queue`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    /// - The database returned an error
+    pub async fn queue_with<Q>(&self, qs: Vec<Q>, opts: RequestOpts) -> Result<u64, RequestError>
+    where
+        Q: TryInto<RqliteQuery>,
+        RequestError: From<Q::Error>,
+    {
+        let queries = qs
+            .into_iter()
+            .map(std::convert::TryInto::try_into)
+            .collect::<Result<Vec<RqliteQuery>, _>>()?;
+
+        self.queue_request(queries, RqliteQueryParams::new().queue(), opts)
+            .await
+    }
+
+    /// Queues multiple queries and waits until they have been applied, or until `timeout`
+    /// seconds have passed.
+    /// Returns the sequence number rqlite assigned to the request.
+    ///
+    /// The queries are never sent to another node once they may have been queued, even if a
+    /// [`RetryPolicy`](crate::retry::RetryPolicy) allows retrying writes, as they would be queued
+    /// twice.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The query could not be converted to a `RqliteQuery`
+    /// - The request to the rqlite server failed, including when the timeout is reached
+    /// - The response could not be parsed
+    pub async fn queue_and_wait<Q>(
+        &self,
+        qs: Vec<Q>,
+        timeout: NonZeroU16,
+    ) -> Result<u64, RequestError>
     where
         Q: TryInto<RqliteQuery>,
         RequestError: From<Q::Error>,
@@ -448,18 +1292,280 @@ impl RqliteClient {
             .map(std::convert::TryInto::try_into)
             .collect::<Result<Vec<RqliteQuery>, _>>()?;
 
+        self.queue_request(
+            queries,
+            RqliteQueryParams::new().queue().wait().timeout(timeout),
+            RequestOpts::default(),
+        )
+        .await
+    }
+
+    /// Waits until the queued queries with the given sequence number have been applied, or until
+    /// `timeout` seconds have passed. Reads issued afterwards see the queued writes.
+    ///
+    /// The queue of the node is flushed by waiting for an empty queued request, which does not
+    /// write anything, as the queue of a node is applied in order. Sequence numbers are assigned
+    /// per node, so this only works for requests sent to the same node.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the rqlite server failed, including when the timeout is reached
+    /// - The response could not be parsed
+    /// - The node has not assigned `sequence_number` yet, so the queries were queued on another
+    ///   node
+    pub async fn wait_for_sequence(
+        &self,
+        sequence_number: u64,
+        timeout: NonZeroU16,
+    ) -> Result<(), RequestError> {
+        let applied = self
+            .queue_request(
+                Vec::new(),
+                RqliteQueryParams::new().queue().wait().timeout(timeout),
+                RequestOpts::default(),
+            )
+            .await?;
+
+        if applied < sequence_number {
+            return Err(RequestError::SequenceNumberNotAssigned {
+                sequence_number,
+                applied,
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn queue_request(
+        &self,
+        queries: Vec<RqliteQuery>,
+        params: RqliteQueryParams,
+        opts: RequestOpts,
+    ) -> Result<u64, RequestError> {
+        let operation = Operation::of_queries(&queries);
         let batch = QueryArgs::from(queries);
         let body = serde_json::to_string(&batch).map_err(RequestError::FailedParseRequestBody)?;
+        let wait_timeout = params.timeout_duration();
 
-        self.try_request(RequestOptions {
+        let mut options = RequestOptions {
             endpoint: "db/execute".to_string(),
             body: Some(body),
-            params: Some(RqliteQueryParams::new().queue().into_request_query_params()),
+            operation,
+            params: Some(params.into_request_query_params()),
+            // Queued queries are routed like writes even if they only read, so they share a queue
+            write: true,
+            queued: true,
             ..Default::default()
-        })
-        .await?;
+        }
+        .with_opts(opts);
 
-        Ok(())
+        // The node only responds once the queue is flushed or the timeout is reached
+        if let Some(timeout) = wait_timeout {
+            options.extend_timeout(timeout.saturating_add(TIMEOUT_MARGIN));
+        }
+
+        let res = self.try_request(options).await?;
+
+        let body = res.text().await?;
+
+        serde_json::from_str::<QueuedResponseRaw>(&body)
+            .map(|response| response.sequence_number)
+            .map_err(RequestError::FailedParseResponseBody)
+    }
+
+    /// Downloads a backup of the database and streams it into `writer`.
+    /// The backup is written as it is received, without buffering it in memory.
+    /// Unless a SQL dump is requested, the header of the file is verified before anything is
+    /// written.
+    /// Returns the number of bytes written.
+    ///
+    /// For more information on backups, see the [rqlite documentation](https://rqlite.io/docs/guides/backup/).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the rqlite server failed
+    /// - The backup has an invalid header
+    /// - Writing the backup failed
+    pub async fn backup<W>(
+        &self,
+        writer: &mut W,
+        options: BackupOptions,
+    ) -> Result<u64, BackupError>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        // The default query parameters are meant for queries, not for the backup endpoint
+        let mut res = self
+            .try_request_without_defaults(RequestOptions {
+                endpoint: "db/backup".to_string(),
+                method: reqwest::Method::GET,
+                params: Some(options.query_params()),
+                timeout: Some(options.request_timeout()),
+                ..Default::default()
+            })
+            .await?;
+
+        let mut pending_header = options.expected_header();
+        let mut buffer = Vec::new();
+        let mut written = 0u64;
+
+        while let Some(chunk) = res.chunk().await.map_err(RequestError::from)? {
+            if let Some(header) = pending_header {
+                // Hold back the data until the header can be verified
+                buffer.extend_from_slice(&chunk);
+                if buffer.len() < header.len() {
+                    continue;
+                }
+
+                if !buffer.starts_with(header) {
+                    return Err(BackupError::InvalidHeader);
+                }
+
+                pending_header = None;
+                writer.write_all(&buffer).await?;
+                written += buffer.len() as u64;
+            } else {
+                writer.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+            }
+        }
+
+        if pending_header.is_some() {
+            return Err(BackupError::InvalidHeader);
+        }
+
+        writer.flush().await?;
+
+        Ok(written)
+    }
+
+    /// Downloads a backup of the database into the file at `path`, see [`RqliteClient::backup`].
+    /// The file is created or truncated, and removed again if the backup fails.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file could not be created
+    /// - The request to the rqlite server failed
+    /// - The backup has an invalid header
+    /// - Writing the backup failed
+    pub async fn backup_to_file(
+        &self,
+        path: impl AsRef<Path>,
+        options: BackupOptions,
+    ) -> Result<u64, BackupError> {
+        let path = path.as_ref();
+        let mut file = tokio::fs::File::create(path).await?;
+
+        let result = self.backup(&mut file, options).await;
+
+        if result.is_err() {
+            if let Err(e) = tokio::fs::remove_file(path).await {
+                tracing::warn!("Failed to remove incomplete backup {}: {e}", path.display());
+            }
+        }
+
+        result
+    }
+
+    /// Sends the file at `path` to `endpoint` as a stream and parses the results.
+    async fn load_file(
+        &self,
+        endpoint: &str,
+        path: &Path,
+        content_type: &'static str,
+        options: &LoadOptions,
+    ) -> Result<LoadResult, LoadError> {
+        // Fail early if the file can not be read, the body is only opened when it is sent
+        let size = tokio::fs::metadata(path).await?.len();
+
+        let res = self
+            .try_request(RequestOptions {
+                endpoint: endpoint.to_string(),
+                file_body: Some(FileBody::new(path, size, content_type, options)),
+                timeout: Some(options.request_timeout()),
+                write: true,
+                ..Default::default()
+            })
+            .await?;
+
+        let body = res.text().await.map_err(RequestError::from)?;
+
+        // Loading a SQLite file does not return results
+        if body.trim().is_empty() {
+            return Ok(LoadResult::new(size, vec![]));
+        }
+
+        let results = serde_json::from_str::<RqliteResponseRaw<QueryResult>>(&body)
+            .map_err(RequestError::FailedParseResponseBody)?
+            .results;
+
+        Ok(LoadResult::new(size, results))
+    }
+
+    /// Loads a `SQLite` database file into the cluster, replacing the current database.
+    /// The file is streamed to the node without reading it into memory.
+    ///
+    /// For more information on loading data, see the [rqlite documentation](https://rqlite.io/docs/guides/backup/#restoring-from-sqlite).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file could not be read
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    pub async fn load_sqlite_file(
+        &self,
+        path: impl AsRef<Path>,
+        options: LoadOptions,
+    ) -> Result<LoadResult, LoadError> {
+        self.load_file(
+            "db/load",
+            path.as_ref(),
+            "application/octet-stream",
+            &options,
+        )
+        .await
+    }
+
+    /// Loads a SQL dump, as created by `.dump` in the `SQLite` shell, into the cluster.
+    /// Returns the number of executed statements and the errors of the statements that failed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file could not be read
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    pub async fn load_sql_dump(
+        &self,
+        path: impl AsRef<Path>,
+        options: LoadOptions,
+    ) -> Result<LoadResult, LoadError> {
+        self.load_file("db/load", path.as_ref(), "text/plain", &options)
+            .await
+    }
+
+    /// Boots a single node directly from a `SQLite` database file.
+    /// This is the fastest way to load large databases, but only works for single node clusters.
+    ///
+    /// For more information on booting, see the [rqlite documentation](https://rqlite.io/docs/guides/backup/#booting-with-a-sqlite-database).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file could not be read
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    pub async fn boot(
+        &self,
+        path: impl AsRef<Path>,
+        options: LoadOptions,
+    ) -> Result<LoadResult, LoadError> {
+        self.load_file("boot", path.as_ref(), "application/octet-stream", &options)
+            .await
     }
 
     /// Checks if the rqlite cluster is ready.
@@ -504,6 +1610,41 @@ impl RqliteClient {
         Ok(response.nodes)
     }
 
+    /// Refreshes the known hosts from the nodes of the cluster.
+    /// Nodes that joined the cluster are added and nodes that were removed are dropped, while
+    /// the hosts passed to the builder are always kept as a last resort.
+    /// Returns the updated hosts.
+    ///
+    /// See [`RqliteClientBuilder::host_discovery`] to refresh the hosts periodically.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    pub async fn refresh_hosts(&self) -> Result<Vec<String>, RequestError> {
+        let nodes = self.nodes().await?;
+
+        discovery::apply_nodes(&self.hosts, &nodes, &self.seed_hosts)
+    }
+
+    /// Returns the health of the known hosts, in their current order.
+    /// Without [`RqliteClientBuilder::health_check`], all hosts are reported as healthy and
+    /// unchecked.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a lock was poisoned.
+    pub fn host_health(&self) -> Result<Vec<HostHealth>, RequestError> {
+        let hosts = self
+            .hosts
+            .read()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?
+            .clone();
+
+        self.health.snapshot(&hosts)
+    }
+
     /// Retrieves current the leader of the rqlite cluster.
     /// Returns a [`Node`] if a leader is found, otherwise `None`.
     ///
@@ -518,6 +1659,95 @@ impl RqliteClient {
         Ok(nodes.into_iter().find(|n| n.leader))
     }
 
+    /// Sends a GET request to exactly one host, without falling back to other hosts.
+    async fn get_from(&self, host: &str, endpoint: &str) -> Result<String, RequestError> {
+        let res = RequestOptions {
+            endpoint: endpoint.to_string(),
+            method: reqwest::Method::GET,
+            ..Default::default()
+        }
+        .to_reqwest_request(&self.client, host, &self.config.scheme)
+        .send()
+        .await?;
+
+        match res.status() {
+            status if status.is_success() => Ok(res.text().await?),
+            reqwest::StatusCode::UNAUTHORIZED => Err(RequestError::Unauthorized),
+            status => Err(RequestError::ReqwestError {
+                body: res.text().await?,
+                status,
+            }),
+        }
+    }
+
+    /// Sends a GET request and deserializes the response.
+    async fn get_json<T>(&self, endpoint: &str) -> Result<T, RequestError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let res = self
+            .try_request(RequestOptions {
+                endpoint: endpoint.to_string(),
+                method: reqwest::Method::GET,
+                ..Default::default()
+            })
+            .await?;
+
+        let body = res.text().await?;
+
+        serde_json::from_str::<T>(&body).map_err(RequestError::FailedParseResponseBody)
+    }
+
+    /// Retrieves the status of the node the request is sent to, including its Raft state,
+    /// `SQLite` database and build information.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    pub async fn status(&self) -> Result<Status, RequestError> {
+        self.get_json("status").await
+    }
+
+    /// Retrieves the runtime statistics of the node the request is sent to, including HTTP
+    /// and memory statistics.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the rqlite server failed
+    /// - The response could not be parsed
+    pub async fn expvar(&self) -> Result<Expvar, RequestError> {
+        self.get_json("debug/vars").await
+    }
+
+    /// Compares the applied index of every node with the commit index of the leader.
+    /// The status of all nodes is retrieved concurrently. Nodes that could not be reached are
+    /// reported with an error instead of failing the whole request.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The nodes of the cluster could not be retrieved
+    pub async fn replication_lag(&self) -> Result<ReplicationLag, RequestError> {
+        let nodes = self.nodes().await?;
+
+        let statuses = futures_util::future::join_all(nodes.iter().map(|node| async {
+            let body = self.get_from(api_host(&node.api_addr), "status").await?;
+            serde_json::from_str::<Status>(&body).map_err(RequestError::FailedParseResponseBody)
+        }))
+        .await;
+
+        Ok(ReplicationLag::new(
+            nodes
+                .into_iter()
+                .zip(statuses)
+                .map(|(node, status)| (node, status.map_err(|e| e.to_string())))
+                .collect(),
+        ))
+    }
+
     /// Removes a node from the rqlite cluster.
     ///
     /// # Errors
@@ -669,6 +1899,176 @@ mod tests {
         assert_eq!(client.config.fallback_count.count(4), 3);
     }
 
+    #[test]
+    fn unit_rqlite_client_builder_leader_routing() {
+        let client = RqliteClientBuilder::new()
+            .known_host("localhost:4001")
+            .leader_routing(true)
+            .build()
+            .unwrap();
+
+        assert!(client.config.leader_routing);
+        assert!(client.cached_leader().unwrap().is_none());
+    }
+
+    #[test]
+    fn unit_switch_host_skips_quarantined_hosts() {
+        let client = RqliteClientBuilder::new()
+            .known_host("localhost:4001")
+            .known_host("localhost:4002")
+            .known_host("localhost:4003")
+            .fallback_persistence(false)
+            .build()
+            .unwrap();
+        let check = HealthCheck::new(Duration::from_secs(1));
+
+        client
+            .health
+            .record("localhost:4002", Err("down".to_string()), &check)
+            .unwrap();
+
+        let mut host = "localhost:4001".to_string();
+        client
+            .switch_host(
+                &mut host,
+                RetryErrorKind::Connect,
+                1,
+                Duration::ZERO,
+                &RequestOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(host, "localhost:4003");
+
+        // All other hosts are quarantined, so the strategy's choice is kept
+        client
+            .health
+            .record("localhost:4003", Err("down".to_string()), &check)
+            .unwrap();
+        client
+            .health
+            .record("localhost:4001", Err("down".to_string()), &check)
+            .unwrap();
+
+        let mut host = "localhost:4001".to_string();
+        client
+            .switch_host(
+                &mut host,
+                RetryErrorKind::Connect,
+                1,
+                Duration::ZERO,
+                &RequestOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(host, "localhost:4002");
+
+        let health = client.host_health().unwrap();
+        assert_eq!(health.len(), 3);
+        assert!(health
+            .iter()
+            .all(|host| host.status == crate::health::HealthStatus::Unhealthy));
+    }
+
+    /// The current host, the error and the failures of the current host in the history.
+    type FallbackCall = (String, Option<RetryErrorKind>, u64);
+
+    /// Falls back like `RoundRobin` and records the contexts it was called with.
+    struct RecordingStrategy(Arc<RwLock<Vec<FallbackCall>>>);
+
+    impl FallbackStrategy for RecordingStrategy {
+        fn fallback<'a>(
+            &mut self,
+            hosts: &'a mut Vec<String>,
+            current_host: &str,
+            persist: bool,
+        ) -> Option<&'a String> {
+            crate::fallback::RoundRobin.fallback(hosts, current_host, persist)
+        }
+
+        fn fallback_with_context<'a>(
+            &mut self,
+            hosts: &'a mut Vec<String>,
+            ctx: &FallbackContext<'_>,
+        ) -> Option<&'a String> {
+            let failures = ctx
+                .history
+                .get(ctx.current_host)
+                .map_or(0, |stats| stats.failures);
+            self.0
+                .write()
+                .unwrap()
+                .push((ctx.current_host.to_string(), ctx.error, failures));
+            self.fallback(hosts, ctx.current_host, ctx.persist)
+        }
+    }
+
+    #[test]
+    fn unit_switch_host_passes_context() {
+        let calls = Arc::new(RwLock::new(Vec::new()));
+        let client = RqliteClientBuilder::new()
+            .known_host("localhost:4001")
+            .known_host("localhost:4002")
+            .known_host("localhost:4003")
+            .fallback_persistence(false)
+            .fallback_strategy(RecordingStrategy(calls.clone()))
+            .build()
+            .unwrap();
+
+        client
+            .health
+            .record(
+                "localhost:4002",
+                Err("down".to_string()),
+                &HealthCheck::new(Duration::from_secs(1)),
+            )
+            .unwrap();
+        client
+            .history
+            .write()
+            .unwrap()
+            .record_failure("localhost:4001", RetryErrorKind::Timeout);
+
+        let mut host = "localhost:4001".to_string();
+        client
+            .switch_host(
+                &mut host,
+                RetryErrorKind::Timeout,
+                1,
+                Duration::ZERO,
+                &RequestOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(host, "localhost:4003");
+
+        // The quarantined host was skipped without being tried
+        assert_eq!(
+            *calls.read().unwrap(),
+            vec![
+                (
+                    "localhost:4001".to_string(),
+                    Some(RetryErrorKind::Timeout),
+                    1
+                ),
+                ("localhost:4002".to_string(), None, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn unit_api_host() {
+        assert_eq!(api_host("http://localhost:4001"), "localhost:4001");
+        assert_eq!(api_host("https://node-1:4001/"), "node-1:4001");
+        assert_eq!(api_host("localhost:4001"), "localhost:4001");
+    }
+
+    #[test]
+    fn unit_url_host() {
+        let url = reqwest::Url::parse("http://localhost:4003/db/execute").unwrap();
+        assert_eq!(url_host(&url), Some("localhost:4003".to_string()));
+
+        let url = reqwest::Url::parse("https://example.com/db/execute").unwrap();
+        assert_eq!(url_host(&url), Some("example.com".to_string()));
+    }
+
     #[test]
     fn unit_rqllite_client_builder_fallback_persistence() {
         let client = RqliteClientBuilder::new()
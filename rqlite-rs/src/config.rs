@@ -3,6 +3,7 @@ use std::sync::RwLock;
 use crate::{
     fallback::{FallbackCount, FallbackStrategy},
     request::{RequestQueryParams, RqliteQueryParam, RqliteQueryParams},
+    retry::RetryPolicy,
 };
 
 #[derive(Default)]
@@ -12,6 +13,8 @@ pub(crate) struct RqliteClientConfigBuilder {
     pub(crate) fallback_strategy: Option<Box<dyn FallbackStrategy>>,
     pub(crate) fallback_count: Option<FallbackCount>,
     pub(crate) fallback_persistence: bool,
+    pub(crate) leader_routing: bool,
+    pub(crate) retry_policy: Option<Box<dyn RetryPolicy>>,
 }
 
 impl RqliteClientConfigBuilder {
@@ -40,6 +43,16 @@ impl RqliteClientConfigBuilder {
         self
     }
 
+    pub(crate) const fn leader_routing(mut self, enabled: bool) -> Self {
+        self.leader_routing = enabled;
+        self
+    }
+
+    pub(crate) fn retry_policy(mut self, policy: impl RetryPolicy) -> Self {
+        self.retry_policy = Some(Box::new(policy));
+        self
+    }
+
     pub(crate) fn build(self) -> RqliteClientConfig {
         // When fast-blob is disabled, we need to retrieve blob_arrays if possible because we dont decode base64
         #[cfg(not(feature = "fast-blob"))]
@@ -66,6 +79,8 @@ impl RqliteClientConfigBuilder {
             fallback_strategy: RwLock::new(self.fallback_strategy.unwrap_or_default()),
             fallback_count: self.fallback_count.unwrap_or_default(),
             fallback_persistence: self.fallback_persistence,
+            leader_routing: self.leader_routing,
+            retry_policy: self.retry_policy,
         }
     }
 }
@@ -77,9 +92,11 @@ pub(crate) struct RqliteClientConfig {
     pub(crate) fallback_strategy: RwLock<Box<dyn FallbackStrategy>>,
     pub(crate) fallback_count: FallbackCount,
     pub(crate) fallback_persistence: bool,
+    pub(crate) leader_routing: bool,
+    pub(crate) retry_policy: Option<Box<dyn RetryPolicy>>,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub enum Scheme {
     #[default]
     Http,
@@ -157,6 +174,28 @@ mod tests {
         assert!(matches!(config.fallback_count, FallbackCount::Infinite));
     }
 
+    #[test]
+    fn unit_config_leader_routing() {
+        let config = RqliteClientConfigBuilder::default().build();
+        assert!(!config.leader_routing);
+
+        let config = RqliteClientConfigBuilder::default()
+            .leader_routing(true)
+            .build();
+        assert!(config.leader_routing);
+    }
+
+    #[test]
+    fn unit_config_retry_policy() {
+        let config = RqliteClientConfigBuilder::default().build();
+        assert!(config.retry_policy.is_none());
+
+        let config = RqliteClientConfigBuilder::default()
+            .retry_policy(crate::retry::ExponentialBackoff::new())
+            .build();
+        assert!(config.retry_policy.is_some());
+    }
+
     #[test]
     fn unit_config_fallback_persistence() {
         let config = RqliteClientConfigBuilder::default()
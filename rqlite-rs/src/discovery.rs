@@ -0,0 +1,240 @@
+//! Discovery of the nodes that are part of the cluster.
+//!
+//! See [`RqliteClientBuilder::host_discovery`](crate::RqliteClientBuilder::host_discovery) and
+//! [`RqliteClient::refresh_hosts`](crate::RqliteClient::refresh_hosts).
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{RwLock, Weak},
+    time::Duration,
+};
+
+use crate::{
+    client::api_host,
+    config::Scheme,
+    error::RequestError,
+    node::{Node, NodeResponse},
+    request::{RequestOptions, RqliteQueryParams},
+};
+
+/// A task running in the background, handed to the spawn hook of the client builder.
+/// It can be spawned on any async runtime, e.g. with `tokio::spawn`.
+pub type BackgroundTask = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Spawns a [`BackgroundTask`] on the runtime of the caller.
+pub(crate) type Spawn = Box<dyn FnOnce(BackgroundTask) + Send>;
+
+/// The discovery configured on the builder.
+pub(crate) struct DiscoveryConfig {
+    pub(crate) interval: Duration,
+    pub(crate) spawn: Spawn,
+}
+
+/// Merges the nodes of the cluster into the known hosts.
+///
+/// Hosts that are still part of the cluster keep their order, so the current host stays first.
+/// New nodes are appended, nodes that left the cluster are dropped and seed hosts that are not
+/// part of the cluster are kept at the end, as a last resort.
+pub(crate) fn merge_hosts(current: &[String], nodes: &[Node], seeds: &[String]) -> Vec<String> {
+    let cluster = nodes
+        .iter()
+        .map(|node| api_host(&node.api_addr))
+        .filter(|host| !host.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut hosts = current
+        .iter()
+        .filter(|host| cluster.contains(&host.as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for host in cluster.into_iter().chain(seeds.iter().map(String::as_str)) {
+        if !hosts.iter().any(|known| known == host) {
+            hosts.push(host.to_string());
+        }
+    }
+
+    hosts
+}
+
+/// Replaces the known hosts with the merged list and returns it.
+pub(crate) fn apply_nodes(
+    hosts: &RwLock<Vec<String>>,
+    nodes: &[Node],
+    seeds: &[String],
+) -> Result<Vec<String>, RequestError> {
+    let mut hosts = hosts
+        .write()
+        .map_err(|_poisoned| RequestError::LockPoisoned)?;
+    let merged = merge_hosts(&hosts, nodes, seeds);
+
+    if *hosts == merged {
+        return Ok(merged);
+    }
+
+    hosts.clone_from(&merged);
+    drop(hosts);
+
+    tracing::info!("Discovered hosts: {merged:?}");
+    Ok(merged)
+}
+
+/// Refreshes the hosts periodically until the client is dropped.
+pub(crate) struct Discovery {
+    pub(crate) client: reqwest::Client,
+    pub(crate) scheme: Scheme,
+    pub(crate) hosts: Weak<RwLock<Vec<String>>>,
+    pub(crate) seeds: Vec<String>,
+    pub(crate) interval: Duration,
+}
+
+impl Discovery {
+    pub(crate) fn into_task(self) -> BackgroundTask {
+        Box::pin(async move {
+            loop {
+                futures_timer::Delay::new(self.interval).await;
+
+                // The client has been dropped
+                let Some(hosts) = self.hosts.upgrade() else {
+                    break;
+                };
+
+                let result = match self.fetch_nodes(&hosts).await {
+                    Ok(nodes) => apply_nodes(&hosts, &nodes, &self.seeds).map(|_hosts| ()),
+                    Err(e) => Err(e),
+                };
+
+                if let Err(e) = result {
+                    tracing::warn!("Failed to discover hosts: {e}");
+                }
+            }
+        })
+    }
+
+    /// Asks the known hosts for the nodes of the cluster, until one of them answers.
+    async fn fetch_nodes(&self, hosts: &RwLock<Vec<String>>) -> Result<Vec<Node>, RequestError> {
+        let hosts = hosts
+            .read()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?
+            .clone();
+
+        let options = RequestOptions {
+            endpoint: "nodes".to_string(),
+            params: Some(
+                RqliteQueryParams::new()
+                    .ver("2".to_string())
+                    .into_request_query_params(),
+            ),
+            method: reqwest::Method::GET,
+            ..Default::default()
+        };
+
+        for host in &hosts {
+            let res = options
+                .to_reqwest_request(&self.client, host, &self.scheme)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+
+            match res {
+                Ok(res) => {
+                    let body = res.text().await?;
+
+                    return serde_json::from_str::<NodeResponse>(&body)
+                        .map(|response| response.nodes)
+                        .map_err(RequestError::FailedParseResponseBody);
+                }
+                Err(e) => tracing::debug!("Failed to fetch nodes from {host}: {e}"),
+            }
+        }
+
+        Err(RequestError::NoAvailableHosts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(api_addr: &str) -> Node {
+        Node {
+            id: api_addr.to_string(),
+            api_addr: api_addr.to_string(),
+            raft_addr: String::new(),
+            voter: true,
+            reachable: true,
+            leader: false,
+            time: 0.0,
+            error: None,
+        }
+    }
+
+    fn hosts(hosts: &[&str]) -> Vec<String> {
+        hosts.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn unit_merge_hosts() {
+        let nodes = vec![
+            node("http://node-1:4001"),
+            node("http://node-3:4001"),
+            node("http://node-4:4001"),
+        ];
+
+        let merged = merge_hosts(
+            &hosts(&["node-3:4001", "node-2:4001", "node-1:4001", "seed:4001"]),
+            &nodes,
+            &hosts(&["seed:4001", "node-1:4001"]),
+        );
+
+        // node-2 left the cluster, node-4 joined and the seed is kept last
+        assert_eq!(
+            merged,
+            hosts(&["node-3:4001", "node-1:4001", "node-4:4001", "seed:4001"])
+        );
+    }
+
+    #[test]
+    fn unit_merge_hosts_without_nodes() {
+        let merged = merge_hosts(&hosts(&["node-1:4001"]), &[], &hosts(&["seed:4001"]));
+
+        assert_eq!(merged, hosts(&["seed:4001"]));
+    }
+
+    #[test]
+    fn unit_apply_nodes() {
+        let known = RwLock::new(hosts(&["node-1:4001"]));
+
+        let merged = apply_nodes(
+            &known,
+            &[node("http://node-1:4001"), node("http://node-2:4001")],
+            &hosts(&["node-1:4001"]),
+        )
+        .unwrap();
+
+        assert_eq!(merged, hosts(&["node-1:4001", "node-2:4001"]));
+        assert_eq!(*known.read().unwrap(), merged);
+    }
+
+    #[tokio::test]
+    async fn unit_discovery_stops_when_client_dropped() {
+        let hosts = std::sync::Arc::new(RwLock::new(hosts(&["localhost:1"])));
+
+        let task = Discovery {
+            client: reqwest::Client::new(),
+            scheme: Scheme::Http,
+            hosts: std::sync::Arc::downgrade(&hosts),
+            seeds: vec![],
+            interval: Duration::from_millis(1),
+        }
+        .into_task();
+
+        drop(hosts);
+
+        // Finishes on the first tick instead of looping forever
+        tokio::time::timeout(Duration::from_secs(5), task)
+            .await
+            .unwrap();
+    }
+}
@@ -13,6 +13,21 @@ pub enum QueryBuilderError {
     /// An invalid query was provided.
     #[error("Invalid Query: {0}")]
     InvalidQuery(String),
+    /// A named placeholder in the query has no matching argument.
+    #[error("Missing named argument: {0}")]
+    MissingNamedArgument(String),
+    /// A named argument has no matching placeholder in the query.
+    #[error("Unknown named argument: {0}")]
+    UnknownNamedArgument(String),
+    /// A named argument was provided more than once.
+    #[error("Duplicate named argument: {0}")]
+    DuplicateNamedArgument(String),
+    /// Named arguments were provided for a query with positional placeholders.
+    #[error("Named arguments can not be bound to positional placeholders")]
+    MixedArguments,
+    /// A column used to build a query does not exist.
+    #[error("Unknown column: {0}")]
+    UnknownColumn(String),
 }
 
 #[derive(Error, Debug)]
@@ -39,6 +54,9 @@ pub enum RequestError {
     /// No available hosts to send the request to.
     #[error("No available hosts")]
     NoAvailableHosts,
+    /// The request could not be sent or timed out, and the retry policy gave up.
+    #[error("Request failed: {0}")]
+    RequestFailed(#[source] reqwest::Error),
     /// An unhandled error occured while switching to a different host.
     #[error("Switchover - Wrong Error: {0}")]
     SwitchoverWrongError(String),
@@ -66,6 +84,74 @@ pub enum RequestError {
     /// A lock was poisoned.
     #[error("Lock Poisoned")]
     LockPoisoned,
+    /// The number of results in the response does not match the number of statements.
+    #[error("Expected {expected} results, got {actual}")]
+    ResultCountMismatch { expected: usize, actual: usize },
+    /// The node has not assigned the sequence number that was waited for yet.
+    #[error(
+        "Sequence number {sequence_number} was not assigned by the node, which is at {applied}"
+    )]
+    SequenceNumberNotAssigned { sequence_number: u64, applied: u64 },
+}
+
+#[derive(Error, Debug)]
+pub enum MigrateError {
+    /// An error occurred while sending a request to the rqlite cluster.
+    #[error("Request Error: {0}")]
+    RequestError(#[from] RequestError),
+    /// A migration statement could not be turned into a query.
+    #[error("Invalid Query: {0}")]
+    InvalidQuery(#[from] QueryBuilderError),
+    /// The applied migrations could not be decoded.
+    #[error("Failed to decode applied migrations: {0}")]
+    DecodeError(#[from] rqlite_rs_core::IntoTypedError),
+    /// A migration file could not be read.
+    #[error("Failed to read migrations: {0}")]
+    IoError(#[from] std::io::Error),
+    /// A migration file does not follow the `<version>_<description>.sql` naming scheme.
+    #[error("Invalid migration file name: {0}")]
+    InvalidFileName(String),
+    /// Two migrations share the same version.
+    #[error("Duplicate migration version: {0}")]
+    DuplicateVersion(i64),
+    /// A down migration was found without a matching up migration.
+    #[error("Down migration {0} has no matching up migration")]
+    MissingUpMigration(i64),
+    /// A migration was applied to the database but is not known to the migrator.
+    #[error("Migration {0} was applied but is missing")]
+    VersionMissing(i64),
+    /// A migration was modified after it has been applied.
+    #[error("Migration {0} was modified after it has been applied")]
+    ChecksumMismatch(i64),
+    /// A migration has to be reverted but has no down migration.
+    #[error("Migration {0} has no down migration")]
+    NoDownMigration(i64),
+    /// The database returned an error while executing a migration.
+    #[error("Migration {version} failed: {message}")]
+    MigrationFailed { version: i64, message: String },
+}
+
+#[derive(Error, Debug)]
+pub enum BackupError {
+    /// An error occurred while requesting the backup.
+    #[error("Request Error: {0}")]
+    RequestError(#[from] RequestError),
+    /// The backup could not be written.
+    #[error("Failed to write backup: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The downloaded file does not start with the expected header.
+    #[error("Backup has an invalid header")]
+    InvalidHeader,
+}
+
+#[derive(Error, Debug)]
+pub enum LoadError {
+    /// An error occurred while sending the data.
+    #[error("Request Error: {0}")]
+    RequestError(#[from] RequestError),
+    /// The file could not be read.
+    #[error("Failed to read file: {0}")]
+    IoError(#[from] std::io::Error),
 }
 
 // This is a conversion from the `Infallible` type to the `RequestError` type.
@@ -0,0 +1,137 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::{query::Operation, retry::RetryErrorKind};
+
+/// Information about a failed request passed to
+/// [`FallbackStrategy::fallback_with_context`](super::FallbackStrategy::fallback_with_context).
+#[derive(Debug, Clone, Copy)]
+pub struct FallbackContext<'a> {
+    /// The host the request was sent to.
+    pub current_host: &'a str,
+    /// If the client keeps using the host that is returned, see
+    /// [`RqliteClientBuilder::fallback_persistence`](crate::RqliteClientBuilder::fallback_persistence).
+    pub persist: bool,
+    /// The class of error the request failed with.
+    /// `None` if the current host was skipped without sending the request to it, for example
+    /// because it failed its health checks.
+    pub error: Option<RetryErrorKind>,
+    /// The number of attempts made so far, starting at `1`.
+    pub attempt: u32,
+    /// The time since the first attempt was started.
+    pub elapsed: Duration,
+    /// The operation of the query, if the request executes queries.
+    pub operation: Option<Operation>,
+    /// If the request modifies the database.
+    pub is_write: bool,
+    /// The results of previous requests, per host.
+    pub history: &'a HostHistory,
+}
+
+/// Information about a successful request passed to
+/// [`FallbackStrategy::on_success_with_context`](super::FallbackStrategy::on_success_with_context).
+#[derive(Debug, Clone, Copy)]
+pub struct SuccessContext<'a> {
+    /// The host that answered the request.
+    pub host: &'a str,
+    /// The time the successful attempt took.
+    pub latency: Duration,
+    /// The number of attempts it took, starting at `1`.
+    pub attempt: u32,
+    /// The time since the first attempt was started.
+    pub elapsed: Duration,
+    /// The operation of the query, if the request executes queries.
+    pub operation: Option<Operation>,
+    /// If the request modifies the database.
+    pub is_write: bool,
+    /// The results of previous requests, per host, including this one.
+    pub history: &'a HostHistory,
+}
+
+/// The results of the requests sent to a single host.
+#[derive(Debug, Clone, Default)]
+pub struct HostStats {
+    /// The number of successful requests.
+    pub successes: u64,
+    /// The number of failed requests.
+    pub failures: u64,
+    /// The number of failed requests since the last successful one.
+    pub consecutive_failures: u32,
+    /// The error of the last failed request.
+    pub last_error: Option<RetryErrorKind>,
+    /// When the last request succeeded.
+    pub last_success: Option<Instant>,
+    /// When the last request failed.
+    pub last_failure: Option<Instant>,
+    /// The time the last successful request took.
+    pub last_latency: Option<Duration>,
+}
+
+/// The results of previous requests, per host, kept by the client.
+#[derive(Debug, Default)]
+pub struct HostHistory {
+    hosts: HashMap<String, HostStats>,
+}
+
+impl HostHistory {
+    /// Returns the results of the requests sent to `host`, if any were sent.
+    #[must_use]
+    pub fn get(&self, host: &str) -> Option<&HostStats> {
+        self.hosts.get(host)
+    }
+
+    /// Returns the results of all hosts requests were sent to.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &HostStats)> {
+        self.hosts
+            .iter()
+            .map(|(host, stats)| (host.as_str(), stats))
+    }
+
+    pub(crate) fn record_success(&mut self, host: &str, latency: Duration) {
+        let stats = self.hosts.entry(host.to_string()).or_default();
+        stats.successes = stats.successes.saturating_add(1);
+        stats.consecutive_failures = 0;
+        stats.last_success = Some(Instant::now());
+        stats.last_latency = Some(latency);
+    }
+
+    pub(crate) fn record_failure(&mut self, host: &str, error: RetryErrorKind) {
+        let stats = self.hosts.entry(host.to_string()).or_default();
+        stats.failures = stats.failures.saturating_add(1);
+        stats.consecutive_failures = stats.consecutive_failures.saturating_add(1);
+        stats.last_error = Some(error);
+        stats.last_failure = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_host_history() {
+        let mut history = HostHistory::default();
+
+        history.record_failure("localhost:4001", RetryErrorKind::Connect);
+        history.record_failure("localhost:4001", RetryErrorKind::Timeout);
+
+        let stats = history.get("localhost:4001").unwrap();
+        assert_eq!(stats.failures, 2);
+        assert_eq!(stats.consecutive_failures, 2);
+        assert_eq!(stats.last_error, Some(RetryErrorKind::Timeout));
+        assert!(stats.last_success.is_none());
+
+        history.record_success("localhost:4001", Duration::from_millis(5));
+
+        let stats = history.get("localhost:4001").unwrap();
+        assert_eq!(stats.successes, 1);
+        assert_eq!(stats.failures, 2);
+        assert_eq!(stats.consecutive_failures, 0);
+        assert_eq!(stats.last_latency, Some(Duration::from_millis(5)));
+
+        assert!(history.get("localhost:4002").is_none());
+        assert_eq!(history.iter().count(), 1);
+    }
+}
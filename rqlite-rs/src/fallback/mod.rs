@@ -1,4 +1,6 @@
+mod context;
 mod strategy;
+pub use context::{FallbackContext, HostHistory, HostStats, SuccessContext};
 pub use strategy::*;
 
 /// `FallbackCount` is the number of hosts to try to fallback to if the current host fails.
@@ -0,0 +1,405 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use super::FallbackStrategy;
+use crate::fallback::{FallbackContext, SuccessContext};
+
+/// The state of the circuit of a single host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitState {
+    /// The host is used as usual.
+    Closed,
+    /// The host failed too often and is skipped until the cool-down has passed.
+    Open,
+    /// The cool-down has passed and the host is tried again.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Circuit {
+    state: CircuitState,
+    failures: u32,
+    successes: u32,
+    opened_at: Instant,
+}
+
+impl Default for Circuit {
+    fn default() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            failures: 0,
+            successes: 0,
+            opened_at: Instant::now(),
+        }
+    }
+}
+
+/// A circuit breaker wrapping another [`FallbackStrategy`].
+///
+/// Every host has a circuit, which opens after `failure_threshold` consecutive failures.
+/// Hosts with an open circuit are skipped when selecting the host of a new request and when
+/// falling back, until the cool-down has passed.
+/// Afterwards the circuit is half-open and the host is tried again: it is closed after
+/// `success_threshold` successful requests and opened again on the next failure.
+/// If the circuits of all hosts the inner strategy returns are open, no host is returned, so the
+/// request fails fast.
+///
+/// State transitions are emitted as `tracing` events.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use rqlite_rs::{fallback::{CircuitBreaker, RoundRobin}, RqliteClientBuilder};
+///
+/// let client = RqliteClientBuilder::new()
+///     .known_host("localhost:4001")
+///     .known_host("localhost:4003")
+///     .fallback_strategy(
+///         CircuitBreaker::new(RoundRobin)
+///             .failure_threshold(3)
+///             .cool_down(Duration::from_secs(10)),
+///     )
+///     .build();
+///
+/// assert!(client.is_ok());
+/// ```
+pub struct CircuitBreaker<S> {
+    inner: S,
+    failure_threshold: u32,
+    success_threshold: u32,
+    cool_down: Duration,
+    circuits: HashMap<String, Circuit>,
+}
+
+impl<S: FallbackStrategy> CircuitBreaker<S> {
+    /// Creates a new circuit breaker around `inner`, which opens after 5 consecutive failures
+    /// and tries the host again after a cool-down of 30s.
+    #[must_use]
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            failure_threshold: 5,
+            success_threshold: 1,
+            cool_down: Duration::from_secs(30),
+            circuits: HashMap::new(),
+        }
+    }
+
+    /// Sets the number of consecutive failures after which the circuit of a host opens.
+    /// Values below `1` are treated as `1`.
+    #[must_use]
+    pub const fn failure_threshold(mut self, threshold: u32) -> Self {
+        self.failure_threshold = threshold;
+        self
+    }
+
+    /// Sets the number of successful requests after which a half-open circuit is closed.
+    /// Values below `1` are treated as `1`.
+    #[must_use]
+    pub const fn success_threshold(mut self, threshold: u32) -> Self {
+        self.success_threshold = threshold;
+        self
+    }
+
+    /// Sets how long the circuit of a host stays open before the host is tried again.
+    #[must_use]
+    pub const fn cool_down(mut self, cool_down: Duration) -> Self {
+        self.cool_down = cool_down;
+        self
+    }
+
+    fn record_failure(&mut self, host: &str) {
+        let threshold = self.failure_threshold.max(1);
+        let circuit = self.circuits.entry(host.to_string()).or_default();
+        circuit.failures = circuit.failures.saturating_add(1);
+        circuit.successes = 0;
+
+        match circuit.state {
+            CircuitState::Closed if circuit.failures >= threshold => {
+                tracing::warn!(
+                    "Circuit for {host} opened after {} consecutive failures",
+                    circuit.failures
+                );
+                circuit.state = CircuitState::Open;
+                circuit.opened_at = Instant::now();
+            }
+            CircuitState::HalfOpen => {
+                tracing::warn!("Circuit for {host} opened again, the trial request failed");
+                circuit.state = CircuitState::Open;
+                circuit.opened_at = Instant::now();
+            }
+            CircuitState::Closed | CircuitState::Open => {}
+        }
+    }
+
+    fn record_success(&mut self, host: &str) {
+        let Some(circuit) = self.circuits.get_mut(host) else {
+            return;
+        };
+
+        circuit.failures = 0;
+        circuit.successes = circuit.successes.saturating_add(1);
+
+        if circuit.state == CircuitState::HalfOpen
+            && circuit.successes >= self.success_threshold.max(1)
+        {
+            tracing::info!("Circuit for {host} closed");
+            circuit.state = CircuitState::Closed;
+        }
+    }
+
+    /// Asks the inner strategy until it returns a host whose circuit is not open.
+    /// `fallback` is called with the hosts, the host to fall back from and whether it is the
+    /// first call.
+    fn next_allowed<'a>(
+        &mut self,
+        hosts: &'a mut Vec<String>,
+        current_host: &str,
+        mut fallback: impl FnMut(&mut S, &mut Vec<String>, &str, bool) -> Option<String>,
+    ) -> Option<&'a String> {
+        let mut candidate = current_host.to_string();
+
+        for attempt in 0..hosts.len() {
+            let next = fallback(&mut self.inner, hosts, &candidate, attempt == 0)?;
+
+            if self.allows(&next) {
+                return hosts.iter().find(|host| **host == next);
+            }
+
+            candidate = next;
+        }
+
+        None
+    }
+
+    /// Returns `true` if requests may be sent to the host, moving open circuits whose cool-down
+    /// has passed to half-open.
+    fn allows(&mut self, host: &str) -> bool {
+        let Some(circuit) = self.circuits.get_mut(host) else {
+            return true;
+        };
+
+        match circuit.state {
+            CircuitState::Closed | CircuitState::HalfOpen => true,
+            CircuitState::Open if circuit.opened_at.elapsed() >= self.cool_down => {
+                tracing::info!("Circuit for {host} half-open, trying it again");
+                circuit.state = CircuitState::HalfOpen;
+                true
+            }
+            CircuitState::Open => false,
+        }
+    }
+
+    #[cfg(test)]
+    fn state(&self, host: &str) -> CircuitState {
+        self.circuits
+            .get(host)
+            .map_or(CircuitState::Closed, |circuit| circuit.state)
+    }
+}
+
+impl<S: FallbackStrategy> FallbackStrategy for CircuitBreaker<S> {
+    fn fallback<'a>(
+        &mut self,
+        hosts: &'a mut Vec<String>,
+        current_host: &str,
+        persist: bool,
+    ) -> Option<&'a String> {
+        self.record_failure(current_host);
+
+        self.next_allowed(hosts, current_host, |inner, hosts, candidate, _first| {
+            inner.fallback(hosts, candidate, persist).cloned()
+        })
+    }
+
+    fn fallback_with_context<'a>(
+        &mut self,
+        hosts: &'a mut Vec<String>,
+        ctx: &FallbackContext<'_>,
+    ) -> Option<&'a String> {
+        // Hosts that were skipped without being tried did not fail
+        if ctx.error.is_some() {
+            self.record_failure(ctx.current_host);
+        }
+
+        self.next_allowed(hosts, ctx.current_host, |inner, hosts, candidate, first| {
+            let ctx = FallbackContext {
+                current_host: candidate,
+                error: ctx.error.filter(|_error| first),
+                ..*ctx
+            };
+            inner.fallback_with_context(hosts, &ctx).cloned()
+        })
+    }
+
+    fn select<'a>(&mut self, hosts: &'a [String]) -> Option<&'a String> {
+        let selected = self.inner.select(hosts).or_else(|| hosts.first())?;
+
+        if self.allows(selected) {
+            Some(selected)
+        } else {
+            hosts.iter().find(|host| self.allows(host))
+        }
+    }
+
+    fn on_success(&mut self, host: &str, latency: Duration) {
+        self.inner.on_success(host, latency);
+        self.record_success(host);
+    }
+
+    fn on_success_with_context(&mut self, ctx: &SuccessContext<'_>) {
+        self.inner.on_success_with_context(ctx);
+        self.record_success(ctx.host);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fallback::RoundRobin;
+
+    fn hosts() -> Vec<String> {
+        vec![
+            "localhost:4001".to_string(),
+            "localhost:4002".to_string(),
+            "localhost:4003".to_string(),
+        ]
+    }
+
+    #[test]
+    fn unit_circuit_breaker_opens_after_threshold() {
+        let mut hosts = hosts();
+        let mut strategy = CircuitBreaker::new(RoundRobin)
+            .failure_threshold(2)
+            .cool_down(Duration::from_secs(60));
+
+        assert_eq!(
+            strategy.fallback(&mut hosts, "localhost:4002", false),
+            Some(&"localhost:4003".to_string())
+        );
+        assert_eq!(strategy.state("localhost:4002"), CircuitState::Closed);
+
+        assert_eq!(
+            strategy.fallback(&mut hosts, "localhost:4002", false),
+            Some(&"localhost:4003".to_string())
+        );
+        assert_eq!(strategy.state("localhost:4002"), CircuitState::Open);
+
+        // The open host is skipped
+        assert_eq!(
+            strategy.fallback(&mut hosts, "localhost:4001", false),
+            Some(&"localhost:4003".to_string())
+        );
+    }
+
+    #[test]
+    fn unit_circuit_breaker_resets_failures_on_success() {
+        let mut hosts = hosts();
+        let mut strategy = CircuitBreaker::new(RoundRobin).failure_threshold(2);
+
+        strategy.fallback(&mut hosts, "localhost:4001", false);
+        strategy.on_success("localhost:4001", Duration::ZERO);
+        strategy.fallback(&mut hosts, "localhost:4001", false);
+
+        assert_eq!(strategy.state("localhost:4001"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn unit_circuit_breaker_half_open() {
+        let mut hosts = hosts();
+        let mut strategy = CircuitBreaker::new(RoundRobin)
+            .failure_threshold(1)
+            .success_threshold(2)
+            .cool_down(Duration::ZERO);
+
+        strategy.fallback(&mut hosts, "localhost:4001", false);
+        assert_eq!(strategy.state("localhost:4001"), CircuitState::Open);
+
+        // The cool-down has passed, so the host is tried again
+        assert_eq!(
+            strategy.fallback(&mut hosts, "localhost:4003", false),
+            Some(&"localhost:4001".to_string())
+        );
+        assert_eq!(strategy.state("localhost:4001"), CircuitState::HalfOpen);
+
+        strategy.on_success("localhost:4001", Duration::ZERO);
+        assert_eq!(strategy.state("localhost:4001"), CircuitState::HalfOpen);
+        strategy.on_success("localhost:4001", Duration::ZERO);
+        assert_eq!(strategy.state("localhost:4001"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn unit_circuit_breaker_half_open_failure() {
+        let mut hosts = hosts();
+        let mut strategy = CircuitBreaker::new(RoundRobin)
+            .failure_threshold(1)
+            .cool_down(Duration::ZERO);
+
+        strategy.fallback(&mut hosts, "localhost:4001", false);
+        strategy.fallback(&mut hosts, "localhost:4003", false);
+        assert_eq!(strategy.state("localhost:4001"), CircuitState::HalfOpen);
+
+        strategy.fallback(&mut hosts, "localhost:4001", false);
+        assert_eq!(strategy.state("localhost:4001"), CircuitState::Open);
+    }
+
+    #[test]
+    fn unit_circuit_breaker_select() {
+        let mut hosts = hosts();
+        let mut strategy = CircuitBreaker::new(RoundRobin)
+            .failure_threshold(1)
+            .cool_down(Duration::from_secs(60));
+
+        assert_eq!(strategy.select(&hosts), Some(&"localhost:4001".to_string()));
+
+        strategy.fallback(&mut hosts, "localhost:4001", false);
+        assert_eq!(strategy.select(&hosts), Some(&"localhost:4002".to_string()));
+    }
+
+    #[test]
+    fn unit_circuit_breaker_ignores_skipped_hosts() {
+        let mut hosts = hosts();
+        let history = crate::fallback::HostHistory::default();
+        let mut strategy = CircuitBreaker::new(RoundRobin).failure_threshold(1);
+
+        let ctx = FallbackContext {
+            current_host: "localhost:4001",
+            persist: false,
+            error: None,
+            attempt: 1,
+            elapsed: Duration::ZERO,
+            operation: None,
+            is_write: false,
+            history: &history,
+        };
+
+        assert_eq!(
+            strategy.fallback_with_context(&mut hosts, &ctx),
+            Some(&"localhost:4002".to_string())
+        );
+        assert_eq!(strategy.state("localhost:4001"), CircuitState::Closed);
+
+        let ctx = FallbackContext {
+            error: Some(crate::retry::RetryErrorKind::Connect),
+            ..ctx
+        };
+        strategy.fallback_with_context(&mut hosts, &ctx);
+        assert_eq!(strategy.state("localhost:4001"), CircuitState::Open);
+    }
+
+    #[test]
+    fn unit_circuit_breaker_all_open() {
+        let mut hosts = hosts();
+        let mut strategy = CircuitBreaker::new(RoundRobin)
+            .failure_threshold(1)
+            .cool_down(Duration::from_secs(60));
+
+        strategy.fallback(&mut hosts, "localhost:4001", false);
+        strategy.fallback(&mut hosts, "localhost:4002", false);
+
+        assert_eq!(strategy.fallback(&mut hosts, "localhost:4003", false), None);
+    }
+}
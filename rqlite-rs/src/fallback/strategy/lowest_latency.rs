@@ -0,0 +1,353 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use super::FallbackStrategy;
+use crate::{client::api_host, fallback::FallbackContext, node::Node};
+
+#[derive(Debug, Clone, Copy)]
+struct Estimate {
+    /// The moving average of the latency in seconds.
+    latency: f64,
+    /// When the latency was last measured, `None` if it was only seeded.
+    measured_at: Option<Instant>,
+    /// If the last request to the host failed.
+    failed: bool,
+}
+
+/// A strategy picking the host with the lowest latency.
+///
+/// The latency of every host is tracked as an exponentially weighted moving average of the
+/// durations of successful requests. New requests are sent to the fastest host, so reads go to
+/// the nearest node. Hosts that have not been measured yet are tried first and hosts whose last
+/// request failed are only used if no other host is left.
+///
+/// Every `probe_interval` requests, the host with the oldest measurement is picked instead, so
+/// measurements of slower hosts do not get stale.
+///
+/// The estimates can be seeded with the latencies reported by the `/nodes` endpoint using
+/// [`LowestLatency::seed`].
+///
+/// # Example
+/// ```
+/// use rqlite_rs::{fallback::LowestLatency, RqliteClientBuilder};
+///
+/// let client = RqliteClientBuilder::new()
+///     .known_host("localhost:4001")
+///     .known_host("localhost:4003")
+///     .fallback_strategy(LowestLatency::new().smoothing(0.2).probe_interval(50))
+///     .build();
+///
+/// assert!(client.is_ok());
+/// ```
+pub struct LowestLatency {
+    smoothing: f64,
+    probe_interval: u64,
+    selections: u64,
+    estimates: HashMap<String, Estimate>,
+}
+
+impl Default for LowestLatency {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LowestLatency {
+    /// Creates a new strategy with a smoothing factor of `0.3`, probing the host with the oldest
+    /// measurement every 100 requests.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            smoothing: 0.3,
+            probe_interval: 100,
+            selections: 0,
+            estimates: HashMap::new(),
+        }
+    }
+
+    /// Sets the weight of a new measurement in the moving average, between `0` and `1`.
+    /// Higher values adapt faster to changes, lower values smooth out outliers.
+    #[must_use]
+    pub fn smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = smoothing.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets after how many requests the host with the oldest measurement is probed.
+    /// `0` disables probing.
+    #[must_use]
+    pub const fn probe_interval(mut self, requests: u64) -> Self {
+        self.probe_interval = requests;
+        self
+    }
+
+    /// Seeds the estimates with the latencies reported by the `/nodes` endpoint, as returned
+    /// by [`RqliteClient::nodes`](crate::RqliteClient::nodes).
+    /// Unreachable nodes are treated as failed.
+    #[must_use]
+    pub fn seed(mut self, nodes: &[Node]) -> Self {
+        for node in nodes {
+            let latency = if node.time.is_finite() && node.time >= 0.0 {
+                node.time
+            } else {
+                0.0
+            };
+
+            self.estimates.insert(
+                api_host(&node.api_addr).to_string(),
+                Estimate {
+                    latency,
+                    measured_at: None,
+                    failed: !node.reachable,
+                },
+            );
+        }
+
+        self
+    }
+
+    /// Orders hosts by whether they failed, then by their latency.
+    /// Hosts without an estimate come first.
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        let rank = |host: &str| {
+            self.estimates
+                .get(host)
+                .map_or((false, f64::NEG_INFINITY), |estimate| {
+                    (estimate.failed, estimate.latency)
+                })
+        };
+        let (a, b) = (rank(a), rank(b));
+
+        a.0.cmp(&b.0).then(a.1.total_cmp(&b.1))
+    }
+
+    fn fastest<'a>(&self, hosts: &'a [String], exclude: Option<&str>) -> Option<&'a String> {
+        hosts
+            .iter()
+            .filter(|host| Some(host.as_str()) != exclude)
+            .min_by(|a, b| self.compare(a, b))
+    }
+
+    fn stalest<'a>(&self, hosts: &'a [String]) -> Option<&'a String> {
+        hosts.iter().min_by_key(|host| {
+            self.estimates
+                .get(host.as_str())
+                .and_then(|estimate| estimate.measured_at)
+        })
+    }
+
+    fn record_failure(&mut self, host: &str) {
+        if let Some(estimate) = self.estimates.get_mut(host) {
+            estimate.failed = true;
+        } else {
+            self.estimates.insert(
+                host.to_string(),
+                Estimate {
+                    latency: 0.0,
+                    measured_at: None,
+                    failed: true,
+                },
+            );
+        }
+    }
+
+    /// Returns the fastest host other than `current_host`.
+    fn next<'a>(
+        &self,
+        hosts: &'a mut [String],
+        current_host: &str,
+        persist: bool,
+    ) -> Option<&'a String> {
+        let next = self.fastest(hosts, Some(current_host))?;
+        let index = hosts.iter().position(|host| host == next)?;
+
+        if persist {
+            hosts.swap(0, index);
+            hosts.first()
+        } else {
+            hosts.get(index)
+        }
+    }
+
+    #[cfg(test)]
+    fn latency(&self, host: &str) -> Option<f64> {
+        self.estimates.get(host).map(|estimate| estimate.latency)
+    }
+}
+
+impl FallbackStrategy for LowestLatency {
+    fn fallback<'a>(
+        &mut self,
+        hosts: &'a mut Vec<String>,
+        current_host: &str,
+        persist: bool,
+    ) -> Option<&'a String> {
+        self.record_failure(current_host);
+        self.next(hosts, current_host, persist)
+    }
+
+    fn fallback_with_context<'a>(
+        &mut self,
+        hosts: &'a mut Vec<String>,
+        ctx: &FallbackContext<'_>,
+    ) -> Option<&'a String> {
+        // Hosts that were skipped without being tried did not fail
+        if ctx.error.is_some() {
+            self.record_failure(ctx.current_host);
+        }
+        self.next(hosts, ctx.current_host, ctx.persist)
+    }
+
+    fn select<'a>(&mut self, hosts: &'a [String]) -> Option<&'a String> {
+        self.selections = self.selections.wrapping_add(1);
+
+        if self.probe_interval > 0 && self.selections % self.probe_interval == 0 {
+            return self.stalest(hosts);
+        }
+
+        self.fastest(hosts, None)
+    }
+
+    fn on_success(&mut self, host: &str, latency: Duration) {
+        let sample = latency.as_secs_f64();
+        let now = Some(Instant::now());
+
+        match self.estimates.get_mut(host) {
+            // The first measurement after a failure or seed replaces the estimate
+            Some(estimate) if !estimate.failed && estimate.measured_at.is_some() => {
+                estimate.latency = self
+                    .smoothing
+                    .mul_add(sample - estimate.latency, estimate.latency);
+                estimate.measured_at = now;
+            }
+            _ => {
+                self.estimates.insert(
+                    host.to_string(),
+                    Estimate {
+                        latency: sample,
+                        measured_at: now,
+                        failed: false,
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hosts() -> Vec<String> {
+        vec![
+            "localhost:4001".to_string(),
+            "localhost:4002".to_string(),
+            "localhost:4003".to_string(),
+        ]
+    }
+
+    fn measure(strategy: &mut LowestLatency, host: &str, millis: u64) {
+        strategy.on_success(host, Duration::from_millis(millis));
+    }
+
+    #[test]
+    fn unit_lowest_latency_select() {
+        let hosts = hosts();
+        let mut strategy = LowestLatency::new().probe_interval(0);
+
+        // Unmeasured hosts are tried first
+        measure(&mut strategy, "localhost:4001", 30);
+        measure(&mut strategy, "localhost:4002", 10);
+        assert_eq!(strategy.select(&hosts), Some(&hosts[2]));
+
+        measure(&mut strategy, "localhost:4003", 20);
+        assert_eq!(strategy.select(&hosts), Some(&hosts[1]));
+    }
+
+    #[test]
+    fn unit_lowest_latency_moving_average() {
+        let mut strategy = LowestLatency::new().smoothing(0.5);
+
+        measure(&mut strategy, "localhost:4001", 100);
+        measure(&mut strategy, "localhost:4001", 200);
+
+        let latency = strategy.latency("localhost:4001").unwrap();
+        assert!((latency - 0.15).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unit_lowest_latency_fallback() {
+        let mut hosts = hosts();
+        let mut strategy = LowestLatency::new();
+
+        measure(&mut strategy, "localhost:4001", 10);
+        measure(&mut strategy, "localhost:4002", 30);
+        measure(&mut strategy, "localhost:4003", 20);
+
+        assert_eq!(
+            strategy.fallback(&mut hosts, "localhost:4001", false),
+            Some(&"localhost:4003".to_string())
+        );
+
+        // The failed host is only used again after a successful request
+        assert_eq!(strategy.select(&hosts), Some(&hosts[2]));
+        measure(&mut strategy, "localhost:4001", 15);
+        assert_eq!(strategy.select(&hosts), Some(&hosts[0]));
+
+        assert_eq!(
+            strategy.fallback(&mut hosts, "localhost:4001", true),
+            Some(&"localhost:4003".to_string())
+        );
+        assert_eq!(hosts[0], "localhost:4003");
+    }
+
+    #[test]
+    fn unit_lowest_latency_probe() {
+        let hosts = hosts();
+        let mut strategy = LowestLatency::new().probe_interval(3);
+
+        for (host, millis) in [
+            ("localhost:4002", 30),
+            ("localhost:4001", 10),
+            ("localhost:4003", 20),
+        ] {
+            measure(&mut strategy, host, millis);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        assert_eq!(strategy.select(&hosts), Some(&hosts[0]));
+        assert_eq!(strategy.select(&hosts), Some(&hosts[0]));
+        // The oldest measurement is refreshed
+        assert_eq!(strategy.select(&hosts), Some(&hosts[1]));
+    }
+
+    #[test]
+    fn unit_lowest_latency_seed() {
+        let node = |api_addr: &str, time: f64, reachable: bool| Node {
+            id: api_addr.to_string(),
+            api_addr: api_addr.to_string(),
+            raft_addr: String::new(),
+            voter: true,
+            reachable,
+            leader: false,
+            time,
+            error: None,
+        };
+
+        let hosts = hosts();
+        let mut strategy = LowestLatency::new().probe_interval(0).seed(&[
+            node("http://localhost:4001", 0.03, true),
+            node("http://localhost:4002", 0.01, false),
+            node("http://localhost:4003", 0.02, true),
+        ]);
+
+        assert_eq!(strategy.select(&hosts), Some(&hosts[2]));
+
+        // A measurement replaces the seeded latency
+        measure(&mut strategy, "localhost:4001", 5);
+        assert_eq!(strategy.select(&hosts), Some(&hosts[0]));
+    }
+}
@@ -1,7 +1,15 @@
+use std::time::Duration;
+
+use super::{FallbackContext, SuccessContext};
+
 mod round_robin;
 pub use round_robin::RoundRobin;
 mod priority;
 pub use priority::Priority;
+mod circuit_breaker;
+pub use circuit_breaker::CircuitBreaker;
+mod lowest_latency;
+pub use lowest_latency::LowestLatency;
 #[cfg(feature = "random-fallback")]
 mod random;
 #[cfg(feature = "random-fallback")]
@@ -9,6 +17,11 @@ pub use random::Random;
 
 /// `FallbackStrategy` is the trait that defines the strategy to use when a host fails.
 /// The default strategy is `RoundRobin`.
+///
+/// The client calls [`FallbackStrategy::fallback_with_context`] and
+/// [`FallbackStrategy::on_success_with_context`], which default to
+/// [`FallbackStrategy::fallback`] and [`FallbackStrategy::on_success`]. Strategies that need
+/// to know why a request failed or how it went before override them.
 pub trait FallbackStrategy: Send + Sync + 'static {
     /// fallback returns the next host to try and can modify the hosts list if needed.
     fn fallback<'a>(
@@ -17,6 +30,32 @@ pub trait FallbackStrategy: Send + Sync + 'static {
         current_host: &str,
         persist: bool,
     ) -> Option<&'a String>;
+
+    /// Like [`FallbackStrategy::fallback`], with details about the failed request and the
+    /// results of previous requests.
+    fn fallback_with_context<'a>(
+        &mut self,
+        hosts: &'a mut Vec<String>,
+        ctx: &FallbackContext<'_>,
+    ) -> Option<&'a String> {
+        self.fallback(hosts, ctx.current_host, ctx.persist)
+    }
+
+    /// Selects the host a new request is sent to.
+    /// Returns `None` to use the first host, which is the default.
+    fn select<'a>(&mut self, _hosts: &'a [String]) -> Option<&'a String> {
+        None
+    }
+
+    /// Called after a request to `host` succeeded, with the time it took.
+    /// Strategies keeping track of failures can use this to reset them.
+    fn on_success(&mut self, _host: &str, _latency: Duration) {}
+
+    /// Like [`FallbackStrategy::on_success`], with details about the request and the results of
+    /// previous requests.
+    fn on_success_with_context(&mut self, ctx: &SuccessContext<'_>) {
+        self.on_success(ctx.host, ctx.latency);
+    }
 }
 
 impl Default for Box<dyn FallbackStrategy> {
@@ -0,0 +1,377 @@
+//! Health checks of the known hosts.
+//!
+//! When enabled using
+//! [`RqliteClientBuilder::health_check`](crate::RqliteClientBuilder::health_check), the
+//! `/readyz` endpoint of every known host is probed periodically. Hosts failing the check are
+//! quarantined: requests and the [`FallbackStrategy`](crate::fallback::FallbackStrategy) skip
+//! them until they pass again, unless all hosts are quarantined.
+
+use std::{
+    collections::HashMap,
+    sync::{RwLock, Weak},
+    time::{Duration, Instant},
+};
+
+use futures_util::future::join_all;
+
+use crate::{
+    config::Scheme, discovery::BackgroundTask, error::RequestError, request::RequestOptions,
+};
+
+/// Whether a host passed its last health checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthStatus {
+    /// The host is used for requests.
+    Healthy,
+    /// The host failed its health checks and is skipped until it passes again.
+    Unhealthy,
+}
+
+/// The health of a single host, as returned by
+/// [`RqliteClient::host_health`](crate::RqliteClient::host_health).
+#[derive(Debug, Clone)]
+pub struct HostHealth {
+    /// The host, in the format `hostname:port`.
+    pub host: String,
+    /// The current status. Hosts that have not been checked yet are healthy.
+    pub status: HealthStatus,
+    /// The number of consecutive failed checks.
+    pub consecutive_failures: u32,
+    /// When the host was last checked, if it has been checked.
+    pub last_checked: Option<Instant>,
+    /// The error of the last check, if it failed.
+    pub last_error: Option<String>,
+}
+
+/// The configuration of the health checks.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use rqlite_rs::{health::HealthCheck, RqliteClientBuilder};
+///
+/// let client = RqliteClientBuilder::new()
+///     .known_host("localhost:4001")
+///     .known_host("localhost:4003")
+///     .health_check(
+///         HealthCheck::new(Duration::from_secs(10)).unhealthy_threshold(2),
+///         |task| {
+///             tokio::spawn(task);
+///         },
+///     )
+///     .build();
+///
+/// assert!(client.is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct HealthCheck {
+    interval: Duration,
+    timeout: Duration,
+    unhealthy_threshold: u32,
+    healthy_threshold: u32,
+}
+
+impl HealthCheck {
+    /// Creates a new configuration checking all hosts every `interval`, with a timeout of 1s.
+    /// A single failed check quarantines a host and a single passed check restores it.
+    #[must_use]
+    pub const fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            timeout: Duration::from_secs(1),
+            unhealthy_threshold: 1,
+            healthy_threshold: 1,
+        }
+    }
+
+    /// Sets the timeout of a single check.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the number of consecutive failed checks after which a host is quarantined.
+    /// Values below `1` are treated as `1`.
+    #[must_use]
+    pub const fn unhealthy_threshold(mut self, threshold: u32) -> Self {
+        self.unhealthy_threshold = threshold;
+        self
+    }
+
+    /// Sets the number of consecutive passed checks after which a quarantined host is used again.
+    /// Values below `1` are treated as `1`.
+    #[must_use]
+    pub const fn healthy_threshold(mut self, threshold: u32) -> Self {
+        self.healthy_threshold = threshold;
+        self
+    }
+}
+
+#[derive(Default)]
+struct HostState {
+    unhealthy: bool,
+    consecutive_failures: u32,
+    consecutive_successes: u32,
+    last_checked: Option<Instant>,
+    last_error: Option<String>,
+}
+
+/// The health of all hosts, shared between the client and the health checker.
+#[derive(Default)]
+pub(crate) struct HealthRegistry {
+    hosts: RwLock<HashMap<String, HostState>>,
+}
+
+impl HealthRegistry {
+    /// Returns `true` if the host failed its health checks.
+    pub(crate) fn is_quarantined(&self, host: &str) -> bool {
+        // A poisoned lock must not make hosts unusable
+        self.hosts
+            .read()
+            .is_ok_and(|hosts| hosts.get(host).is_some_and(|state| state.unhealthy))
+    }
+
+    /// Records the result of a check and updates the status of the host.
+    pub(crate) fn record(
+        &self,
+        host: &str,
+        result: Result<(), String>,
+        check: &HealthCheck,
+    ) -> Result<(), RequestError> {
+        let mut hosts = self
+            .hosts
+            .write()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?;
+        let state = hosts.entry(host.to_string()).or_default();
+        state.last_checked = Some(Instant::now());
+
+        match result {
+            Ok(()) => {
+                state.consecutive_failures = 0;
+                state.consecutive_successes = state.consecutive_successes.saturating_add(1);
+                state.last_error = None;
+
+                if state.unhealthy && state.consecutive_successes >= check.healthy_threshold {
+                    state.unhealthy = false;
+                    tracing::info!("Host {host} passed its health checks again");
+                }
+            }
+            Err(e) => {
+                state.consecutive_successes = 0;
+                state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+
+                if !state.unhealthy && state.consecutive_failures >= check.unhealthy_threshold {
+                    state.unhealthy = true;
+                    tracing::warn!("Host {host} failed its health check, quarantining it: {e}");
+                }
+
+                state.last_error = Some(e);
+            }
+        }
+
+        drop(hosts);
+        Ok(())
+    }
+
+    /// Forgets hosts that are no longer known.
+    pub(crate) fn retain(&self, known: &[String]) -> Result<(), RequestError> {
+        self.hosts
+            .write()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?
+            .retain(|host, _state| known.contains(host));
+        Ok(())
+    }
+
+    /// Returns the health of the given hosts, in their order.
+    pub(crate) fn snapshot(&self, known: &[String]) -> Result<Vec<HostHealth>, RequestError> {
+        let hosts = self
+            .hosts
+            .read()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?;
+
+        Ok(known
+            .iter()
+            .map(|host| {
+                let state = hosts.get(host);
+
+                HostHealth {
+                    host: host.clone(),
+                    status: if state.is_some_and(|state| state.unhealthy) {
+                        HealthStatus::Unhealthy
+                    } else {
+                        HealthStatus::Healthy
+                    },
+                    consecutive_failures: state.map_or(0, |state| state.consecutive_failures),
+                    last_checked: state.and_then(|state| state.last_checked),
+                    last_error: state.and_then(|state| state.last_error.clone()),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Checks the health of all hosts periodically until the client is dropped.
+pub(crate) struct HealthChecker {
+    pub(crate) client: reqwest::Client,
+    pub(crate) scheme: Scheme,
+    pub(crate) hosts: Weak<RwLock<Vec<String>>>,
+    pub(crate) health: Weak<HealthRegistry>,
+    pub(crate) check: HealthCheck,
+}
+
+impl HealthChecker {
+    pub(crate) fn into_task(mut self) -> BackgroundTask {
+        self.check.unhealthy_threshold = self.check.unhealthy_threshold.max(1);
+        self.check.healthy_threshold = self.check.healthy_threshold.max(1);
+
+        Box::pin(async move {
+            // Checked right away, so hosts that are down are skipped from the start.
+            // Stops once the client has been dropped.
+            while let (Some(hosts), Some(health)) = (self.hosts.upgrade(), self.health.upgrade()) {
+                if let Err(e) = self.check_hosts(&hosts, &health).await {
+                    tracing::warn!("Failed to check the health of the hosts: {e}");
+                }
+
+                drop((hosts, health));
+                futures_timer::Delay::new(self.check.interval).await;
+            }
+        })
+    }
+
+    async fn check_hosts(
+        &self,
+        hosts: &RwLock<Vec<String>>,
+        health: &HealthRegistry,
+    ) -> Result<(), RequestError> {
+        let hosts = hosts
+            .read()
+            .map_err(|_poisoned| RequestError::LockPoisoned)?
+            .clone();
+
+        health.retain(&hosts)?;
+
+        let results = join_all(hosts.iter().map(|host| self.probe(host))).await;
+
+        for (host, result) in hosts.iter().zip(results) {
+            health.record(host, result, &self.check)?;
+        }
+
+        Ok(())
+    }
+
+    /// Probes the `/readyz` endpoint of a host.
+    async fn probe(&self, host: &str) -> Result<(), String> {
+        let options = RequestOptions {
+            endpoint: "readyz".to_string(),
+            method: reqwest::Method::GET,
+            timeout: Some(self.check.timeout),
+            ..Default::default()
+        };
+
+        let res = options
+            .to_reqwest_request(&self.client, host, &self.scheme)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if res.status() == reqwest::StatusCode::OK {
+            Ok(())
+        } else {
+            Err(format!("Host is not ready: {}", res.status()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn status(health: &HealthRegistry, host: &str) -> HealthStatus {
+        health.snapshot(&[host.to_string()]).unwrap()[0].status
+    }
+
+    #[test]
+    fn unit_health_registry_thresholds() {
+        let health = HealthRegistry::default();
+        let check = HealthCheck::new(Duration::from_secs(1))
+            .unhealthy_threshold(2)
+            .healthy_threshold(2);
+        let host = "localhost:4001";
+
+        assert_eq!(status(&health, host), HealthStatus::Healthy);
+
+        health
+            .record(host, Err("down".to_string()), &check)
+            .unwrap();
+        assert!(!health.is_quarantined(host));
+
+        health
+            .record(host, Err("down".to_string()), &check)
+            .unwrap();
+        assert!(health.is_quarantined(host));
+        assert_eq!(status(&health, host), HealthStatus::Unhealthy);
+
+        health.record(host, Ok(()), &check).unwrap();
+        assert!(health.is_quarantined(host));
+
+        health.record(host, Ok(()), &check).unwrap();
+        assert!(!health.is_quarantined(host));
+    }
+
+    #[test]
+    fn unit_health_registry_snapshot() {
+        let health = HealthRegistry::default();
+        let check = HealthCheck::new(Duration::from_secs(1));
+        let hosts = vec!["localhost:4001".to_string(), "localhost:4002".to_string()];
+
+        health
+            .record("localhost:4002", Err("down".to_string()), &check)
+            .unwrap();
+
+        let snapshot = health.snapshot(&hosts).unwrap();
+        assert_eq!(snapshot[0].host, "localhost:4001");
+        assert_eq!(snapshot[0].status, HealthStatus::Healthy);
+        assert!(snapshot[0].last_checked.is_none());
+        assert_eq!(snapshot[1].status, HealthStatus::Unhealthy);
+        assert_eq!(snapshot[1].consecutive_failures, 1);
+        assert_eq!(snapshot[1].last_error.as_deref(), Some("down"));
+
+        health.retain(&hosts[..1]).unwrap();
+        assert!(!health.is_quarantined("localhost:4002"));
+    }
+
+    #[tokio::test]
+    async fn unit_health_checker_quarantines_unreachable_host() {
+        let hosts = Arc::new(RwLock::new(vec!["localhost:1".to_string()]));
+        let health = Arc::new(HealthRegistry::default());
+
+        let task = HealthChecker {
+            client: reqwest::Client::new(),
+            scheme: Scheme::Http,
+            hosts: Arc::downgrade(&hosts),
+            health: Arc::downgrade(&health),
+            check: HealthCheck::new(Duration::from_millis(10)),
+        }
+        .into_task();
+        let task = tokio::spawn(task);
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !health.is_quarantined("localhost:1") {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // The task finishes once the client is dropped
+        drop(hosts);
+        tokio::time::timeout(Duration::from_secs(5), task)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
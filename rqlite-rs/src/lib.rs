@@ -10,14 +10,22 @@ pub mod query;
 pub mod query_result;
 pub mod response;
 pub use client::{RqliteClient, RqliteClientBuilder};
+pub use query::ToArgs;
 pub use rqlite_rs_core::*;
+pub mod backup;
 pub mod batch;
 pub mod config;
+pub mod discovery;
 pub mod error;
 pub mod fallback;
+pub mod health;
+pub mod load;
+pub mod migrate;
 pub mod node;
 pub mod request;
+pub mod retry;
 pub(crate) mod select;
+pub mod status;
 
 #[cfg(feature = "macros")]
 pub use rqlite_rs_macros::*;
@@ -27,8 +35,11 @@ pub use rqlite_rs_core::decode;
 pub mod prelude {
     pub use crate::client::RqliteClient;
     pub use crate::client::RqliteClientBuilder;
+    pub use crate::decode::Decode;
     pub use crate::query_result::QueryResult;
     pub use crate::FromRow;
     pub use crate::IntoTypedRows;
     pub use crate::Row;
+    pub use crate::SqlValue;
+    pub use crate::ToArgs;
 }
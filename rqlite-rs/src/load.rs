@@ -0,0 +1,269 @@
+//! Options and results for loading data into the database.
+//!
+//! See [`RqliteClient::load_sqlite_file`](crate::RqliteClient::load_sqlite_file),
+//! [`RqliteClient::load_sql_dump`](crate::RqliteClient::load_sql_dump),
+//! [`RqliteClient::boot`](crate::RqliteClient::boot) and the
+//! [rqlite documentation](https://rqlite.io/docs/guides/backup/#restoring-from-sqlite).
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use futures_util::{stream, Stream, TryStreamExt};
+use tokio::io::AsyncReadExt;
+
+use crate::{
+    query_result::QueryResult,
+    response::{QueryError, RqliteResult},
+};
+
+/// The default timeout for loading data.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// The size of the chunks the file is sent in.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The progress of an upload, passed to the callback set using [`LoadOptions::progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// The number of bytes sent so far.
+    pub sent: u64,
+    /// The size of the file in bytes.
+    pub total: u64,
+}
+
+type ProgressCallback = Arc<dyn Fn(LoadProgress) + Send + Sync>;
+
+/// Options for loading data into the database.
+///
+/// # Example
+/// ```
+/// use rqlite_rs::load::LoadOptions;
+///
+/// let options = LoadOptions::new().progress(|progress| {
+///     println!("{} of {} bytes sent", progress.sent, progress.total);
+/// });
+/// ```
+#[derive(Clone)]
+pub struct LoadOptions {
+    progress: Option<ProgressCallback>,
+    timeout: Duration,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for LoadOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoadOptions")
+            .field("progress", &self.progress.is_some())
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl LoadOptions {
+    /// Creates options without progress reporting.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            progress: None,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets a callback that is called every time a chunk of the file has been sent.
+    /// If the request is retried, the progress starts again from zero.
+    #[must_use]
+    pub fn progress(mut self, progress: impl Fn(LoadProgress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Sets the timeout for loading the data. Defaults to 5 minutes.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub(crate) const fn request_timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+/// A file sent as the body of a request.
+/// The file is opened again for every attempt, so the request can be retried.
+#[derive(Clone)]
+pub(crate) struct FileBody {
+    pub(crate) path: PathBuf,
+    pub(crate) size: u64,
+    pub(crate) content_type: &'static str,
+    pub(crate) progress: Option<ProgressCallback>,
+}
+
+impl FileBody {
+    pub(crate) fn new(
+        path: &Path,
+        size: u64,
+        content_type: &'static str,
+        options: &LoadOptions,
+    ) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            size,
+            content_type,
+            progress: options.progress.clone(),
+        }
+    }
+
+    /// Returns a body streaming the file in chunks.
+    pub(crate) fn to_body(&self) -> reqwest::Body {
+        reqwest::Body::wrap_stream(self.chunks())
+    }
+
+    /// Reads the file in chunks, reporting the progress after every chunk.
+    fn chunks(&self) -> impl Stream<Item = std::io::Result<Vec<u8>>> + Send + 'static {
+        let total = self.size;
+        let progress = self.progress.clone();
+        let mut sent = 0u64;
+
+        stream::once(tokio::fs::File::open(self.path.clone()))
+            .map_ok(|file| {
+                stream::try_unfold(file, |mut file| async move {
+                    let mut chunk = vec![0; CHUNK_SIZE];
+                    let read = file.read(&mut chunk).await?;
+
+                    if read == 0 {
+                        return Ok(None);
+                    }
+
+                    chunk.truncate(read);
+                    Ok(Some((chunk, file)))
+                })
+            })
+            .try_flatten()
+            .inspect_ok(move |chunk| {
+                sent += chunk.len() as u64;
+                if let Some(progress) = &progress {
+                    progress(LoadProgress { sent, total });
+                }
+            })
+    }
+}
+
+/// The result of loading data into the database.
+#[derive(Debug, Clone)]
+pub struct LoadResult {
+    bytes: u64,
+    results: Vec<RqliteResult<QueryResult>>,
+}
+
+impl LoadResult {
+    pub(crate) const fn new(bytes: u64, results: Vec<RqliteResult<QueryResult>>) -> Self {
+        Self { bytes, results }
+    }
+
+    /// Returns the size of the loaded file in bytes.
+    #[must_use]
+    pub const fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Returns the number of statements that were executed.
+    /// This is only reported for SQL dumps, `SQLite` files are loaded as a whole.
+    #[must_use]
+    pub fn statements(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns the index and error message of every statement that failed.
+    #[must_use]
+    pub fn errors(&self) -> Vec<(usize, &str)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, result)| match result {
+                RqliteResult::Error(QueryError { error }) => Some((index, error.as_str())),
+                RqliteResult::Success(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns `true` if no statement failed.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.errors().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_load_result() {
+        let results = serde_json::from_str::<Vec<RqliteResult<QueryResult>>>(
+            r#"[
+                {"last_insert_id": 1, "rows_affected": 1},
+                {"error": "table foo already exists"},
+                {"rows_affected": 0}
+            ]"#,
+        )
+        .unwrap();
+
+        let result = LoadResult::new(128, results);
+
+        assert_eq!(result.bytes(), 128);
+        assert_eq!(result.statements(), 3);
+        assert_eq!(result.errors(), vec![(1, "table foo already exists")]);
+        assert!(!result.is_success());
+
+        let result = LoadResult::new(0, vec![]);
+        assert!(result.is_success());
+    }
+
+    #[tokio::test]
+    async fn unit_file_body_progress() {
+        let path = std::env::temp_dir().join("rqlite_rs_unit_file_body_progress.sql");
+        let size = CHUNK_SIZE + 10;
+        std::fs::write(&path, vec![b'x'; size]).unwrap();
+
+        let reports = Arc::new(std::sync::Mutex::new(vec![]));
+        let options = LoadOptions::new().progress({
+            let reports = Arc::clone(&reports);
+            move |progress| reports.lock().unwrap().push(progress)
+        });
+
+        let body = FileBody::new(&path, size as u64, "text/plain", &options);
+        let chunks = body.chunks().try_collect::<Vec<_>>().await.unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(chunks.concat().len(), size);
+        assert_eq!(
+            reports.lock().unwrap().last(),
+            Some(&LoadProgress {
+                sent: size as u64,
+                total: size as u64
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn unit_file_body_missing_file() {
+        let body = FileBody::new(
+            Path::new("/does/not/exist.sql"),
+            0,
+            "text/plain",
+            &LoadOptions::new(),
+        );
+
+        assert!(body.chunks().try_collect::<Vec<_>>().await.is_err());
+    }
+}
@@ -1 +1,799 @@
+//! Schema migrations for rqlite.
+//!
+//! A [`Migrator`] holds an ordered list of [`Migration`]s and applies the ones that are
+//! still pending. Applied versions are tracked in the `_rqlite_rs_migrations` table together
+//! with a checksum of their `up` SQL, so a migration that was edited after it has been applied
+//! is detected instead of silently diverging.
+//!
+//! Migrations can be embedded at compile time with the `migrate!` macro (requires the
+//! `macros` feature), loaded at runtime with [`Migrator::from_dir`] or defined inline:
+//!
+//! ```
+//! use rqlite_rs::migrate::{Migration, Migrator};
+//!
+//! let migrator = Migrator::new(vec![
+//!     Migration::new(1, "create users", "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)")
+//!         .with_down("DROP TABLE users"),
+//!     Migration::new(2, "add email", "CREATE INDEX users_name ON users (name)")
+//!         .with_down("DROP INDEX users_name"),
+//! ]);
+//!
+//! assert_eq!(migrator.migrations().len(), 2);
+//! ```
+//!
+//! Inside a migration directory each file is named `<version>_<description>.sql` or, when a
+//! down migration is provided, `<version>_<description>.up.sql` and
+//! `<version>_<description>.down.sql`.
 
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::MigrateError,
+    query::{
+        lexer::{self, TokenKind},
+        Operation, RqliteArguments, RqliteQuery,
+    },
+    response::RqliteResult,
+    IntoTypedRows, RqliteClient,
+};
+
+/// The name of the table used to track applied migrations.
+pub const MIGRATIONS_TABLE: &str = "_rqlite_rs_migrations";
+
+/// A single schema migration.
+#[derive(Debug, Clone)]
+pub struct Migration {
+    version: i64,
+    description: Cow<'static, str>,
+    up: Cow<'static, str>,
+    down: Option<Cow<'static, str>>,
+    checksum: String,
+}
+
+impl Migration {
+    /// Creates a new migration from its version, a short description and the `up` SQL.
+    /// The `up` SQL may contain multiple statements separated by `;`.
+    #[must_use]
+    pub fn new(
+        version: i64,
+        description: impl Into<Cow<'static, str>>,
+        up: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let up = up.into();
+        let checksum = checksum(&up);
+
+        Self {
+            version,
+            description: description.into(),
+            up,
+            down: None,
+            checksum,
+        }
+    }
+
+    /// Sets the `down` SQL used to revert the migration.
+    #[must_use]
+    pub fn with_down(mut self, down: impl Into<Cow<'static, str>>) -> Self {
+        self.down = Some(down.into());
+        self
+    }
+
+    /// Returns the version of the migration.
+    #[must_use]
+    pub const fn version(&self) -> i64 {
+        self.version
+    }
+
+    /// Returns the description of the migration.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns the `up` SQL of the migration.
+    #[must_use]
+    pub fn up(&self) -> &str {
+        &self.up
+    }
+
+    /// Returns the `down` SQL of the migration, if any.
+    #[must_use]
+    pub fn down(&self) -> Option<&str> {
+        self.down.as_deref()
+    }
+
+    /// Returns the hex encoded SHA-256 checksum of the `up` SQL.
+    #[must_use]
+    pub fn checksum(&self) -> &str {
+        &self.checksum
+    }
+}
+
+/// A migration as recorded in the migrations table.
+#[derive(Debug, Clone)]
+pub struct AppliedMigration {
+    /// The version of the migration.
+    pub version: i64,
+    /// The description of the migration at the time it was applied.
+    pub description: String,
+    /// The checksum of the `up` SQL at the time it was applied.
+    pub checksum: String,
+    /// Unix timestamp (in seconds) of when the migration was applied.
+    pub applied_at: i64,
+}
+
+/// Describes a migration that was (or in dry-run mode would have been) applied or reverted.
+#[derive(Debug, Clone)]
+pub struct MigrationInfo {
+    /// The version of the migration.
+    pub version: i64,
+    /// The description of the migration.
+    pub description: String,
+    /// The number of statements executed for the migration.
+    pub statements: usize,
+}
+
+/// The outcome of [`Migrator::run`] or [`Migrator::undo`].
+#[derive(Debug, Clone)]
+pub struct MigrationReport {
+    /// If `true`, nothing was executed and `migrations` lists what would have been done.
+    pub dry_run: bool,
+    /// The migrations in the order they were (or would have been) executed.
+    pub migrations: Vec<MigrationInfo>,
+}
+
+impl MigrationReport {
+    /// Returns `true` if no migration was (or would have been) executed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty()
+    }
+}
+
+/// Applies and reverts [`Migration`]s on a rqlite cluster.
+#[derive(Debug, Clone, Default)]
+pub struct Migrator {
+    migrations: Vec<Migration>,
+    dry_run: bool,
+}
+
+impl Migrator {
+    /// Creates a new [`Migrator`] from a list of migrations.
+    /// The migrations are sorted by version.
+    #[must_use]
+    pub fn new(mut migrations: Vec<Migration>) -> Self {
+        migrations.sort_by_key(Migration::version);
+
+        Self {
+            migrations,
+            dry_run: false,
+        }
+    }
+
+    /// Loads all migrations from a directory at runtime.
+    /// See the [module documentation](self) for the expected file names.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The directory or one of the files could not be read
+    /// - A `.sql` file does not follow the naming scheme
+    /// - A down migration exists without a matching up migration
+    /// - Two migrations share the same version
+    pub fn from_dir(path: impl AsRef<Path>) -> Result<Self, MigrateError> {
+        let mut ups: BTreeMap<i64, (String, String)> = BTreeMap::new();
+        let mut downs: HashMap<i64, String> = HashMap::new();
+
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+
+            if !path.is_file() {
+                continue;
+            }
+
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+
+            let Some(parsed) = parse_file_name(file_name)? else {
+                continue;
+            };
+
+            let sql = std::fs::read_to_string(&path)?;
+
+            match parsed.kind {
+                MigrationKind::Up => {
+                    if ups
+                        .insert(parsed.version, (parsed.description, sql))
+                        .is_some()
+                    {
+                        return Err(MigrateError::DuplicateVersion(parsed.version));
+                    }
+                }
+                MigrationKind::Down => {
+                    if downs.insert(parsed.version, sql).is_some() {
+                        return Err(MigrateError::DuplicateVersion(parsed.version));
+                    }
+                }
+            }
+        }
+
+        if let Some(version) = downs.keys().find(|version| !ups.contains_key(version)) {
+            return Err(MigrateError::MissingUpMigration(*version));
+        }
+
+        let migrations = ups
+            .into_iter()
+            .map(|(version, (description, up))| {
+                let migration = Migration::new(version, description, up);
+
+                match downs.remove(&version) {
+                    Some(down) => migration.with_down(down),
+                    None => migration,
+                }
+            })
+            .collect();
+
+        Ok(Self::new(migrations))
+    }
+
+    /// Enables or disables dry-run mode.
+    /// In dry-run mode [`Migrator::run`] and [`Migrator::undo`] only report what would be
+    /// executed without changing the database (not even creating the migrations table).
+    #[must_use]
+    pub const fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Returns the migrations known to this migrator, ordered by version.
+    #[must_use]
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// Retrieves the migrations that have been applied to the database.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the rqlite server failed
+    /// - The migrations table could not be read
+    pub async fn applied(
+        &self,
+        client: &RqliteClient,
+    ) -> Result<Vec<AppliedMigration>, MigrateError> {
+        if !migrations_table_exists(client).await? {
+            return Ok(vec![]);
+        }
+
+        let query = format!(
+            "SELECT version, description, checksum, applied_at FROM {MIGRATIONS_TABLE} ORDER BY version"
+        );
+
+        let applied = client
+            .fetch(query)
+            .await?
+            .into_typed::<(i64, String, String, i64)>()?
+            .into_iter()
+            .map(
+                |(version, description, checksum, applied_at)| AppliedMigration {
+                    version,
+                    description,
+                    checksum,
+                    applied_at,
+                },
+            )
+            .collect();
+
+        Ok(applied)
+    }
+
+    /// Applies all pending migrations in order of their version.
+    /// Every migration is executed in its own transaction together with its entry in the
+    /// migrations table, so a failing migration leaves no partial changes behind.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - Two migrations share the same version
+    /// - An applied migration is missing or its checksum changed
+    /// - The request to the rqlite server failed
+    /// - A migration failed to execute
+    pub async fn run(&self, client: &RqliteClient) -> Result<MigrationReport, MigrateError> {
+        self.validate()?;
+
+        let applied = self.applied(client).await?;
+        self.verify_applied(&applied)?;
+
+        let pending = self
+            .migrations
+            .iter()
+            .filter(|m| !applied.iter().any(|a| a.version == m.version))
+            .collect::<Vec<_>>();
+
+        let mut report = MigrationReport {
+            dry_run: self.dry_run,
+            migrations: Vec::with_capacity(pending.len()),
+        };
+
+        if !self.dry_run && !pending.is_empty() {
+            ensure_migrations_table(client).await?;
+        }
+
+        for migration in pending {
+            let mut queries = statements(migration.up())
+                .into_iter()
+                .map(statement_query)
+                .collect::<Result<Vec<_>, _>>()?;
+            let statement_count = queries.len();
+
+            if !self.dry_run {
+                let insert = crate::query!(
+                    "INSERT INTO _rqlite_rs_migrations (version, description, checksum, applied_at) VALUES (?, ?, ?, ?)",
+                    migration.version,
+                    migration.description(),
+                    migration.checksum(),
+                    unix_timestamp()
+                )?;
+                queries.push(insert);
+
+                tracing::info!(
+                    "Applying migration {} ({})",
+                    migration.version,
+                    migration.description
+                );
+                execute_migration(client, migration.version, queries).await?;
+            }
+
+            report.migrations.push(MigrationInfo {
+                version: migration.version,
+                description: migration.description.to_string(),
+                statements: statement_count,
+            });
+        }
+
+        Ok(report)
+    }
+
+    /// Reverts all applied migrations with a version greater than `target`, newest first.
+    /// Pass `0` to revert every migration.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - Two migrations share the same version
+    /// - An applied migration is missing or its checksum changed
+    /// - A migration that has to be reverted has no down migration
+    /// - The request to the rqlite server failed
+    /// - A down migration failed to execute
+    pub async fn undo(
+        &self,
+        client: &RqliteClient,
+        target: i64,
+    ) -> Result<MigrationReport, MigrateError> {
+        self.validate()?;
+
+        let applied = self.applied(client).await?;
+        self.verify_applied(&applied)?;
+
+        let mut report = MigrationReport {
+            dry_run: self.dry_run,
+            migrations: vec![],
+        };
+
+        for applied in applied.iter().rev().filter(|a| a.version > target) {
+            let Some(migration) = self
+                .migrations
+                .iter()
+                .find(|m| m.version == applied.version)
+            else {
+                return Err(MigrateError::VersionMissing(applied.version));
+            };
+
+            let down = migration
+                .down()
+                .ok_or(MigrateError::NoDownMigration(migration.version))?;
+
+            let mut queries = statements(down)
+                .into_iter()
+                .map(statement_query)
+                .collect::<Result<Vec<_>, _>>()?;
+            let statement_count = queries.len();
+
+            if !self.dry_run {
+                let delete = crate::query!(
+                    "DELETE FROM _rqlite_rs_migrations WHERE version = ?",
+                    migration.version
+                )?;
+                queries.push(delete);
+
+                tracing::info!(
+                    "Reverting migration {} ({})",
+                    migration.version,
+                    migration.description
+                );
+                execute_migration(client, migration.version, queries).await?;
+            }
+
+            report.migrations.push(MigrationInfo {
+                version: migration.version,
+                description: migration.description.to_string(),
+                statements: statement_count,
+            });
+        }
+
+        Ok(report)
+    }
+
+    fn validate(&self) -> Result<(), MigrateError> {
+        for pair in self.migrations.windows(2) {
+            if let [a, b] = pair {
+                if a.version == b.version {
+                    return Err(MigrateError::DuplicateVersion(a.version));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn verify_applied(&self, applied: &[AppliedMigration]) -> Result<(), MigrateError> {
+        for applied in applied {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|m| m.version == applied.version)
+                .ok_or(MigrateError::VersionMissing(applied.version))?;
+
+            if migration.checksum != applied.checksum {
+                return Err(MigrateError::ChecksumMismatch(applied.version));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn migrations_table_exists(client: &RqliteClient) -> Result<bool, MigrateError> {
+    let query = crate::query!(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?",
+        MIGRATIONS_TABLE
+    )?;
+
+    Ok(!client.fetch(query).await?.is_empty())
+}
+
+async fn ensure_migrations_table(client: &RqliteClient) -> Result<(), MigrateError> {
+    let query = format!(
+        "CREATE TABLE IF NOT EXISTS {MIGRATIONS_TABLE} (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            checksum TEXT NOT NULL,
+            applied_at INTEGER NOT NULL
+        )"
+    );
+
+    client.exec(query).await?;
+
+    Ok(())
+}
+
+async fn execute_migration(
+    client: &RqliteClient,
+    version: i64,
+    queries: Vec<RqliteQuery>,
+) -> Result<(), MigrateError> {
+    let results = client.transaction(queries).await?;
+
+    if let Some(RqliteResult::Error(e)) =
+        results.iter().find(|r| matches!(r, RqliteResult::Error(_)))
+    {
+        return Err(MigrateError::MigrationFailed {
+            version,
+            message: e.error.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+fn statement_query(statement: String) -> Result<RqliteQuery, MigrateError> {
+    let op = Operation::from_query_string(&statement)?;
+
+    Ok(RqliteQuery {
+        query: statement,
+        args: RqliteArguments::default(),
+        op,
+    })
+}
+
+fn unix_timestamp() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+}
+
+fn checksum(sql: &str) -> String {
+    Sha256::digest(sql.as_bytes())
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .filter_map(|nibble| char::from_digit(u32::from(nibble), 16))
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum MigrationKind {
+    Up,
+    Down,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ParsedFileName {
+    version: i64,
+    description: String,
+    kind: MigrationKind,
+}
+
+/// Parses a migration file name of the form `<version>_<description>[.up|.down].sql`.
+/// Returns `Ok(None)` for files that are not `.sql` files.
+fn parse_file_name(file_name: &str) -> Result<Option<ParsedFileName>, MigrateError> {
+    let Some(stem) = file_name.strip_suffix(".sql") else {
+        return Ok(None);
+    };
+
+    let (stem, kind) = stem.strip_suffix(".down").map_or_else(
+        || (stem.strip_suffix(".up").unwrap_or(stem), MigrationKind::Up),
+        |stem| (stem, MigrationKind::Down),
+    );
+
+    let invalid = || MigrateError::InvalidFileName(file_name.to_string());
+
+    let (version, description) = stem.split_once('_').ok_or_else(invalid)?;
+    let version = version.parse::<i64>().map_err(|_parse_error| invalid())?;
+
+    Ok(Some(ParsedFileName {
+        version,
+        description: description.replace('_', " "),
+        kind,
+    }))
+}
+
+/// Splits SQL text into its individual statements.
+///
+/// Semicolons inside string literals, quoted identifiers, comments and
+/// `CREATE TRIGGER ... BEGIN ... END` bodies do not end a statement.
+/// Leading comments are dropped and statements that only consist of comments are skipped.
+pub(crate) fn statements(sql: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut span: Option<(usize, usize)> = None;
+    let mut is_create = false;
+    let mut is_trigger = false;
+    let mut block_depth = 0usize;
+
+    for token in lexer::tokenize(sql) {
+        if token.kind == TokenKind::Semicolon && block_depth == 0 {
+            if let Some(statement) = span.take().and_then(|(start, end)| sql.get(start..end)) {
+                statements.push(statement.to_string());
+            }
+            is_create = false;
+            is_trigger = false;
+            continue;
+        }
+
+        if let Some((_, end)) = &mut span {
+            *end = token.end;
+        } else {
+            span = Some((token.start, token.end));
+            is_create = token.is_keyword("create");
+        }
+
+        if is_create && token.is_keyword("trigger") {
+            is_trigger = true;
+        } else if is_trigger && (token.is_keyword("begin") || token.is_keyword("case")) {
+            block_depth += 1;
+        } else if is_trigger && token.is_keyword("end") {
+            block_depth = block_depth.saturating_sub(1);
+        }
+    }
+
+    if let Some(statement) = span.and_then(|(start, end)| sql.get(start..end)) {
+        statements.push(statement.to_string());
+    }
+
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_migration_checksum() {
+        let migration = Migration::new(1, "create foo", "CREATE TABLE foo (id INTEGER)");
+
+        assert_eq!(migration.checksum().len(), 64);
+        assert_eq!(
+            migration.checksum(),
+            Migration::new(1, "other", "CREATE TABLE foo (id INTEGER)").checksum()
+        );
+        assert_ne!(
+            migration.checksum(),
+            Migration::new(1, "create foo", "CREATE TABLE foo (id TEXT)").checksum()
+        );
+    }
+
+    #[test]
+    fn unit_migrator_sorts_migrations() {
+        let migrator = Migrator::new(vec![
+            Migration::new(3, "c", "DROP TABLE c"),
+            Migration::new(1, "a", "DROP TABLE a"),
+            Migration::new(2, "b", "DROP TABLE b"),
+        ]);
+
+        let versions = migrator
+            .migrations()
+            .iter()
+            .map(Migration::version)
+            .collect::<Vec<_>>();
+
+        assert_eq!(versions, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn unit_migrator_duplicate_version() {
+        let migrator = Migrator::new(vec![
+            Migration::new(1, "a", "DROP TABLE a"),
+            Migration::new(1, "b", "DROP TABLE b"),
+        ]);
+
+        assert!(matches!(
+            migrator.validate(),
+            Err(MigrateError::DuplicateVersion(1))
+        ));
+    }
+
+    #[test]
+    fn unit_migrator_verify_applied() {
+        let migration = Migration::new(1, "a", "CREATE TABLE a (id INTEGER)");
+        let applied = AppliedMigration {
+            version: 1,
+            description: "a".to_string(),
+            checksum: migration.checksum().to_string(),
+            applied_at: 0,
+        };
+        let migrator = Migrator::new(vec![migration]);
+
+        assert!(migrator
+            .verify_applied(std::slice::from_ref(&applied))
+            .is_ok());
+
+        let changed = AppliedMigration {
+            checksum: "changed".to_string(),
+            ..applied.clone()
+        };
+        assert!(matches!(
+            migrator.verify_applied(&[changed]),
+            Err(MigrateError::ChecksumMismatch(1))
+        ));
+
+        let missing = AppliedMigration {
+            version: 2,
+            ..applied
+        };
+        assert!(matches!(
+            migrator.verify_applied(&[missing]),
+            Err(MigrateError::VersionMissing(2))
+        ));
+    }
+
+    #[test]
+    fn unit_parse_file_name() {
+        assert_eq!(
+            parse_file_name("1_create_users.sql").unwrap(),
+            Some(ParsedFileName {
+                version: 1,
+                description: "create users".to_string(),
+                kind: MigrationKind::Up,
+            })
+        );
+        assert_eq!(
+            parse_file_name("20240101_add_index.up.sql").unwrap(),
+            Some(ParsedFileName {
+                version: 20_240_101,
+                description: "add index".to_string(),
+                kind: MigrationKind::Up,
+            })
+        );
+        assert_eq!(
+            parse_file_name("2_add_index.down.sql").unwrap(),
+            Some(ParsedFileName {
+                version: 2,
+                description: "add index".to_string(),
+                kind: MigrationKind::Down,
+            })
+        );
+        assert_eq!(parse_file_name("README.md").unwrap(), None);
+        assert!(matches!(
+            parse_file_name("create_users.sql"),
+            Err(MigrateError::InvalidFileName(_))
+        ));
+        assert!(matches!(
+            parse_file_name("1.sql"),
+            Err(MigrateError::InvalidFileName(_))
+        ));
+    }
+
+    #[test]
+    fn unit_migrator_from_dir() {
+        let dir = std::env::temp_dir().join(format!("rqlite-rs-migrate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("2_add_index.up.sql"),
+            "CREATE INDEX foo_id ON foo (id);",
+        )
+        .unwrap();
+        std::fs::write(dir.join("2_add_index.down.sql"), "DROP INDEX foo_id;").unwrap();
+        std::fs::write(
+            dir.join("1_create_foo.sql"),
+            "CREATE TABLE foo (id INTEGER);",
+        )
+        .unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let migrator = Migrator::from_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let migrations = migrator.migrations();
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(migrations[0].version(), 1);
+        assert_eq!(migrations[0].description(), "create foo");
+        assert_eq!(migrations[0].down(), None);
+        assert_eq!(migrations[1].version(), 2);
+        assert_eq!(migrations[1].down(), Some("DROP INDEX foo_id;"));
+    }
+
+    #[test]
+    fn unit_statements() {
+        let sql = "
+            -- create the table
+            CREATE TABLE foo (id INTEGER, name TEXT DEFAULT 'a;b');
+            /* seed; data */
+            INSERT INTO foo (id, name) VALUES (1, 'it''s; fine');
+            ;
+            -- trailing comment
+        ";
+
+        assert_eq!(
+            statements(sql),
+            vec![
+                "CREATE TABLE foo (id INTEGER, name TEXT DEFAULT 'a;b')".to_string(),
+                "INSERT INTO foo (id, name) VALUES (1, 'it''s; fine')".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn unit_statements_trigger() {
+        let sql = "
+            CREATE TRIGGER foo_audit AFTER UPDATE ON foo BEGIN
+                INSERT INTO audit (kind) VALUES (CASE WHEN new.id > 1 THEN 'a' ELSE 'b' END);
+                DELETE FROM bar;
+            END;
+            DROP TABLE baz;
+        ";
+
+        let statements = statements(sql);
+
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].starts_with("CREATE TRIGGER"));
+        assert!(statements[0].ends_with("END"));
+        assert_eq!(statements[1], "DROP TABLE baz");
+    }
+}
@@ -1,3 +1,4 @@
+use rqlite_rs_core::SqlValue;
 use serde::Serialize;
 
 #[derive(Debug, PartialEq, Serialize, Clone)]
@@ -14,6 +15,18 @@ pub enum RqliteArgument {
 
 impl RqliteArgument {}
 
+impl From<SqlValue> for RqliteArgument {
+    fn from(value: SqlValue) -> Self {
+        match value {
+            SqlValue::Null => Self::Null,
+            SqlValue::Integer(i) => Self::I64(i),
+            SqlValue::Real(f) => Self::F64(f),
+            SqlValue::Text(s) => Self::String(s),
+            SqlValue::Blob(b) => Self::Blob(b),
+        }
+    }
+}
+
 pub trait RqliteArgumentRaw {
     fn encode(&self) -> RqliteArgument;
 }
@@ -25,6 +38,20 @@ impl RqliteArgumentRaw for i32 {
     }
 }
 
+macro_rules! impl_lossless_integer {
+    ($($t:ty),+) => {
+        $(
+            impl RqliteArgumentRaw for $t {
+                fn encode(&self) -> RqliteArgument {
+                    RqliteArgument::I64(i64::from(*self))
+                }
+            }
+        )+
+    };
+}
+
+impl_lossless_integer!(i8, i16, u8, u16, u32);
+
 impl RqliteArgumentRaw for i64 {
     fn encode(&self) -> RqliteArgument {
         RqliteArgument::I64(self.to_owned())
@@ -93,6 +120,106 @@ where
     }
 }
 
+impl RqliteArgumentRaw for SqlValue {
+    fn encode(&self) -> RqliteArgument {
+        RqliteArgument::from(self.clone())
+    }
+}
+
+/// Implements `RqliteArgumentRaw` for types that are stored using their conversion into a
+/// [`SqlValue`]. See [`rqlite_rs_core::types`] for the formats.
+#[cfg(any(
+    feature = "chrono",
+    feature = "time",
+    feature = "uuid",
+    feature = "rust_decimal"
+))]
+macro_rules! impl_via_sql_value {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl RqliteArgumentRaw for $ty {
+                fn encode(&self) -> RqliteArgument {
+                    RqliteArgument::from(SqlValue::from(*self))
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+    use rqlite_rs_core::types::{Iso8601, UnixTimestamp};
+
+    use super::{RqliteArgument, RqliteArgumentRaw, SqlValue};
+
+    impl_via_sql_value!(
+        DateTime<Utc>,
+        DateTime<FixedOffset>,
+        NaiveDateTime,
+        NaiveDate,
+        NaiveTime,
+        Iso8601<DateTime<Utc>>,
+        Iso8601<DateTime<FixedOffset>>,
+        Iso8601<NaiveDateTime>,
+        Iso8601<NaiveDate>,
+        Iso8601<NaiveTime>,
+        UnixTimestamp<DateTime<Utc>>,
+        UnixTimestamp<NaiveDateTime>,
+    );
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use rqlite_rs_core::types::{Iso8601, UnixTimestamp};
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+    use super::{RqliteArgument, RqliteArgumentRaw, SqlValue};
+
+    impl_via_sql_value!(
+        OffsetDateTime,
+        PrimitiveDateTime,
+        Date,
+        Time,
+        Iso8601<OffsetDateTime>,
+        Iso8601<PrimitiveDateTime>,
+        Iso8601<Date>,
+        Iso8601<Time>,
+        UnixTimestamp<OffsetDateTime>,
+        UnixTimestamp<PrimitiveDateTime>,
+    );
+}
+
+#[cfg(feature = "uuid")]
+mod uuid_impls {
+    use rqlite_rs_core::types::UuidBlob;
+    use uuid::Uuid;
+
+    use super::{RqliteArgument, RqliteArgumentRaw, SqlValue};
+
+    impl_via_sql_value!(Uuid, UuidBlob);
+}
+
+#[cfg(feature = "rust_decimal")]
+mod decimal_impls {
+    use rust_decimal::Decimal;
+
+    use super::{RqliteArgument, RqliteArgumentRaw, SqlValue};
+
+    impl_via_sql_value!(Decimal);
+}
+
+#[cfg(feature = "serde_json")]
+mod json_impls {
+    use super::{RqliteArgument, RqliteArgumentRaw, SqlValue};
+
+    impl RqliteArgumentRaw for serde_json::Value {
+        fn encode(&self) -> RqliteArgument {
+            RqliteArgument::from(SqlValue::from(self.clone()))
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! arg {
     ($e:expr) => {
@@ -112,6 +239,12 @@ mod tests {
         let arg = arg!(1i64);
         assert_eq!(arg, RqliteArgument::I64(1));
 
+        let arg = arg!(1u8);
+        assert_eq!(arg, RqliteArgument::I64(1));
+
+        let arg = arg!(u32::MAX);
+        assert_eq!(arg, RqliteArgument::I64(i64::from(u32::MAX)));
+
         let arg = arg!(1.0);
         assert_eq!(arg, RqliteArgument::F64(1.0));
 
@@ -139,4 +272,92 @@ mod tests {
         let arg = arg!(&[1u8, 2, 3][..]);
         assert_eq!(arg, RqliteArgument::Blob(vec![1, 2, 3]));
     }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn unit_rqlite_argument_chrono() {
+        use rqlite_rs_core::types::UnixTimestamp;
+
+        let t = chrono::DateTime::from_timestamp(1_706_704_200, 0).unwrap();
+
+        assert_eq!(
+            arg!(t),
+            RqliteArgument::String("2024-01-31T12:30:00Z".to_string())
+        );
+        assert_eq!(arg!(UnixTimestamp(t)), RqliteArgument::I64(1_706_704_200));
+        assert_eq!(
+            arg!(Some(t.date_naive())),
+            RqliteArgument::String("2024-01-31".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn unit_rqlite_argument_time() {
+        use rqlite_rs_core::types::UnixTimestamp;
+
+        let t = time::OffsetDateTime::from_unix_timestamp(1_706_704_200).unwrap();
+
+        assert_eq!(
+            arg!(t),
+            RqliteArgument::String("2024-01-31T12:30:00Z".to_string())
+        );
+        assert_eq!(arg!(UnixTimestamp(t)), RqliteArgument::I64(1_706_704_200));
+    }
+
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn unit_rqlite_argument_uuid() {
+        use rqlite_rs_core::types::UuidBlob;
+
+        let uuid = uuid::Uuid::from_u128(1);
+
+        assert_eq!(
+            arg!(uuid),
+            RqliteArgument::String("00000000-0000-0000-0000-000000000001".to_string())
+        );
+        assert_eq!(
+            arg!(UuidBlob(uuid)),
+            RqliteArgument::Blob(uuid.as_bytes().to_vec())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn unit_rqlite_argument_json() {
+        use rqlite_rs_core::types::Json;
+
+        assert_eq!(
+            arg!(serde_json::json!({"a": 1})),
+            RqliteArgument::String(r#"{"a":1}"#.to_string())
+        );
+        assert_eq!(
+            arg!(Json(vec![1, 2]).to_sql_value().unwrap()),
+            RqliteArgument::String("[1,2]".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rust_decimal")]
+    fn unit_rqlite_argument_decimal() {
+        assert_eq!(
+            arg!(rust_decimal::Decimal::new(15, 1)),
+            RqliteArgument::String("1.5".to_string())
+        );
+    }
+
+    #[test]
+    fn unit_rqlite_argument_from_sql_value() {
+        assert_eq!(arg!(SqlValue::Null), RqliteArgument::Null);
+        assert_eq!(arg!(SqlValue::Integer(1)), RqliteArgument::I64(1));
+        assert_eq!(arg!(SqlValue::Real(1.5)), RqliteArgument::F64(1.5));
+        assert_eq!(
+            arg!(SqlValue::Text("hello".to_string())),
+            RqliteArgument::String("hello".to_string())
+        );
+        assert_eq!(
+            RqliteArgument::from(SqlValue::Blob(vec![1, 2, 3])),
+            RqliteArgument::Blob(vec![1, 2, 3])
+        );
+    }
 }
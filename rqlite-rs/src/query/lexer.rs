@@ -0,0 +1,210 @@
+//! A small SQL lexer.
+//!
+//! It is not a full `SQLite` tokenizer. It knows enough to skip whitespace and comments and to tell
+//! string literals and quoted identifiers apart from keywords, parameters and statement
+//! separators.
+
+/// A bound parameter placeholder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Parameter {
+    /// `?`
+    Anonymous,
+    /// `?NNN`
+    Numbered(usize),
+    /// `:name`, `@name` or `$name`, stored without its prefix.
+    Named(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A keyword or an unquoted identifier.
+    Word,
+    /// A string literal (`'...'`).
+    String,
+    /// A quoted identifier (`"..."`, `` `...` `` or `[...]`).
+    QuotedIdentifier,
+    /// A numeric literal.
+    Number,
+    /// A parameter placeholder.
+    Parameter(Parameter),
+    /// The statement separator `;`.
+    Semicolon,
+    /// Any other character, such as operators and parentheses.
+    Punctuation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// Byte offset of the first character of the token.
+    pub start: usize,
+    /// Byte offset after the last character of the token.
+    pub end: usize,
+}
+
+impl Token<'_> {
+    /// Returns `true` if the token is the given keyword (case insensitive).
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        self.kind == TokenKind::Word && self.text.eq_ignore_ascii_case(keyword)
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Splits SQL text into tokens, skipping whitespace and comments.
+/// Unterminated literals and comments extend to the end of the input.
+pub fn tokenize(sql: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '-' if chars.peek().is_some_and(|(_, c)| *c == '-') => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            '/' if chars.peek().is_some_and(|(_, c)| *c == '*') => {
+                chars.next();
+                let mut previous = '\0';
+                for (_, c) in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                continue;
+            }
+            '\'' | '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+                while let Some((_, c)) = chars.next() {
+                    if c == close {
+                        // Doubled quotes are escapes, brackets can not be escaped
+                        if close != ']' && chars.peek().is_some_and(|(_, c)| *c == close) {
+                            chars.next();
+                            continue;
+                        }
+                        break;
+                    }
+                }
+                if c == '\'' {
+                    TokenKind::String
+                } else {
+                    TokenKind::QuotedIdentifier
+                }
+            }
+            '?' => {
+                let mut number = String::new();
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    number.push(c);
+                }
+                TokenKind::Parameter(
+                    number
+                        .parse()
+                        .map_or(Parameter::Anonymous, Parameter::Numbered),
+                )
+            }
+            ':' | '@' | '$' if chars.peek().is_some_and(|(_, c)| is_word_char(*c)) => {
+                let mut name = String::new();
+                while let Some((_, c)) = chars.next_if(|(_, c)| is_word_char(*c)) {
+                    name.push(c);
+                }
+                TokenKind::Parameter(Parameter::Named(name))
+            }
+            ';' => TokenKind::Semicolon,
+            c if c.is_ascii_digit() => {
+                while chars
+                    .next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '.')
+                    .is_some()
+                {}
+                TokenKind::Number
+            }
+            c if is_word_char(c) => {
+                while chars.next_if(|(_, c)| is_word_char(*c)).is_some() {}
+                TokenKind::Word
+            }
+            _ => TokenKind::Punctuation,
+        };
+
+        let end = chars.peek().map_or(sql.len(), |(i, _)| *i);
+
+        tokens.push(Token {
+            kind,
+            text: sql.get(start..end).unwrap_or_default(),
+            start,
+            end,
+        });
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(sql: &str) -> Vec<TokenKind> {
+        tokenize(sql).into_iter().map(|t| t.kind).collect()
+    }
+
+    #[test]
+    fn unit_lexer_skips_whitespace_and_comments() {
+        let tokens = tokenize("  -- comment\n /* block; */ SELECT 1 -- trailing");
+
+        assert_eq!(tokens.len(), 2);
+        assert!(tokens[0].is_keyword("select"));
+        assert_eq!(tokens[1].kind, TokenKind::Number);
+        assert_eq!(tokens[1].text, "1");
+    }
+
+    #[test]
+    fn unit_lexer_literals() {
+        assert_eq!(
+            kinds(r#"'it''s; :not_a_param' "quoted "" ident" `tick` [bracket]"#),
+            vec![
+                TokenKind::String,
+                TokenKind::QuotedIdentifier,
+                TokenKind::QuotedIdentifier,
+                TokenKind::QuotedIdentifier,
+            ]
+        );
+    }
+
+    #[test]
+    fn unit_lexer_parameters() {
+        assert_eq!(
+            kinds("? ?2 :name @other $third"),
+            vec![
+                TokenKind::Parameter(Parameter::Anonymous),
+                TokenKind::Parameter(Parameter::Numbered(2)),
+                TokenKind::Parameter(Parameter::Named("name".to_string())),
+                TokenKind::Parameter(Parameter::Named("other".to_string())),
+                TokenKind::Parameter(Parameter::Named("third".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn unit_lexer_spans() {
+        let sql = "INSERT INTO foo VALUES (:a);";
+        let tokens = tokenize(sql);
+
+        let last = tokens.last().unwrap();
+        assert_eq!(last.kind, TokenKind::Semicolon);
+        assert_eq!(last.start, sql.len() - 1);
+        assert_eq!(last.end, sql.len());
+
+        let param = tokens.iter().find(|t| t.text == ":a").unwrap();
+        assert_eq!(
+            param.kind,
+            TokenKind::Parameter(Parameter::Named("a".to_string()))
+        );
+    }
+}
@@ -1,18 +1,71 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use serde::Serialize;
 use serde_json;
 
 pub mod arguments;
+pub(crate) mod lexer;
+mod to_args;
 use crate::error::QueryBuilderError;
 pub(crate) use arguments::RqliteArgument;
+use lexer::{Parameter, TokenKind};
+pub use to_args::ToArgs;
 
 /// A query to be executed on the rqlite cluster.
 #[derive(Debug)]
 pub struct RqliteQuery {
     pub query: String,
-    pub args: Vec<RqliteArgument>,
+    pub args: RqliteArguments,
     pub op: Operation,
 }
 
+/// The arguments bound to a query.
+///
+/// rqlite binds positional arguments to `?` placeholders in order and named arguments to
+/// `:name`, `@name` or `$name` placeholders. A single query can only use one of both forms.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RqliteArguments {
+    /// Arguments bound by position.
+    Positional(Vec<RqliteArgument>),
+    /// Arguments bound by name. The names are stored without their prefix.
+    Named(BTreeMap<String, RqliteArgument>),
+}
+
+impl Default for RqliteArguments {
+    fn default() -> Self {
+        Self::Positional(vec![])
+    }
+}
+
+impl RqliteArguments {
+    /// Returns the number of arguments.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Positional(args) => args.len(),
+            Self::Named(args) => args.len(),
+        }
+    }
+
+    /// Returns `true` if there are no arguments.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl From<Vec<RqliteArgument>> for RqliteArguments {
+    fn from(args: Vec<RqliteArgument>) -> Self {
+        Self::Positional(args)
+    }
+}
+
+impl From<BTreeMap<String, RqliteArgument>> for RqliteArguments {
+    fn from(args: BTreeMap<String, RqliteArgument>) -> Self {
+        Self::Named(args)
+    }
+}
+
 impl TryInto<RqliteQuery> for String {
     type Error = QueryBuilderError;
 
@@ -25,7 +78,7 @@ impl TryInto<RqliteQuery> for String {
 
         Ok(RqliteQuery {
             query: self,
-            args: vec![],
+            args: RqliteArguments::default(),
             op,
         })
     }
@@ -43,7 +96,7 @@ impl TryInto<RqliteQuery> for &str {
 
         Ok(RqliteQuery {
             query: self.to_string(),
-            args: vec![],
+            args: RqliteArguments::default(),
             op,
         })
     }
@@ -62,7 +115,26 @@ impl TryInto<RqliteQuery> for Result<RqliteQuery, QueryBuilderError> {
 }
 
 #[derive(Serialize, Debug)]
-struct QueryComponent(RqliteArgument);
+#[serde(untagged)]
+enum QueryComponent {
+    Argument(RqliteArgument),
+    Named(BTreeMap<String, RqliteArgument>),
+}
+
+impl QueryComponent {
+    fn from_query(query: RqliteQuery) -> Vec<Self> {
+        let mut components = vec![Self::Argument(RqliteArgument::String(query.query))];
+
+        match query.args {
+            RqliteArguments::Positional(args) => {
+                components.extend(args.into_iter().map(Self::Argument));
+            }
+            RqliteArguments::Named(args) => components.push(Self::Named(args)),
+        }
+
+        components
+    }
+}
 
 // This is a helper struct for serializing multiple queries with arguments.
 #[derive(Serialize, Debug)]
@@ -72,19 +144,7 @@ pub(crate) struct QueryArgs(Vec<Vec<QueryComponent>>);
 // This is used for batch queries.
 impl From<RqliteQuery> for QueryArgs {
     fn from(query: RqliteQuery) -> Self {
-        let mut args = Vec::new();
-
-        let mut components = Vec::new();
-
-        components.push(QueryComponent(RqliteArgument::String(query.query)));
-
-        for arg in query.args {
-            components.push(QueryComponent(arg));
-        }
-
-        args.push(components);
-
-        Self(args)
+        Self(vec![QueryComponent::from_query(query)])
     }
 }
 
@@ -92,80 +152,279 @@ impl From<RqliteQuery> for QueryArgs {
 // This is used for batch queries.
 impl From<Vec<RqliteQuery>> for QueryArgs {
     fn from(queries: Vec<RqliteQuery>) -> Self {
-        let mut args = Vec::new();
-
-        for query in queries {
-            let mut components = Vec::new();
-
-            components.push(QueryComponent(RqliteArgument::String(query.query)));
+        Self(
+            queries
+                .into_iter()
+                .map(QueryComponent::from_query)
+                .collect(),
+        )
+    }
+}
 
-            for arg in query.args {
-                components.push(QueryComponent(arg));
+impl RqliteQuery {
+    /// Checks that the arguments match the placeholders in the query.
+    ///
+    /// Positional arguments have to match the number of parameters in the query.
+    /// Named arguments need a binding for every `:name`, `@name` or `$name` placeholder,
+    /// every binding has to be used and `?` placeholders are not allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first mismatch between arguments and placeholders.
+    pub fn validate(&self) -> Result<(), QueryBuilderError> {
+        let placeholders = Placeholders::parse(&self.query);
+
+        match &self.args {
+            RqliteArguments::Positional(args) => {
+                let expected = placeholders.count();
+
+                if args.len() != expected {
+                    return Err(QueryBuilderError::InvalidArgumentCount(
+                        expected,
+                        args.len(),
+                    ));
+                }
+            }
+            RqliteArguments::Named(args) => {
+                if placeholders.positional {
+                    return Err(QueryBuilderError::MixedArguments);
+                }
+
+                if let Some(name) = placeholders.named.iter().find(|n| !args.contains_key(*n)) {
+                    return Err(QueryBuilderError::MissingNamedArgument(name.clone()));
+                }
+
+                if let Some(name) = args.keys().find(|n| !placeholders.named.contains(*n)) {
+                    return Err(QueryBuilderError::UnknownNamedArgument(name.clone()));
+                }
             }
-
-            args.push(components);
         }
 
-        Self(args)
+        Ok(())
     }
-}
 
-impl RqliteQuery {
     pub(crate) fn into_json(self) -> Result<String, serde_json::Error> {
         let args = QueryArgs::from(self);
 
         serde_json::to_string(&args)
     }
 
+    /// Returns `true` if the query returns rows.
+    /// This is the case for reads and for writes with a `RETURNING` clause.
+    #[must_use]
+    pub fn returns_rows(&self) -> bool {
+        self.op.is_read()
+            || (matches!(
+                self.op,
+                Operation::Insert | Operation::Update | Operation::Delete
+            ) && has_returning_clause(&self.query))
+    }
+
+    /// Returns the endpoint for the query.
     pub(crate) fn endpoint(&self) -> String {
-        let resource = match self.op {
-            Operation::Create
-            | Operation::Update
-            | Operation::Delete
-            | Operation::Insert
-            | Operation::Drop => "execute",
-            Operation::Select | Operation::Pragma => "query",
+        let resource = if self.op.is_read() {
+            "query"
+        } else {
+            "execute"
         };
 
         format!("db/{resource}")
     }
+
+    /// Returns the endpoint for a query whose rows are fetched.
+    /// Writes with a `RETURNING` clause are sent to the unified endpoint, as only it returns
+    /// their rows.
+    pub(crate) fn fetch_endpoint(&self) -> String {
+        if !self.op.is_read() && self.returns_rows() {
+            "db/request".to_string()
+        } else {
+            self.endpoint()
+        }
+    }
+}
+
+/// The parameter placeholders found in a query.
+struct Placeholders {
+    /// The highest parameter index, following `SQLite`'s numbering rules.
+    count: usize,
+    /// If the query contains `?` or `?NNN` placeholders.
+    positional: bool,
+    /// The names of all named placeholders, without their prefix.
+    named: BTreeSet<String>,
+}
+
+impl Placeholders {
+    fn parse(query: &str) -> Self {
+        let mut placeholders = Self {
+            count: 0,
+            positional: false,
+            named: BTreeSet::new(),
+        };
+
+        for token in lexer::tokenize(query) {
+            let TokenKind::Parameter(parameter) = token.kind else {
+                continue;
+            };
+
+            match parameter {
+                Parameter::Anonymous => {
+                    placeholders.positional = true;
+                    placeholders.count += 1;
+                }
+                Parameter::Numbered(index) => {
+                    placeholders.positional = true;
+                    placeholders.count = placeholders.count.max(index);
+                }
+                Parameter::Named(name) => {
+                    // Named parameters get the next free index the first time they appear
+                    if placeholders.named.insert(name) {
+                        placeholders.count += 1;
+                    }
+                }
+            }
+        }
+
+        placeholders
+    }
+
+    const fn count(&self) -> usize {
+        self.count
+    }
 }
 
 /// The type of operation for a query.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Operation {
     Create,
+    /// `SELECT` and `VALUES` statements.
     Select,
     Update,
     Delete,
+    /// `INSERT` and `REPLACE` statements.
     Insert,
     Pragma,
     Drop,
+    Alter,
+    Explain,
+    Vacuum,
+    Analyze,
+    Reindex,
+    Attach,
+    Detach,
+    /// `BEGIN`, `COMMIT`, `END`, `ROLLBACK`, `SAVEPOINT` and `RELEASE` statements.
+    Transaction,
 }
 
 impl Operation {
+    /// Returns `true` if the operation returns rows instead of modifying the database.
+    #[must_use]
+    pub const fn is_read(&self) -> bool {
+        matches!(self, Self::Select | Self::Pragma | Self::Explain)
+    }
+
+    /// Returns the operation that represents a group of queries sent in a single request.
+    /// This is the first write operation, or the first operation if all queries are reads.
+    pub(crate) fn of_queries(queries: &[RqliteQuery]) -> Option<Self> {
+        queries
+            .iter()
+            .map(|q| q.op)
+            .find(|op| !op.is_read())
+            .or_else(|| queries.first().map(|q| q.op))
+    }
+
     /// Convert a SQL query string into an [`Operation`].
     ///
+    /// Leading whitespace and comments are skipped.
+    /// Common table expressions (`WITH ...`) are classified by the statement following them,
+    /// so a CTE followed by an `INSERT` is an [`Operation::Insert`].
+    ///
     /// # Errors
     /// Returns [`QueryBuilderError::InvalidOperation`] if the query string does not start with a valid operation keyword.
     pub fn from_query_string(query: &str) -> Result<Self, QueryBuilderError> {
-        match query.to_lowercase() {
-            q if q.starts_with("create") => Ok(Self::Create),
-            q if q.starts_with("select") => Ok(Self::Select),
-            q if q.starts_with("update") => Ok(Self::Update),
-            q if q.starts_with("delete") => Ok(Self::Delete),
-            q if q.starts_with("insert") => Ok(Self::Insert),
-            q if q.starts_with("pragma") => Ok(Self::Pragma),
-            q if q.starts_with("drop") => Ok(Self::Drop),
-            _ => Err(QueryBuilderError::InvalidOperation(query.to_string())),
+        let tokens = lexer::tokenize(query);
+        let invalid = || QueryBuilderError::InvalidOperation(query.to_string());
+
+        let first = tokens.first().ok_or_else(invalid)?;
+
+        let keyword = if first.is_keyword("with") {
+            Self::cte_statement(&tokens).ok_or_else(invalid)?
+        } else {
+            first
+        };
+
+        Self::from_keyword(keyword.text).ok_or_else(invalid)
+    }
+
+    /// Returns the first keyword after the common table expressions of a `WITH` statement.
+    fn cte_statement<'a, 'b>(tokens: &'b [lexer::Token<'a>]) -> Option<&'b lexer::Token<'a>> {
+        let mut depth = 0usize;
+
+        // Skip the `WITH` keyword
+        for token in tokens.get(1..)? {
+            match token.text {
+                "(" => depth += 1,
+                ")" => depth = depth.saturating_sub(1),
+                _ if depth == 0
+                    && ["select", "values", "insert", "replace", "update", "delete"]
+                        .iter()
+                        .any(|keyword| token.is_keyword(keyword)) =>
+                {
+                    return Some(token);
+                }
+                _ => {}
+            }
         }
+
+        None
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        let op = match keyword.to_lowercase().as_str() {
+            "create" => Self::Create,
+            "select" | "values" => Self::Select,
+            "update" => Self::Update,
+            "delete" => Self::Delete,
+            "insert" | "replace" => Self::Insert,
+            "pragma" => Self::Pragma,
+            "drop" => Self::Drop,
+            "alter" => Self::Alter,
+            "explain" => Self::Explain,
+            "vacuum" => Self::Vacuum,
+            "analyze" => Self::Analyze,
+            "reindex" => Self::Reindex,
+            "attach" => Self::Attach,
+            "detach" => Self::Detach,
+            "begin" | "commit" | "end" | "rollback" | "savepoint" | "release" => Self::Transaction,
+            _ => return None,
+        };
+
+        Some(op)
     }
 }
 
+/// Returns `true` if the statement has a `RETURNING` clause.
+fn has_returning_clause(query: &str) -> bool {
+    let mut depth = 0usize;
+
+    lexer::tokenize(query).iter().any(|token| {
+        match token.text {
+            "(" => depth += 1,
+            ")" => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+
+        depth == 0 && token.is_keyword("returning")
+    })
+}
+
 /// A macro for creating a query.
 /// Returns a `Result` with an [`RqliteQuery`] if the query is valid.
 /// The macro accepts a query string and optional arguments.
 ///
+/// Arguments are either positional or named (`name = value`), but can not be mixed.
+/// The arguments are checked against the placeholders of the query, see [`RqliteQuery::validate`].
+///
 /// # Examples
 ///
 /// ```
@@ -176,6 +435,9 @@ impl Operation {
 ///
 /// let query = query!("SELECT * FROM foo WHERE id = ? AND name = ?", 1i64, "bar");
 /// assert!(query.is_ok());
+///
+/// let query = query!("INSERT INTO foo (name, age) VALUES (:name, :age)", name = "bar", age = 3);
+/// assert!(query.is_ok());
 /// ```
 #[macro_export]
 macro_rules! query {
@@ -187,18 +449,45 @@ macro_rules! query {
 
             Ok($crate::query::RqliteQuery {
                 query: $query.to_string(),
-                args: vec![],
+                args: $crate::query::RqliteArguments::default(),
                 op,
             })
         }
     }};
-    ( $query:expr, $( $args:expr ),* ) => {{
+    ( $query:expr, $( $name:ident = $value:expr ),+ $(,)? ) => {{
         'blk: {
             let Ok(query) = ($crate::query!($query)) else {
                 break 'blk Err($crate::error::QueryBuilderError::InvalidQuery($query.to_string()));
             };
 
-            let param_count = $query.matches("?").count();
+            let mut args = ::std::collections::BTreeMap::new();
+
+            $(
+                let name = stringify!($name).to_string();
+                if args.contains_key(&name) {
+                    break 'blk Err($crate::error::QueryBuilderError::DuplicateNamedArgument(name));
+                }
+                args.insert(name, $crate::arg!($value));
+            )+
+
+            let query = $crate::query::RqliteQuery {
+                query: query.query,
+                args: $crate::query::RqliteArguments::Named(args),
+                op: query.op,
+            };
+
+            if let Err(e) = query.validate() {
+                break 'blk Err(e);
+            }
+
+            Ok(query)
+        }
+    }};
+    ( $query:expr, $( $args:expr ),* ) => {{
+        'blk: {
+            let Ok(query) = ($crate::query!($query)) else {
+                break 'blk Err($crate::error::QueryBuilderError::InvalidQuery($query.to_string()));
+            };
 
             let mut args = vec![];
 
@@ -207,17 +496,17 @@ macro_rules! query {
                 args.push(arg);
             )*
 
-            let argc = args.len();
+            let query = $crate::query::RqliteQuery {
+                query: query.query,
+                args: $crate::query::RqliteArguments::Positional(args),
+                op: query.op,
+            };
 
-            if argc != param_count {
-                break 'blk Err($crate::error::QueryBuilderError::InvalidArgumentCount(param_count, argc));
+            if let Err(e) = query.validate() {
+                break 'blk Err(e);
             }
 
-            Ok($crate::query::RqliteQuery {
-                query: query.query,
-                args,
-                op: query.op,
-            })
+            Ok(query)
         }
     }};
 }
@@ -335,6 +624,104 @@ mod tests {
         assert_eq!(json.unwrap(), r#"[["SELECT * FROM foo WHERE id = ?",1]]"#);
     }
 
+    #[test]
+    fn unit_query_macro_named_args() {
+        let query = query!(
+            "INSERT INTO foo (name, age) VALUES (:name, @age)",
+            name = "bar",
+            age = 3i64,
+        );
+        assert!(query.is_ok());
+
+        let query = query.unwrap();
+        assert_eq!(query.args.len(), 2);
+
+        let json = query.into_json().unwrap();
+        assert_eq!(
+            json,
+            r#"[["INSERT INTO foo (name, age) VALUES (:name, @age)",{"age":3,"name":"bar"}]]"#
+        );
+    }
+
+    #[test]
+    fn unit_query_macro_named_args_validation() {
+        let query = query!(
+            "SELECT * FROM foo WHERE name = :name AND age = $age",
+            name = "bar"
+        );
+        assert!(matches!(
+            query.unwrap_err(),
+            crate::error::QueryBuilderError::MissingNamedArgument(name) if name == "age"
+        ));
+
+        let query = query!(
+            "SELECT * FROM foo WHERE name = :name",
+            name = "bar",
+            age = 3i64
+        );
+        assert!(matches!(
+            query.unwrap_err(),
+            crate::error::QueryBuilderError::UnknownNamedArgument(name) if name == "age"
+        ));
+
+        let query = query!(
+            "SELECT * FROM foo WHERE name = :name",
+            name = "bar",
+            name = "baz"
+        );
+        assert!(matches!(
+            query.unwrap_err(),
+            crate::error::QueryBuilderError::DuplicateNamedArgument(name) if name == "name"
+        ));
+
+        let query = query!(
+            "SELECT * FROM foo WHERE name = :name AND id = ?",
+            name = "bar"
+        );
+        assert!(matches!(
+            query.unwrap_err(),
+            crate::error::QueryBuilderError::MixedArguments
+        ));
+
+        // Reused names only need a single binding
+        let query = query!("SELECT * FROM foo WHERE a = :v OR b = :v", v = 1i64);
+        assert!(query.is_ok());
+    }
+
+    #[test]
+    fn unit_query_macro_placeholders_in_literals() {
+        let query = query!(
+            "SELECT * FROM foo WHERE time = '12:30?' -- :comment ?\n AND id = ?",
+            1i64
+        );
+        assert!(query.is_ok());
+
+        let query = query!("SELECT * FROM foo WHERE a = ?2 AND b = ?1", 1i64, 2i64);
+        assert!(query.is_ok());
+
+        let query = query!("SELECT * FROM foo WHERE a = :a AND b = ?", 1i64, 2i64);
+        assert!(query.is_ok());
+    }
+
+    #[test]
+    fn unit_operation_of_queries() {
+        let select = query!("SELECT * FROM foo").unwrap();
+        let insert = query!("INSERT INTO foo (id) VALUES (1)").unwrap();
+        let pragma = query!("PRAGMA table_info(foo)").unwrap();
+
+        assert_eq!(
+            crate::query::Operation::of_queries(&[select, insert]),
+            Some(crate::query::Operation::Insert)
+        );
+
+        let select = query!("SELECT * FROM foo").unwrap();
+        assert_eq!(
+            crate::query::Operation::of_queries(&[select, pragma]),
+            Some(crate::query::Operation::Select)
+        );
+        assert_eq!(crate::query::Operation::of_queries(&[]), None);
+    }
+
     #[test]
     fn unit_query_macro_query_endpoint() {
         let query = query!("SELECT * FROM foo WHERE id = ?", 1i64);
@@ -342,5 +729,93 @@ mod tests {
 
         let endpoint = query.unwrap().endpoint();
         assert_eq!(endpoint, "db/query");
+
+        let query = query!("INSERT INTO foo (name) VALUES ('bar')").unwrap();
+        assert_eq!(query.endpoint(), "db/execute");
+        assert!(!query.returns_rows());
+
+        let query = query!("INSERT INTO foo (name) VALUES ('bar') RETURNING id").unwrap();
+        assert_eq!(query.endpoint(), "db/execute");
+        assert_eq!(query.fetch_endpoint(), "db/request");
+        assert!(query.returns_rows());
+
+        let query = query!("DELETE FROM foo WHERE name = 'returning' RETURNING *").unwrap();
+        assert_eq!(query.fetch_endpoint(), "db/request");
+
+        let query = query!("UPDATE foo SET \"returning\" = 1").unwrap();
+        assert_eq!(query.endpoint(), "db/execute");
+        assert_eq!(query.fetch_endpoint(), "db/execute");
+
+        let query = query!("SELECT * FROM foo").unwrap();
+        assert_eq!(query.fetch_endpoint(), "db/query");
+
+        let query =
+            query!("WITH ids AS (SELECT id FROM bar) DELETE FROM foo WHERE id IN ids").unwrap();
+        assert_eq!(query.endpoint(), "db/execute");
+    }
+
+    #[test]
+    fn unit_operation_from_query_string() {
+        use crate::query::Operation;
+
+        let cases = [
+            ("  \n\tselect 1", Operation::Select),
+            ("-- comment\nSELECT 1", Operation::Select),
+            ("/* comment */ INSERT INTO foo VALUES (1)", Operation::Insert),
+            ("VALUES (1), (2)", Operation::Select),
+            ("REPLACE INTO foo (id) VALUES (1)", Operation::Insert),
+            ("ALTER TABLE foo ADD COLUMN bar TEXT", Operation::Alter),
+            ("EXPLAIN QUERY PLAN SELECT * FROM foo", Operation::Explain),
+            ("VACUUM", Operation::Vacuum),
+            ("ANALYZE foo", Operation::Analyze),
+            ("REINDEX foo", Operation::Reindex),
+            ("ATTACH DATABASE 'other.db' AS other", Operation::Attach),
+            ("DETACH other", Operation::Detach),
+            ("SAVEPOINT one", Operation::Transaction),
+            ("RELEASE one", Operation::Transaction),
+            ("BEGIN", Operation::Transaction),
+            ("WITH x AS (SELECT 1) SELECT * FROM x", Operation::Select),
+            (
+                "WITH RECURSIVE x(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM x) SELECT n FROM x",
+                Operation::Select,
+            ),
+            (
+                "WITH old AS (SELECT id FROM foo) DELETE FROM foo WHERE id IN old",
+                Operation::Delete,
+            ),
+            (
+                "WITH a AS (SELECT 1), b AS MATERIALIZED (SELECT 2) INSERT INTO foo SELECT * FROM a",
+                Operation::Insert,
+            ),
+            (
+                "WITH x AS (SELECT 1) UPDATE foo SET id = (SELECT * FROM x)",
+                Operation::Update,
+            ),
+        ];
+
+        for (query, expected) in cases {
+            assert_eq!(
+                Operation::from_query_string(query).unwrap(),
+                expected,
+                "{query}"
+            );
+        }
+
+        assert!(Operation::from_query_string("").is_err());
+        assert!(Operation::from_query_string("-- only a comment").is_err());
+        assert!(Operation::from_query_string("WITH x AS (SELECT 1)").is_err());
+        assert!(Operation::from_query_string("'SELECT'").is_err());
+    }
+
+    #[test]
+    fn unit_operation_is_read() {
+        use crate::query::Operation;
+
+        assert!(Operation::Select.is_read());
+        assert!(Operation::Pragma.is_read());
+        assert!(Operation::Explain.is_read());
+        assert!(!Operation::Insert.is_read());
+        assert!(!Operation::Alter.is_read());
+        assert!(!Operation::Transaction.is_read());
     }
 }
@@ -0,0 +1,309 @@
+use std::collections::BTreeMap;
+
+use crate::error::QueryBuilderError;
+
+use super::{Operation, RqliteArgument, RqliteArguments, RqliteQuery};
+
+/// Converts a value into query arguments, one per column.
+///
+/// This is usually implemented with `#[derive(ToArgs)]`, which uses the same `#[rqlite(...)]`
+/// attributes as `#[derive(FromRow)]` to name the columns. The provided methods build complete
+/// `INSERT`, `UPDATE` and upsert queries from it. Table and column names are quoted in the
+/// generated queries.
+///
+/// ```ignore
+/// #[derive(ToArgs)]
+/// struct User {
+///     id: i64,
+///     name: String,
+/// }
+///
+/// client.exec(user.insert_query("users")).await?;
+/// ```
+pub trait ToArgs {
+    /// The names of the columns, in the same order as the arguments returned by [`ToArgs::to_args`].
+    fn columns() -> &'static [&'static str]
+    where
+        Self: Sized;
+
+    /// Returns the arguments for all columns.
+    fn to_args(&self) -> Vec<RqliteArgument>;
+
+    /// Returns the arguments for all columns, keyed by column name.
+    fn to_named_args(&self) -> BTreeMap<String, RqliteArgument>
+    where
+        Self: Sized,
+    {
+        Self::columns()
+            .iter()
+            .map(ToString::to_string)
+            .zip(self.to_args())
+            .collect()
+    }
+
+    /// Builds an `INSERT` query for all columns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no columns to insert.
+    fn insert_query(&self, table: &str) -> Result<RqliteQuery, QueryBuilderError>
+    where
+        Self: Sized,
+    {
+        let columns = Self::columns();
+
+        if columns.is_empty() {
+            return Err(QueryBuilderError::InvalidQuery(format!(
+                "No columns to insert into {table}"
+            )));
+        }
+
+        Ok(RqliteQuery {
+            query: insert_statement(table, columns),
+            args: RqliteArguments::Positional(self.to_args()),
+            op: Operation::Insert,
+        })
+    }
+
+    /// Builds an `UPDATE` query that sets all other columns of the row identified by `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `key` is not one of the columns or if there are no other columns.
+    fn update_query(&self, table: &str, key: &str) -> Result<RqliteQuery, QueryBuilderError>
+    where
+        Self: Sized,
+    {
+        let columns = Self::columns();
+        let (key_arg, mut args) = split_key(columns, self.to_args(), key)?;
+
+        let assignments = columns
+            .iter()
+            .filter(|column| **column != key)
+            .map(|column| format!("{} = ?", quote_identifier(column)))
+            .collect::<Vec<_>>();
+
+        if assignments.is_empty() {
+            return Err(QueryBuilderError::InvalidQuery(format!(
+                "No columns to update in {table}"
+            )));
+        }
+
+        let query = format!(
+            "UPDATE {} SET {} WHERE {} = ?",
+            quote_table(table),
+            assignments.join(", "),
+            quote_identifier(key)
+        );
+
+        args.push(key_arg);
+
+        Ok(RqliteQuery {
+            query,
+            args: RqliteArguments::Positional(args),
+            op: Operation::Update,
+        })
+    }
+
+    /// Builds an `INSERT` query that updates all other columns if a row with the same `key`
+    /// already exists. `key` needs to be the primary key or have a unique constraint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `key` is not one of the columns.
+    fn upsert_query(&self, table: &str, key: &str) -> Result<RqliteQuery, QueryBuilderError>
+    where
+        Self: Sized,
+    {
+        let columns = Self::columns();
+
+        if !columns.contains(&key) {
+            return Err(QueryBuilderError::UnknownColumn(key.to_string()));
+        }
+
+        let assignments = columns
+            .iter()
+            .filter(|column| **column != key)
+            .map(|column| {
+                let column = quote_identifier(column);
+                format!("{column} = excluded.{column}")
+            })
+            .collect::<Vec<_>>();
+
+        let action = if assignments.is_empty() {
+            "NOTHING".to_string()
+        } else {
+            format!("UPDATE SET {}", assignments.join(", "))
+        };
+
+        let query = format!(
+            "{} ON CONFLICT ({}) DO {action}",
+            insert_statement(table, columns),
+            quote_identifier(key)
+        );
+
+        Ok(RqliteQuery {
+            query,
+            args: RqliteArguments::Positional(self.to_args()),
+            op: Operation::Insert,
+        })
+    }
+}
+
+/// Quotes an identifier, so column names that are keywords or contain special characters can be used.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Quotes a table name, which can be qualified with a schema name like `main.users`.
+fn quote_table(table: &str) -> String {
+    table
+        .split('.')
+        .map(quote_identifier)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn insert_statement(table: &str, columns: &[&str]) -> String {
+    let names = columns
+        .iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>();
+    let placeholders = vec!["?"; columns.len()];
+
+    format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        quote_table(table),
+        names.join(", "),
+        placeholders.join(", ")
+    )
+}
+
+/// Splits the argument of the `key` column from the arguments of all other columns.
+fn split_key(
+    columns: &[&str],
+    args: Vec<RqliteArgument>,
+    key: &str,
+) -> Result<(RqliteArgument, Vec<RqliteArgument>), QueryBuilderError> {
+    let mut key_arg = None;
+    let mut rest = Vec::with_capacity(args.len());
+
+    for (column, arg) in columns.iter().zip(args) {
+        if *column == key {
+            key_arg = Some(arg);
+        } else {
+            rest.push(arg);
+        }
+    }
+
+    key_arg
+        .map(|key_arg| (key_arg, rest))
+        .ok_or_else(|| QueryBuilderError::UnknownColumn(key.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct User {
+        id: i64,
+        name: String,
+        order: Option<i64>,
+    }
+
+    impl ToArgs for User {
+        fn columns() -> &'static [&'static str] {
+            &["id", "name", "order"]
+        }
+
+        fn to_args(&self) -> Vec<RqliteArgument> {
+            vec![
+                crate::arg!(self.id),
+                crate::arg!(self.name),
+                crate::arg!(self.order),
+            ]
+        }
+    }
+
+    fn user() -> User {
+        User {
+            id: 1,
+            name: "test".to_string(),
+            order: None,
+        }
+    }
+
+    #[test]
+    fn unit_to_named_args() {
+        let args = user().to_named_args();
+
+        assert_eq!(args["id"], RqliteArgument::I64(1));
+        assert_eq!(args["name"], RqliteArgument::String("test".to_string()));
+        assert_eq!(args["order"], RqliteArgument::Null);
+    }
+
+    #[test]
+    fn unit_insert_query() {
+        let query = user().insert_query("users").unwrap();
+
+        assert_eq!(
+            query.query,
+            r#"INSERT INTO "users" ("id", "name", "order") VALUES (?, ?, ?)"#
+        );
+        assert_eq!(query.op, Operation::Insert);
+        assert_eq!(query.args.len(), 3);
+        assert!(query.validate().is_ok());
+    }
+
+    #[test]
+    fn unit_update_query() {
+        let query = user().update_query("users", "id").unwrap();
+
+        assert_eq!(
+            query.query,
+            r#"UPDATE "users" SET "name" = ?, "order" = ? WHERE "id" = ?"#
+        );
+        assert_eq!(query.op, Operation::Update);
+        assert_eq!(
+            query.args,
+            RqliteArguments::Positional(vec![
+                RqliteArgument::String("test".to_string()),
+                RqliteArgument::Null,
+                RqliteArgument::I64(1),
+            ])
+        );
+
+        assert!(matches!(
+            user().update_query("users", "missing"),
+            Err(QueryBuilderError::UnknownColumn(column)) if column == "missing"
+        ));
+    }
+
+    #[test]
+    fn unit_upsert_query() {
+        let query = user().upsert_query("users", "id").unwrap();
+
+        assert_eq!(
+            query.query,
+            r#"INSERT INTO "users" ("id", "name", "order") VALUES (?, ?, ?) ON CONFLICT ("id") DO UPDATE SET "name" = excluded."name", "order" = excluded."order""#
+        );
+        assert_eq!(query.op, Operation::Insert);
+        assert!(query.validate().is_ok());
+    }
+
+    #[test]
+    fn unit_quote_identifier() {
+        assert_eq!(quote_identifier("name"), r#""name""#);
+        assert_eq!(quote_identifier(r#"we"ird"#), r#""we""ird""#);
+    }
+
+    #[test]
+    fn unit_quote_table() {
+        assert_eq!(quote_table("users"), r#""users""#);
+        assert_eq!(quote_table("main.users"), r#""main"."users""#);
+        assert_eq!(
+            quote_table("users; DROP TABLE users"),
+            r#""users; DROP TABLE users""#
+        );
+    }
+}
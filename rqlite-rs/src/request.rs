@@ -1,14 +1,31 @@
-use std::{fmt::Display, num::NonZeroU16};
+use std::{fmt::Display, num::NonZeroU16, time::Duration};
 
 use serde::Serialize;
 
-use crate::config::Scheme;
+use crate::{config::Scheme, load::FileBody, query::Operation};
+
+/// The time the client waits for a response, unless a request overrides it.
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The time the client waits for a response in addition to a timeout enforced by the node.
+pub(crate) const TIMEOUT_MARGIN: Duration = Duration::from_secs(2);
 
 pub(crate) struct RequestOptions {
     pub(crate) method: reqwest::Method,
     pub(crate) endpoint: String,
     pub(crate) body: Option<String>,
     pub(crate) params: Option<RequestQueryParams>,
+    /// The operation of the query, if the request executes queries.
+    pub(crate) operation: Option<Operation>,
+    /// Overrides the timeout of the client for this request.
+    pub(crate) timeout: Option<Duration>,
+    /// A file streamed as the body, replacing `body`.
+    pub(crate) file_body: Option<FileBody>,
+    /// Marks requests that modify the database without executing queries, such as loads.
+    pub(crate) write: bool,
+    /// Marks queued writes. They stay queued on the node even if waiting for them fails,
+    /// so they are never sent again once they may have been received.
+    pub(crate) queued: bool,
 }
 
 impl Default for RequestOptions {
@@ -18,11 +35,21 @@ impl Default for RequestOptions {
             endpoint: "db/request".to_string(),
             body: None,
             params: None,
+            operation: None,
+            timeout: None,
+            file_body: None,
+            write: false,
+            queued: false,
         }
     }
 }
 
 impl RequestOptions {
+    /// Returns `true` if the request modifies the database.
+    pub(crate) fn is_write(&self) -> bool {
+        self.write || self.operation.is_some_and(|op| !op.is_read())
+    }
+
     pub(crate) fn to_reqwest_request(
         &self,
         client: &reqwest::Client,
@@ -38,13 +65,40 @@ impl RequestOptions {
             req = req.body(body.clone());
         }
 
+        if let Some(file) = &self.file_body {
+            req = req
+                .header(reqwest::header::CONTENT_TYPE, file.content_type)
+                .body(file.to_body());
+        }
+
         if let Some(params) = &self.params {
             req = req.query(&params.clone().into_reqwest_query());
         }
 
+        if let Some(timeout) = self.timeout {
+            req = req.timeout(timeout);
+        }
+
         req
     }
 
+    /// Adds the per-call options of a request.
+    /// Parameters already set by the request itself take precedence.
+    pub(crate) fn with_opts(mut self, opts: RequestOpts) -> Self {
+        if let Some(timeout) = opts.0.timeout_duration() {
+            self.extend_timeout(timeout.saturating_add(TIMEOUT_MARGIN));
+        }
+
+        self.merge_default_query_params(&opts.0.into_request_query_params());
+        self
+    }
+
+    /// Makes sure the client does not abort the request before `timeout` has passed.
+    /// Shorter timeouts keep the current one, or the default of the client.
+    pub(crate) fn extend_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(self.timeout.unwrap_or(DEFAULT_TIMEOUT).max(timeout));
+    }
+
     pub(crate) fn merge_default_query_params(&mut self, default_params: &RequestQueryParams) {
         if let Some(params) = &mut self.params {
             params.merge(default_params.clone());
@@ -126,6 +180,8 @@ pub enum RqliteQueryParam {
     Transaction,
     /// Queue the query for later execution
     Queue,
+    /// Wait until queued queries have been applied
+    Wait,
     /// Timeout in seconds
     Timeout(NonZeroU16),
     /// Freshness level
@@ -141,6 +197,8 @@ pub enum RqliteQueryParam {
     /// Retrieve blobs as u8 arrays instead of base64 encoded strings
     /// Defaults to true when the `fast-blob` feature is disabled
     BlobArray,
+    /// Include the Raft index of writes in the response
+    RaftIndex,
 }
 
 impl RqliteQueryParam {
@@ -150,6 +208,7 @@ impl RqliteQueryParam {
             Self::Timings => RequestQueryParam::Bool("timings".to_string()),
             Self::Transaction => RequestQueryParam::Bool("transaction".to_string()),
             Self::Queue => RequestQueryParam::Bool("queue".to_string()),
+            Self::Wait => RequestQueryParam::Bool("wait".to_string()),
             Self::Timeout(t) => RequestQueryParam::KV("timeout".to_string(), format!("{t}s")),
             Self::Level(l) => RequestQueryParam::KV("level".to_string(), l.to_string()),
             Self::Freshness(f) => RequestQueryParam::KV("freshness".to_string(), format!("{f}s")),
@@ -157,10 +216,101 @@ impl RqliteQueryParam {
             Self::NoRWRandom => RequestQueryParam::Bool("norwrandom".to_string()),
             Self::Ver(v) => RequestQueryParam::KV("ver".to_string(), v),
             Self::BlobArray => RequestQueryParam::Bool("blob_array".to_string()),
+            Self::RaftIndex => RequestQueryParam::Bool("raft_index".to_string()),
         }
     }
 }
 
+/// Options for a single request.
+///
+/// Options set here override the defaults set using
+/// [`RqliteClientBuilder::default_query_params`](crate::RqliteClientBuilder::default_query_params).
+///
+/// # Example
+/// ```
+/// use std::num::NonZeroU16;
+///
+/// use rqlite_rs::request::{RequestOpts, RqliteFreshnessLevel};
+///
+/// let opts = RequestOpts::new()
+///     .level(RqliteFreshnessLevel::Strong)
+///     .timeout(NonZeroU16::new(10).unwrap());
+/// ```
+#[derive(Default)]
+pub struct RequestOpts(RqliteQueryParams);
+
+impl RequestOpts {
+    /// Creates empty request options, using the defaults of the client.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(RqliteQueryParams::new())
+    }
+
+    /// Sets the read consistency level.
+    /// See <https://rqlite.io/docs/api/read-consistency/>.
+    #[must_use]
+    pub fn level(mut self, level: RqliteFreshnessLevel) -> Self {
+        self.0 = self.0.level(level);
+        self
+    }
+
+    /// Sets the maximum staleness in seconds for reads with the `none` consistency level.
+    #[must_use]
+    pub fn freshness(mut self, freshness: NonZeroU16) -> Self {
+        self.0 = self.0.freshness(freshness);
+        self
+    }
+
+    /// Makes the freshness check strict.
+    #[must_use]
+    pub fn freshness_strict(mut self) -> Self {
+        self.0 = self.0.freshness_strict();
+        self
+    }
+
+    /// Sets the timeout in seconds for the request on the rqlite node.
+    /// The client waits at least this long for the response, even if its own timeout is shorter.
+    #[must_use]
+    pub fn timeout(mut self, timeout: NonZeroU16) -> Self {
+        self.0 = self.0.timeout(timeout);
+        self
+    }
+
+    /// Includes timing information in the response.
+    #[must_use]
+    pub fn timings(mut self) -> Self {
+        self.0 = self.0.timings();
+        self
+    }
+
+    /// Includes the Raft index of writes in the response.
+    #[must_use]
+    pub fn raft_index(mut self) -> Self {
+        self.0 = self.0.raft_index();
+        self
+    }
+
+    /// Disables rewriting of `RANDOM()`.
+    #[must_use]
+    pub fn norwrandom(mut self) -> Self {
+        self.0 = self.0.norwrandom();
+        self
+    }
+
+    /// Adds an arbitrary query parameter.
+    #[must_use]
+    pub fn param(mut self, param: RqliteQueryParam) -> Self {
+        self.0 .0.push(param);
+        self
+    }
+}
+
+impl From<Vec<RqliteQueryParam>> for RequestOpts {
+    fn from(params: Vec<RqliteQueryParam>) -> Self {
+        Self(RqliteQueryParams::from(params))
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct RqliteQueryParams(Vec<RqliteQueryParam>);
 
@@ -196,6 +346,11 @@ impl RqliteQueryParams {
         self
     }
 
+    pub fn wait(mut self) -> Self {
+        self.0.push(RqliteQueryParam::Wait);
+        self
+    }
+
     pub fn timeout(mut self, t: NonZeroU16) -> Self {
         self.0.push(RqliteQueryParam::Timeout(t));
         self
@@ -231,6 +386,19 @@ impl RqliteQueryParams {
         self
     }
 
+    pub fn raft_index(mut self) -> Self {
+        self.0.push(RqliteQueryParam::RaftIndex);
+        self
+    }
+
+    /// Returns the timeout enforced by the node, if set.
+    pub(crate) fn timeout_duration(&self) -> Option<Duration> {
+        self.0.iter().rev().find_map(|param| match param {
+            RqliteQueryParam::Timeout(t) => Some(Duration::from_secs(u64::from(t.get()))),
+            _ => None,
+        })
+    }
+
     pub(crate) fn into_request_query_params(self) -> RequestQueryParams {
         let mut params = RequestQueryParams::new();
 
@@ -278,10 +446,7 @@ mod tests {
         let params = full_query_params();
         let req_params = RequestQueryParams::from(params);
 
-        #[cfg(feature = "fast-blob")]
         assert_eq!(req_params.0.len(), 11);
-        #[cfg(not(feature = "fast-blob"))]
-        assert_eq!(req_params.0.len(), 12);
     }
 
     #[test]
@@ -291,10 +456,7 @@ mod tests {
 
         let reqwest_query = req_params.into_reqwest_query();
 
-        #[cfg(feature = "fast-blob")]
         assert_eq!(reqwest_query.len(), 11);
-        #[cfg(not(feature = "fast-blob"))]
-        assert_eq!(reqwest_query.len(), 12);
     }
 
     #[test]
@@ -305,6 +467,23 @@ mod tests {
         assert_eq!(req.endpoint, "db/request");
         assert_eq!(req.body, None);
         assert!(req.params.is_none());
+        assert!(req.operation.is_none());
+        assert!(!req.is_write());
+    }
+
+    #[test]
+    fn unit_request_options_is_write() {
+        let req = RequestOptions {
+            operation: Some(Operation::Insert),
+            ..Default::default()
+        };
+        assert!(req.is_write());
+
+        let req = RequestOptions {
+            operation: Some(Operation::Select),
+            ..Default::default()
+        };
+        assert!(!req.is_write());
     }
 
     #[test]
@@ -343,6 +522,24 @@ mod tests {
         assert!(query.contains("blob_array=true"));
     }
 
+    #[test]
+    fn unit_request_query_params_queue_and_wait() {
+        let params = RqliteQueryParams::new()
+            .queue()
+            .wait()
+            .timeout(NonZeroU16::new(5).unwrap())
+            .into_request_query_params();
+
+        assert_eq!(
+            params.into_reqwest_query(),
+            vec![
+                ("queue".to_string(), "true".to_string()),
+                ("wait".to_string(), "true".to_string()),
+                ("timeout".to_string(), "5s".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn unit_request_options_merge_default_query_params() {
         let mut req = RequestOptions::default();
@@ -354,10 +551,58 @@ mod tests {
 
         let req_params = req.params.unwrap();
 
-        #[cfg(feature = "fast-blob")]
         assert_eq!(req_params.0.len(), 11);
-        #[cfg(not(feature = "fast-blob"))]
-        assert_eq!(req_params.0.len(), 12);
+    }
+
+    #[test]
+    fn unit_request_options_with_opts() {
+        let req = RequestOptions {
+            params: Some(RqliteQueryParams::new().queue().into_request_query_params()),
+            ..Default::default()
+        }
+        .with_opts(
+            RequestOpts::new()
+                .level(RqliteFreshnessLevel::Strong)
+                .timeout(NonZeroU16::new(3).unwrap())
+                .param(RqliteQueryParam::Queue),
+        );
+
+        // Per-call options override the client defaults
+        let mut req = req;
+        req.merge_default_query_params(
+            &RqliteQueryParams::new()
+                .level(RqliteFreshnessLevel::Weak)
+                .timings()
+                .into_request_query_params(),
+        );
+
+        let query = req.params.unwrap().into_reqwest_query();
+
+        assert_eq!(
+            query,
+            vec![
+                ("queue".to_string(), "true".to_string()),
+                ("level".to_string(), "strong".to_string()),
+                ("timeout".to_string(), "3s".to_string()),
+                ("timings".to_string(), "true".to_string()),
+            ]
+        );
+        assert_eq!(req.timeout, Some(Duration::from_secs(3) + TIMEOUT_MARGIN));
+
+        // A longer timeout of the request is kept
+        let req = RequestOptions {
+            timeout: Some(Duration::from_secs(60)),
+            ..Default::default()
+        }
+        .with_opts(RequestOpts::new().timeout(NonZeroU16::new(3).unwrap()));
+        assert_eq!(req.timeout, Some(Duration::from_secs(60)));
+
+        // A shorter timeout does not undercut the default of the client
+        let req = RequestOptions::default().with_opts(RequestOpts::new().timeout(NonZeroU16::MIN));
+        assert_eq!(req.timeout, Some(DEFAULT_TIMEOUT));
+
+        let req = RequestOptions::default().with_opts(RequestOpts::new());
+        assert!(req.timeout.is_none());
     }
 
     #[test]
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize, Clone)]
@@ -16,3 +18,142 @@ pub struct QueryError {
 pub(crate) struct RqliteResponseRaw<T> {
     pub(crate) results: Vec<RqliteResult<T>>,
 }
+
+/// The response to queued queries, which only contains the sequence number of the request.
+#[derive(Debug, Deserialize)]
+pub(crate) struct QueuedResponseRaw {
+    pub(crate) sequence_number: u64,
+}
+
+/// A response together with information on how it was served.
+/// Returned by the `*_detailed` methods of [`RqliteClient`](crate::RqliteClient).
+#[derive(Debug, Clone)]
+pub struct DetailedResponse<T> {
+    result: T,
+    statement_times: Vec<Option<Duration>>,
+    time: Option<Duration>,
+    raft_index: Option<u64>,
+    node: String,
+}
+
+impl<T> DetailedResponse<T> {
+    /// Returns the result of the request.
+    pub const fn result(&self) -> &T {
+        &self.result
+    }
+
+    /// Consumes the response, returning the result of the request.
+    pub fn into_result(self) -> T {
+        self.result
+    }
+
+    /// Returns the execution time of every statement, in the order the statements were sent.
+    #[must_use]
+    pub fn statement_times(&self) -> &[Option<Duration>] {
+        &self.statement_times
+    }
+
+    /// Returns the total time the node took to process the request.
+    #[must_use]
+    pub const fn time(&self) -> Option<Duration> {
+        self.time
+    }
+
+    /// Returns the Raft index of the write, if the request modified the database.
+    #[must_use]
+    pub const fn raft_index(&self) -> Option<u64> {
+        self.raft_index
+    }
+
+    /// Returns the address of the node that served the request, in the format `hostname:port`.
+    #[must_use]
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+}
+
+/// The timings and Raft index of a response, deserialized independently of the results.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ResponseMeta {
+    #[serde(default)]
+    results: Vec<StatementMeta>,
+    time: Option<f64>,
+    raft_index: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatementMeta {
+    time: Option<f64>,
+}
+
+/// Converts a time in seconds, as returned by rqlite, into a [`Duration`].
+fn seconds(time: Option<f64>) -> Option<Duration> {
+    Duration::try_from_secs_f64(time?).ok()
+}
+
+impl ResponseMeta {
+    pub(crate) fn into_detailed<T>(self, result: T, node: String) -> DetailedResponse<T> {
+        DetailedResponse {
+            result,
+            statement_times: self
+                .results
+                .into_iter()
+                .map(|statement| seconds(statement.time))
+                .collect(),
+            time: seconds(self.time),
+            raft_index: self.raft_index,
+            node,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_response_meta_into_detailed() {
+        let body = r#"{
+            "results": [
+                {"last_insert_id": 1, "rows_affected": 1, "time": 0.5},
+                {"error": "no such table: foo"}
+            ],
+            "time": 1.25,
+            "raft_index": 42
+        }"#;
+
+        let meta = serde_json::from_str::<ResponseMeta>(body).unwrap();
+        let response = meta.into_detailed((), "localhost:4001".to_string());
+
+        assert_eq!(
+            response.statement_times(),
+            &[Some(Duration::from_millis(500)), None]
+        );
+        assert_eq!(response.time(), Some(Duration::from_millis(1250)));
+        assert_eq!(response.raft_index(), Some(42));
+        assert_eq!(response.node(), "localhost:4001");
+    }
+
+    #[test]
+    fn unit_queued_response() {
+        let body = r#"{"results": [], "sequence_number": 1653314298877648934}"#;
+
+        let response = serde_json::from_str::<QueuedResponseRaw>(body).unwrap();
+
+        assert_eq!(response.sequence_number, 1_653_314_298_877_648_934);
+    }
+
+    #[test]
+    fn unit_response_meta_without_timings() {
+        let body = r#"{"results": [{"columns": ["id"], "types": ["integer"]}]}"#;
+
+        let meta = serde_json::from_str::<ResponseMeta>(body).unwrap();
+        let response = meta.into_detailed(1, String::new());
+
+        assert_eq!(response.statement_times(), &[None]);
+        assert_eq!(response.time(), None);
+        assert_eq!(response.raft_index(), None);
+        assert_eq!(*response.result(), 1);
+        assert_eq!(response.into_result(), 1);
+    }
+}
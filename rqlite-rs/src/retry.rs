@@ -0,0 +1,389 @@
+//! Retry policies for failed requests.
+//!
+//! Without a retry policy the client only switches hosts on connection errors and timeouts,
+//! without any delay. A [`RetryPolicy`] set using
+//! [`RqliteClientBuilder::retry_policy`](crate::RqliteClientBuilder::retry_policy) decides for
+//! every failed attempt whether to retry on the same host, switch to another host or give up.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// The class of error a request attempt failed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryErrorKind {
+    /// The connection to the host could not be established.
+    /// The request was not received by the host.
+    Connect,
+    /// The request timed out.
+    Timeout,
+    /// The host responded with `503 Service Unavailable`, for example during a leader election.
+    ServiceUnavailable,
+    /// The host lost its leadership while processing the request.
+    LeadershipLost,
+    /// The host responded with a server error other than `503`.
+    ServerError(reqwest::StatusCode),
+}
+
+impl RetryErrorKind {
+    /// Returns `true` if a write may already have been applied when the attempt failed with
+    /// this kind of error, so retrying it could apply it twice.
+    #[must_use]
+    pub const fn may_have_been_applied(&self) -> bool {
+        matches!(
+            self,
+            Self::Timeout | Self::LeadershipLost | Self::ServerError(_)
+        )
+    }
+
+    /// Classifies an error response. Returns `None` for responses that are not retryable.
+    pub(crate) fn from_status(status: reqwest::StatusCode, body: &str) -> Option<Self> {
+        if is_leadership_error(status, body) {
+            Some(Self::LeadershipLost)
+        } else if status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+            Some(Self::ServiceUnavailable)
+        } else if status.is_server_error() {
+            Some(Self::ServerError(status))
+        } else {
+            None
+        }
+    }
+
+    /// Classifies a transport error. Returns `None` for errors that are not retryable.
+    pub(crate) fn from_reqwest_error(e: &reqwest::Error) -> Option<Self> {
+        if e.is_connect() {
+            Some(Self::Connect)
+        } else if e.is_timeout() {
+            Some(Self::Timeout)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns `true` if the error indicates that the node is (no longer) the leader.
+pub(crate) fn is_leadership_error(status: reqwest::StatusCode, body: &str) -> bool {
+    status == reqwest::StatusCode::SERVICE_UNAVAILABLE && body.to_lowercase().contains("leader")
+}
+
+/// Information about a failed attempt passed to [`RetryPolicy::decide`].
+#[derive(Debug, Clone, Copy)]
+pub struct RetryContext {
+    /// The class of error the attempt failed with.
+    pub error: RetryErrorKind,
+    /// The number of attempts made so far, starting at `1`.
+    pub attempt: u32,
+    /// If the request modifies the database.
+    pub is_write: bool,
+}
+
+/// The maximum number of consecutive retries on the same host, so a policy that never gives up
+/// can not retry a request forever.
+pub(crate) const MAX_SAME_HOST_RETRIES: u32 = 16;
+
+/// What to do after a failed attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Retry on the same host after the given delay.
+    /// A host is retried at most 16 times in a row.
+    RetrySameHost(Duration),
+    /// Retry on the host chosen by the fallback strategy after the given delay.
+    /// The number of hosts tried is limited by the
+    /// [`FallbackCount`](crate::fallback::FallbackCount) of the client.
+    SwitchHost(Duration),
+    /// Stop retrying and return the error.
+    GiveUp,
+}
+
+/// `RetryPolicy` decides how the client reacts to a failed request attempt.
+pub trait RetryPolicy: Send + Sync + 'static {
+    /// Decides whether and where to retry after a failed attempt.
+    fn decide(&self, ctx: &RetryContext) -> RetryDecision;
+}
+
+/// A retry policy with exponentially growing delays and full jitter.
+///
+/// The delay before retry `n` is a random duration between zero and
+/// `min(max_delay, initial_delay * multiplier^(n - 1))`.
+/// Connection errors, timeouts and server errors switch to another host, while `503` responses
+/// and lost leadership are retried on the same host, as they usually resolve once a new leader
+/// has been elected.
+///
+/// Writes that may already have been applied (timeouts, lost leadership and server errors) are
+/// not retried unless [`ExponentialBackoff::retry_writes`] is enabled.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use rqlite_rs::{retry::ExponentialBackoff, RqliteClientBuilder};
+///
+/// let client = RqliteClientBuilder::new()
+///     .known_host("localhost:4001")
+///     .known_host("localhost:4003")
+///     .retry_policy(
+///         ExponentialBackoff::new()
+///             .initial_delay(Duration::from_millis(50))
+///             .max_retries(8),
+///     )
+///     .build();
+///
+/// assert!(client.is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExponentialBackoff {
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    max_retries: u32,
+    jitter: bool,
+    retry_writes: bool,
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExponentialBackoff {
+    /// Creates a new policy retrying up to 5 times, starting with a delay of 100ms that doubles
+    /// with every retry up to 5s.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            multiplier: 2.0,
+            max_retries: 5,
+            jitter: true,
+            retry_writes: false,
+        }
+    }
+
+    /// Sets the delay before the first retry.
+    #[must_use]
+    pub const fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Sets the upper bound for the delay between retries.
+    #[must_use]
+    pub const fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Sets the factor the delay grows by with every retry.
+    #[must_use]
+    pub const fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets the maximum number of retries.
+    #[must_use]
+    pub const fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Enables or disables jitter. Enabled by default.
+    #[must_use]
+    pub const fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Allows retrying writes that may already have been applied.
+    /// Only enable this if all writes are idempotent.
+    #[must_use]
+    pub const fn retry_writes(mut self, retry_writes: bool) -> Self {
+        self.retry_writes = retry_writes;
+        self
+    }
+
+    /// Returns the delay before the given retry, starting at `1`.
+    fn delay(&self, retry: u32) -> Duration {
+        let exponent = i32::try_from(retry.saturating_sub(1)).unwrap_or(i32::MAX);
+        let delay = self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent);
+        let delay = delay.min(self.max_delay.as_secs_f64());
+
+        let delay = if self.jitter {
+            delay * random_fraction()
+        } else {
+            delay
+        };
+
+        Duration::try_from_secs_f64(delay).unwrap_or(self.max_delay)
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn decide(&self, ctx: &RetryContext) -> RetryDecision {
+        if ctx.attempt > self.max_retries {
+            return RetryDecision::GiveUp;
+        }
+
+        if ctx.is_write && ctx.error.may_have_been_applied() && !self.retry_writes {
+            return RetryDecision::GiveUp;
+        }
+
+        let delay = self.delay(ctx.attempt);
+
+        match ctx.error {
+            RetryErrorKind::Connect | RetryErrorKind::Timeout | RetryErrorKind::ServerError(_) => {
+                RetryDecision::SwitchHost(delay)
+            }
+            RetryErrorKind::ServiceUnavailable | RetryErrorKind::LeadershipLost => {
+                RetryDecision::RetrySameHost(delay)
+            }
+        }
+    }
+}
+
+/// Returns a random number in `[0, 1)`.
+/// `RandomState` is seeded randomly, which is good enough for jitter and avoids a dependency.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos()),
+    );
+
+    // Use the upper 53 bits, which an f64 can represent exactly
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "53 bit integers are exactly representable as f64"
+    )]
+    let fraction = (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64;
+
+    fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(error: RetryErrorKind, attempt: u32, is_write: bool) -> RetryContext {
+        RetryContext {
+            error,
+            attempt,
+            is_write,
+        }
+    }
+
+    #[test]
+    fn unit_retry_error_kind_from_status() {
+        assert_eq!(
+            RetryErrorKind::from_status(reqwest::StatusCode::SERVICE_UNAVAILABLE, "not leader"),
+            Some(RetryErrorKind::LeadershipLost)
+        );
+        assert_eq!(
+            RetryErrorKind::from_status(reqwest::StatusCode::SERVICE_UNAVAILABLE, ""),
+            Some(RetryErrorKind::ServiceUnavailable)
+        );
+        assert_eq!(
+            RetryErrorKind::from_status(reqwest::StatusCode::BAD_GATEWAY, ""),
+            Some(RetryErrorKind::ServerError(
+                reqwest::StatusCode::BAD_GATEWAY
+            ))
+        );
+        assert_eq!(
+            RetryErrorKind::from_status(reqwest::StatusCode::BAD_REQUEST, ""),
+            None
+        );
+    }
+
+    #[test]
+    fn unit_is_leadership_error() {
+        assert!(is_leadership_error(
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+            "leadership lost while committing log"
+        ));
+        assert!(is_leadership_error(
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+            "not leader"
+        ));
+        assert!(!is_leadership_error(
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+            "database is locked"
+        ));
+        assert!(!is_leadership_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            "not leader"
+        ));
+    }
+
+    #[test]
+    fn unit_exponential_backoff_delay() {
+        let policy = ExponentialBackoff::new()
+            .initial_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(500))
+            .jitter(false);
+
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(400));
+        assert_eq!(policy.delay(4), Duration::from_millis(500));
+
+        let policy = policy.jitter(true);
+        for retry in 1..10 {
+            assert!(policy.delay(retry) <= Duration::from_millis(500));
+        }
+    }
+
+    #[test]
+    fn unit_exponential_backoff_decide() {
+        let policy = ExponentialBackoff::new().max_retries(2).jitter(false);
+
+        assert_eq!(
+            policy.decide(&ctx(RetryErrorKind::Connect, 1, true)),
+            RetryDecision::SwitchHost(Duration::from_millis(100))
+        );
+        assert_eq!(
+            policy.decide(&ctx(RetryErrorKind::ServiceUnavailable, 2, false)),
+            RetryDecision::RetrySameHost(Duration::from_millis(200))
+        );
+        assert_eq!(
+            policy.decide(&ctx(RetryErrorKind::Connect, 3, false)),
+            RetryDecision::GiveUp
+        );
+    }
+
+    #[test]
+    fn unit_exponential_backoff_writes() {
+        let policy = ExponentialBackoff::new().jitter(false);
+
+        assert_eq!(
+            policy.decide(&ctx(RetryErrorKind::Timeout, 1, true)),
+            RetryDecision::GiveUp
+        );
+        assert_eq!(
+            policy.decide(&ctx(RetryErrorKind::LeadershipLost, 1, true)),
+            RetryDecision::GiveUp
+        );
+        assert_eq!(
+            policy.decide(&ctx(RetryErrorKind::Timeout, 1, false)),
+            RetryDecision::SwitchHost(Duration::from_millis(100))
+        );
+
+        let policy = policy.retry_writes(true);
+        assert_eq!(
+            policy.decide(&ctx(RetryErrorKind::Timeout, 1, true)),
+            RetryDecision::SwitchHost(Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn unit_random_fraction() {
+        for _ in 0..100 {
+            let fraction = random_fraction();
+            assert!((0.0..1.0).contains(&fraction));
+        }
+    }
+}
@@ -1,14 +1,13 @@
 use std::{collections::HashMap, sync::Arc};
 
-use rqlite_rs_core::{Column, Row};
+use rqlite_rs_core::{Column, Row, SqlValue};
 use serde::Deserialize;
-use serde_json::Value;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct RqliteSelectResults {
     columns: Vec<String>,
     types: Vec<String>,
-    values: Option<Vec<Vec<Value>>>,
+    values: Option<Vec<Vec<SqlValue>>>,
 }
 
 impl RqliteSelectResults {
@@ -18,12 +17,7 @@ impl RqliteSelectResults {
         let mut columns = vec![];
         let mut column_names = HashMap::new();
 
-        for (index, (column, column_type)) in self
-            .columns
-            .into_iter()
-            .zip(self.types.into_iter())
-            .enumerate()
-        {
+        for (index, (column, column_type)) in self.columns.into_iter().zip(self.types).enumerate() {
             let column = Column::new(column, index, column_type);
             column_names.insert(column.name().to_string(), index);
             columns.push(column);
@@ -34,11 +28,13 @@ impl RqliteSelectResults {
 
         if let Some(values) = self.values {
             for row in values {
-                rows.push(Row::new(
-                    &columns,
-                    &column_names,
-                    row.into_boxed_slice(), //TODO! unnecessary clone
-                ));
+                let values = row
+                    .into_iter()
+                    .zip(columns.iter())
+                    .map(|(value, column)| value.with_type_data(column.type_data()))
+                    .collect();
+
+                rows.push(Row::from_values(&columns, &column_names, values));
             }
         }
 
@@ -56,8 +52,8 @@ mod tests {
             columns: vec!["id".to_string(), "name".to_string()],
             types: vec!["integer".to_string(), "text".to_string()],
             values: Some(vec![vec![
-                Value::Number(serde_json::Number::from(1)),
-                Value::String("test".to_string()),
+                SqlValue::Integer(1),
+                SqlValue::Text("test".to_string()),
             ]]),
         };
 
@@ -67,4 +63,19 @@ mod tests {
         assert_eq!(row.get::<i64>("id").unwrap(), 1);
         assert_eq!(row.get::<String>("name").unwrap(), "test");
     }
+
+    #[test]
+    fn unit_rqlite_select_results_column_types() {
+        let select_results: RqliteSelectResults = serde_json::from_str(
+            r#"{"columns":["score","name"],"types":["real","text"],"values":[[1,"test"],[1.5,null]]}"#,
+        )
+        .unwrap();
+
+        let rows = select_results.rows();
+        assert_eq!(
+            rows[0].values(),
+            &[SqlValue::Real(1.0), SqlValue::Text("test".to_string())]
+        );
+        assert_eq!(rows[1].values(), &[SqlValue::Real(1.5), SqlValue::Null]);
+    }
 }
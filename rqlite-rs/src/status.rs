@@ -0,0 +1,405 @@
+//! Typed status and diagnostics of rqlite nodes.
+//!
+//! See [`RqliteClient::status`](crate::RqliteClient::status),
+//! [`RqliteClient::expvar`](crate::RqliteClient::expvar) and
+//! [`RqliteClient::replication_lag`](crate::RqliteClient::replication_lag).
+//!
+//! All fields are optional and unknown fields are kept in `other`, so responses of newer or
+//! older rqlite versions can still be deserialized.
+
+use serde::{Deserialize, Deserializer};
+use serde_json::{Map, Value};
+
+use crate::node::Node;
+
+/// Deserializes an unsigned integer that may be encoded as a string.
+/// rqlite returns most Raft statistics as strings.
+fn u64_or_string<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<Value>::deserialize(deserializer)?;
+
+    Ok(match value {
+        Some(Value::Number(n)) => n.as_u64(),
+        Some(Value::String(s)) => s.parse().ok(),
+        _ => None,
+    })
+}
+
+/// The response of the `/status` endpoint.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Status {
+    /// Information about the rqlite build.
+    pub build: BuildInfo,
+    /// Information about the HTTP service.
+    pub http: HttpStatus,
+    /// Information about the node process.
+    pub node: NodeStatus,
+    /// Information about the store, including Raft and `SQLite`.
+    pub store: StoreStatus,
+    /// All other fields.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Information about the rqlite build.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BuildInfo {
+    pub version: Option<String>,
+    pub commit: Option<String>,
+    pub branch: Option<String>,
+    pub build_time: Option<String>,
+    pub compiler: Option<String>,
+    /// All other fields.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Information about the HTTP service of a node.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct HttpStatus {
+    pub bind_addr: Option<String>,
+    pub auth: Option<String>,
+    /// All other fields.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Information about the node process.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct NodeStatus {
+    pub start_time: Option<String>,
+    pub current_time: Option<String>,
+    pub uptime: Option<String>,
+    /// All other fields.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Information about the store of a node.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct StoreStatus {
+    pub node_id: Option<String>,
+    /// The Raft address of the node.
+    pub addr: Option<String>,
+    pub leader: Option<LeaderStatus>,
+    pub ready: Option<bool>,
+    #[serde(deserialize_with = "u64_or_string")]
+    pub db_applied_index: Option<u64>,
+    #[serde(deserialize_with = "u64_or_string")]
+    pub fsm_index: Option<u64>,
+    #[serde(deserialize_with = "u64_or_string")]
+    pub dir_size: Option<u64>,
+    pub raft: RaftStatus,
+    pub sqlite3: SqliteStatus,
+    /// All other fields.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The leader as seen by a node.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LeaderStatus {
+    /// The Raft address of the leader.
+    pub addr: Option<String>,
+    pub node_id: Option<String>,
+}
+
+/// The Raft state of a node.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RaftStatus {
+    /// The Raft state, e.g. `Leader`, `Follower` or `Candidate`.
+    pub state: Option<String>,
+    #[serde(deserialize_with = "u64_or_string")]
+    pub term: Option<u64>,
+    #[serde(deserialize_with = "u64_or_string")]
+    pub applied_index: Option<u64>,
+    #[serde(deserialize_with = "u64_or_string")]
+    pub commit_index: Option<u64>,
+    #[serde(deserialize_with = "u64_or_string")]
+    pub last_log_index: Option<u64>,
+    #[serde(deserialize_with = "u64_or_string")]
+    pub fsm_pending: Option<u64>,
+    #[serde(deserialize_with = "u64_or_string")]
+    pub num_peers: Option<u64>,
+    /// The time since the last contact with the leader, e.g. `0`, `never` or `18.5ms`.
+    pub last_contact: Option<String>,
+    /// All other fields.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The `SQLite` database of a node.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SqliteStatus {
+    /// The `SQLite` version.
+    pub version: Option<String>,
+    /// The size of the database in bytes.
+    #[serde(deserialize_with = "u64_or_string")]
+    pub db_size: Option<u64>,
+    pub path: Option<String>,
+    /// All other fields.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The response of the `/debug/vars` endpoint.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Expvar {
+    /// The command line the node was started with.
+    pub cmdline: Vec<String>,
+    /// Statistics of the HTTP service.
+    pub http: HttpStats,
+    /// Memory statistics of the Go runtime.
+    pub memstats: MemStats,
+    /// All other fields.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Statistics of the HTTP service of a node.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct HttpStats {
+    pub requests: Option<u64>,
+    pub executions: Option<u64>,
+    pub queries: Option<u64>,
+    pub queued_executions: Option<u64>,
+    pub backups: Option<u64>,
+    pub loads: Option<u64>,
+    pub leader_not_found: Option<u64>,
+    /// All other fields.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Memory statistics of the Go runtime.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct MemStats {
+    pub alloc: Option<u64>,
+    pub total_alloc: Option<u64>,
+    pub sys: Option<u64>,
+    pub heap_alloc: Option<u64>,
+    #[serde(rename = "NumGC")]
+    pub num_gc: Option<u64>,
+    /// All other fields.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The replication lag of every node in the cluster.
+#[derive(Debug, Clone)]
+pub struct ReplicationLag {
+    /// The commit index of the leader, or the highest commit index of all nodes if the leader
+    /// could not be reached.
+    pub commit_index: u64,
+    /// The lag of every node.
+    pub nodes: Vec<NodeLag>,
+}
+
+impl ReplicationLag {
+    pub(crate) fn new(nodes: Vec<(Node, Result<Status, String>)>) -> Self {
+        let commit_index =
+            |status: &Result<Status, String>| status.as_ref().ok()?.store.raft.commit_index;
+
+        let leader_commit_index = nodes
+            .iter()
+            .filter(|(node, _)| node.leader)
+            .find_map(|(_, status)| commit_index(status));
+
+        let commit_index = leader_commit_index.unwrap_or_else(|| {
+            nodes
+                .iter()
+                .filter_map(|(_, status)| commit_index(status))
+                .max()
+                .unwrap_or_default()
+        });
+
+        let nodes = nodes
+            .into_iter()
+            .map(|(node, status)| {
+                let (applied_index, error) = match status {
+                    Ok(status) => (status.store.raft.applied_index, None),
+                    Err(e) => (None, Some(e)),
+                };
+
+                NodeLag {
+                    id: node.id,
+                    api_addr: node.api_addr,
+                    leader: node.leader,
+                    applied_index,
+                    lag: applied_index.map(|index| commit_index.saturating_sub(index)),
+                    error,
+                }
+            })
+            .collect();
+
+        Self {
+            commit_index,
+            nodes,
+        }
+    }
+
+    /// Returns the highest lag of all reachable nodes.
+    #[must_use]
+    pub fn max_lag(&self) -> Option<u64> {
+        self.nodes.iter().filter_map(|node| node.lag).max()
+    }
+}
+
+/// The replication lag of a single node.
+#[derive(Debug, Clone)]
+pub struct NodeLag {
+    /// The ID of the node.
+    pub id: String,
+    /// The address of the node's API.
+    pub api_addr: String,
+    /// If the node is the leader.
+    pub leader: bool,
+    /// The index of the last log entry applied by the node.
+    pub applied_index: Option<u64>,
+    /// The number of log entries the node is behind the commit index.
+    pub lag: Option<u64>,
+    /// If the status of the node could not be retrieved, this will contain the error message.
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATUS: &str = r#"{
+        "build": {"version": "v8.36.0", "commit": "abc", "branch": "master", "build_time": "now", "compiler": "gc"},
+        "http": {"bind_addr": "[::]:4001", "auth": "disabled", "queue": {"batch_size": 128}},
+        "node": {"start_time": "2024-01-01T00:00:00Z", "uptime": "1m0s"},
+        "store": {
+            "node_id": "1",
+            "addr": "localhost:4002",
+            "leader": {"addr": "localhost:4002", "node_id": "1"},
+            "ready": true,
+            "db_applied_index": 7,
+            "fsm_index": 7,
+            "raft": {
+                "state": "Leader",
+                "term": "2",
+                "applied_index": "7",
+                "commit_index": "7",
+                "last_contact": "0",
+                "num_peers": "0",
+                "protocol_version": "3"
+            },
+            "sqlite3": {"version": "3.45.1", "db_size": 8192, "path": ":memory:"},
+            "snapshot_threshold": 8192
+        },
+        "last_backup_time": null
+    }"#;
+
+    #[test]
+    fn unit_status_deserialize() {
+        let status = serde_json::from_str::<Status>(STATUS).unwrap();
+
+        assert_eq!(status.build.version.as_deref(), Some("v8.36.0"));
+        assert_eq!(status.http.bind_addr.as_deref(), Some("[::]:4001"));
+        assert!(status.http.other.contains_key("queue"));
+        assert_eq!(status.node.uptime.as_deref(), Some("1m0s"));
+        assert_eq!(status.store.ready, Some(true));
+        assert_eq!(status.store.db_applied_index, Some(7));
+        assert_eq!(status.store.raft.state.as_deref(), Some("Leader"));
+        assert_eq!(status.store.raft.term, Some(2));
+        assert_eq!(status.store.raft.commit_index, Some(7));
+        assert_eq!(status.store.raft.last_contact.as_deref(), Some("0"));
+        assert!(status.store.raft.other.contains_key("protocol_version"));
+        assert_eq!(status.store.sqlite3.version.as_deref(), Some("3.45.1"));
+        assert_eq!(status.store.sqlite3.db_size, Some(8192));
+        assert!(status.store.other.contains_key("snapshot_threshold"));
+        assert!(status.other.contains_key("last_backup_time"));
+    }
+
+    #[test]
+    fn unit_status_deserialize_empty() {
+        let status = serde_json::from_str::<Status>("{}").unwrap();
+
+        assert!(status.build.version.is_none());
+        assert!(status.store.raft.applied_index.is_none());
+    }
+
+    #[test]
+    fn unit_expvar_deserialize() {
+        let expvar = serde_json::from_str::<Expvar>(
+            r#"{
+                "cmdline": ["rqlited", "data"],
+                "http": {"requests": 10, "queries": 4, "executions": 6, "new_counter": 1},
+                "memstats": {"Alloc": 1024, "NumGC": 3, "PauseNs": [0, 1]},
+                "store": {"num_snapshots": 0}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(expvar.cmdline, vec!["rqlited", "data"]);
+        assert_eq!(expvar.http.requests, Some(10));
+        assert_eq!(expvar.http.queries, Some(4));
+        assert!(expvar.http.other.contains_key("new_counter"));
+        assert_eq!(expvar.memstats.alloc, Some(1024));
+        assert_eq!(expvar.memstats.num_gc, Some(3));
+        assert!(expvar.memstats.other.contains_key("PauseNs"));
+        assert!(expvar.other.contains_key("store"));
+    }
+
+    fn status(applied_index: u64, commit_index: u64) -> Status {
+        let mut status = Status::default();
+        status.store.raft.applied_index = Some(applied_index);
+        status.store.raft.commit_index = Some(commit_index);
+        status
+    }
+
+    fn node(id: &str, leader: bool) -> Node {
+        Node {
+            id: id.to_string(),
+            api_addr: format!("http://localhost:400{id}"),
+            raft_addr: String::new(),
+            voter: true,
+            reachable: true,
+            leader,
+            time: 0.0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn unit_replication_lag() {
+        let lag = ReplicationLag::new(vec![
+            (node("1", true), Ok(status(10, 10))),
+            (node("2", false), Ok(status(7, 12))),
+            (node("3", false), Err("down".to_string())),
+        ]);
+
+        // The commit index of the leader is used, even if a follower reports a higher one
+        assert_eq!(lag.commit_index, 10);
+        assert_eq!(lag.nodes[0].lag, Some(0));
+        assert_eq!(lag.nodes[1].lag, Some(3));
+        assert_eq!(lag.nodes[2].lag, None);
+        assert_eq!(lag.nodes[2].error.as_deref(), Some("down"));
+        assert_eq!(lag.max_lag(), Some(3));
+
+        let lag = ReplicationLag::new(vec![
+            (node("2", false), Ok(status(7, 12))),
+            (node("3", false), Ok(status(12, 11))),
+        ]);
+
+        // Without a leader, the highest commit index is used
+        assert_eq!(lag.commit_index, 12);
+        assert_eq!(lag.nodes[0].lag, Some(5));
+        assert_eq!(lag.nodes[1].lag, Some(0));
+    }
+}
@@ -6,7 +6,10 @@
     reason = "test code - panics are acceptable"
 )]
 
-use rqlite_rs::{config::Scheme, request::RqliteQueryParam, RqliteClient, RqliteClientBuilder};
+use rqlite_rs::{
+    config::Scheme, request::RqliteQueryParam, retry::ExponentialBackoff, RqliteClient,
+    RqliteClientBuilder,
+};
 
 pub fn get_client() -> RqliteClient {
     RqliteClientBuilder::default()
@@ -25,6 +28,29 @@ pub fn get_client_with_default_query_params() -> RqliteClient {
         .unwrap()
 }
 
+pub fn get_client_with_leader_routing() -> RqliteClient {
+    RqliteClientBuilder::default()
+        .known_host("localhost:4001")
+        .scheme(Scheme::Http)
+        .leader_routing(true)
+        .build()
+        .unwrap()
+}
+
+pub fn get_client_with_retry_policy() -> RqliteClient {
+    RqliteClientBuilder::default()
+        .known_host("localhost:4042")
+        .known_host("localhost:4001")
+        .scheme(Scheme::Http)
+        .retry_policy(
+            ExponentialBackoff::new()
+                .initial_delay(std::time::Duration::from_millis(10))
+                .max_retries(3),
+        )
+        .build()
+        .unwrap()
+}
+
 pub fn get_client_with_invalid_host() -> RqliteClient {
     RqliteClientBuilder::default()
         .known_host("localhost:4042")
@@ -42,6 +68,21 @@ pub async fn get_client_and_reset_db() -> RqliteClient {
     client
 }
 
+pub async fn get_client_and_reset_migrations() -> RqliteClient {
+    let client = get_client();
+
+    let _ = client
+        .exec("DROP TABLE IF EXISTS migrate_users")
+        .await
+        .unwrap();
+    let _ = client
+        .exec("DROP TABLE IF EXISTS _rqlite_rs_migrations")
+        .await
+        .unwrap();
+
+    client
+}
+
 // pub async fn get_client_with_auth() -> RqliteClient {
 //     RqliteClientBuilder::default()
 //         .known_host("localhost:4003")
@@ -0,0 +1,98 @@
+use rqlite_rs::{
+    backup::{BackupFormat, BackupOptions},
+    error::LoadError,
+    load::LoadOptions,
+};
+
+mod common;
+
+#[tokio::test]
+async fn integration_backup_sqlite() {
+    let client = common::get_client();
+
+    let mut backup = Vec::new();
+    let written = client
+        .backup(&mut backup, BackupOptions::new().vacuum(true))
+        .await
+        .unwrap();
+
+    assert_eq!(written, backup.len() as u64);
+    assert!(backup.starts_with(b"SQLite format 3\0"));
+}
+
+#[tokio::test]
+async fn integration_backup_sql_dump() {
+    let client = common::get_client();
+
+    let mut dump = Vec::new();
+    client
+        .backup(&mut dump, BackupOptions::new().format(BackupFormat::Sql))
+        .await
+        .unwrap();
+
+    let dump = String::from_utf8(dump).unwrap();
+    assert!(dump.contains("BEGIN TRANSACTION;"));
+}
+
+#[tokio::test]
+async fn integration_backup_compressed_to_file() {
+    let client = common::get_client();
+
+    let path = std::env::temp_dir().join("rqlite_rs_integration_backup.sqlite.gz");
+    let written = client
+        .backup_to_file(&path, BackupOptions::new().compress(true))
+        .await
+        .unwrap();
+
+    let backup = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(written, backup.len() as u64);
+    assert!(backup.starts_with(&[0x1f, 0x8b]));
+}
+
+#[tokio::test]
+async fn integration_load_sql_dump() {
+    let client = common::get_client();
+    let _ = client
+        .exec("DROP TABLE IF EXISTS load_users")
+        .await
+        .unwrap();
+
+    let path = std::env::temp_dir().join("rqlite_rs_integration_load.sql");
+    std::fs::write(
+        &path,
+        "CREATE TABLE load_users (id INTEGER PRIMARY KEY, name TEXT);\n\
+         INSERT INTO load_users (name) VALUES ('loaded');\n",
+    )
+    .unwrap();
+
+    let progress = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let options = LoadOptions::new().progress({
+        let progress = std::sync::Arc::clone(&progress);
+        move |p| progress.store(p.sent, std::sync::atomic::Ordering::SeqCst)
+    });
+
+    let result = client.load_sql_dump(&path, options).await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(result.is_success());
+    assert_eq!(
+        progress.load(std::sync::atomic::Ordering::SeqCst),
+        result.bytes()
+    );
+
+    let rows = client.fetch("SELECT * FROM load_users").await.unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[tokio::test]
+async fn integration_load_missing_file() {
+    let client = common::get_client();
+
+    let result = client
+        .load_sqlite_file("/does/not/exist.sqlite", LoadOptions::new())
+        .await;
+
+    assert!(matches!(result, Err(LoadError::IoError(_))));
+}
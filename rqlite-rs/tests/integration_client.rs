@@ -1,5 +1,12 @@
 #![warn(clippy::pedantic)]
-use rqlite_rs::{batch::BatchResult, prelude::*, response::RqliteResult};
+use std::num::NonZeroU16;
+
+use rqlite_rs::{
+    batch::{BatchResult, StatementOutcome},
+    prelude::*,
+    request::{RequestOpts, RqliteFreshnessLevel},
+    response::RqliteResult,
+};
 
 mod common;
 
@@ -42,6 +49,50 @@ async fn integration_leader() {
     assert!(leader.leader);
 }
 
+#[tokio::test]
+async fn integration_refresh_hosts() {
+    let client = common::get_client();
+
+    let hosts = client.refresh_hosts().await.unwrap();
+
+    // The node advertises its own address, the seed host is kept as a last resort
+    assert!(!hosts.is_empty());
+    assert_eq!(hosts.last().map(String::as_str), Some("localhost:4001"));
+}
+
+#[tokio::test]
+async fn integration_status() {
+    let client = common::get_client();
+
+    let status = client.status().await.unwrap();
+
+    assert!(status.build.version.is_some());
+    assert_eq!(status.store.raft.state.as_deref(), Some("Leader"));
+    assert!(status.store.raft.commit_index.is_some());
+    assert!(status.store.sqlite3.version.is_some());
+}
+
+#[tokio::test]
+async fn integration_expvar() {
+    let client = common::get_client();
+
+    let expvar = client.expvar().await.unwrap();
+
+    assert!(!expvar.cmdline.is_empty());
+    assert!(expvar.http.requests.is_some());
+}
+
+#[tokio::test]
+async fn integration_replication_lag() {
+    let client = common::get_client();
+
+    let lag = client.replication_lag().await.unwrap();
+
+    // The API address of the node may not be reachable from the host (because of docker)
+    assert_eq!(lag.nodes.len(), 1);
+    assert!(lag.nodes[0].leader);
+}
+
 #[tokio::test]
 async fn integration_exec() {
     let client = common::get_client_and_reset_db().await;
@@ -78,6 +129,32 @@ async fn integration_execute_batch() {
     assert!(insert_result.changed());
 }
 
+#[tokio::test]
+async fn integration_request() {
+    let client = common::get_client_and_reset_db().await;
+
+    let results = client
+        .request(vec![
+            "CREATE TABLE test (id INTEGER PRIMARY KEY, name TEXT)",
+            "SELECT * FROM test",
+            "INSERT INTO test (name) VALUES ('test')",
+            "SELECT * FROM test",
+            "SELECT * FROM missing",
+        ])
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 5);
+    assert!(matches!(&results[0], StatementOutcome::Exec(_)));
+    assert!(matches!(&results[1], StatementOutcome::Rows(rows) if rows.is_empty()));
+    assert!(matches!(&results[2], StatementOutcome::Exec(result) if result.changed()));
+    assert!(matches!(&results[3], StatementOutcome::Rows(rows) if rows.len() == 1));
+    assert!(matches!(
+        &results[4],
+        StatementOutcome::Error { index: 4, .. }
+    ));
+}
+
 #[tokio::test]
 async fn integration_fetch() {
     let client = common::get_client_and_reset_db().await;
@@ -96,6 +173,135 @@ async fn integration_fetch() {
     assert_eq!(rows[0].get::<String>("name").unwrap(), "test");
 }
 
+#[tokio::test]
+async fn integration_fetch_named_args() {
+    let client = common::get_client_and_reset_db().await;
+
+    let query = "CREATE TABLE test (id INTEGER PRIMARY KEY, name TEXT)";
+    let _ = client.exec(query).await.unwrap();
+
+    let query = rqlite_rs::query!(
+        "INSERT INTO test (id, name) VALUES (:id, :name)",
+        id = 7i64,
+        name = "named"
+    );
+    let _ = client.exec(query).await.unwrap();
+
+    let query = rqlite_rs::query!("SELECT * FROM test WHERE name = :name", name = "named");
+    let rows = client.fetch(query).await.unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<i32>("id").unwrap(), 7);
+}
+
+#[tokio::test]
+async fn integration_fetch_with_options() {
+    let client = common::get_client_and_reset_db().await;
+
+    let query = "CREATE TABLE test (id INTEGER PRIMARY KEY, name TEXT)";
+    let opts = RequestOpts::new().timeout(std::num::NonZeroU16::new(5).unwrap());
+    let _ = client.exec_with(query, opts).await.unwrap();
+
+    let opts = RequestOpts::new()
+        .level(RqliteFreshnessLevel::Strong)
+        .timings();
+    let rows = client.fetch_with("SELECT * FROM test", opts).await.unwrap();
+
+    assert!(rows.is_empty());
+}
+
+#[tokio::test]
+async fn integration_detailed() {
+    let client = common::get_client_and_reset_db().await;
+
+    let query = "CREATE TABLE test (id INTEGER PRIMARY KEY, name TEXT)";
+    let _ = client.exec(query).await.unwrap();
+
+    let response = client
+        .exec_detailed(
+            "INSERT INTO test (name) VALUES ('test')",
+            RequestOpts::new(),
+        )
+        .await
+        .unwrap();
+
+    assert!(response.result().changed());
+    assert!(response.time().is_some());
+    assert_eq!(response.statement_times().len(), 1);
+    assert_eq!(response.node(), "localhost:4001");
+
+    let response = client
+        .fetch_detailed("SELECT * FROM test", RequestOpts::new())
+        .await
+        .unwrap();
+
+    assert_eq!(response.result().len(), 1);
+    assert!(response.statement_times()[0].is_some());
+
+    let response = client
+        .batch_detailed(vec!["SELECT * FROM test", "SELECT 1"], RequestOpts::new())
+        .await
+        .unwrap();
+
+    assert_eq!(response.statement_times().len(), 2);
+}
+
+#[tokio::test]
+async fn integration_fetch_returning() {
+    let client = common::get_client_and_reset_db().await;
+
+    let query = "CREATE TABLE test (id INTEGER PRIMARY KEY, name TEXT)";
+    let _ = client.exec(query).await.unwrap();
+
+    let rows = client
+        .fetch("-- insert a row\nINSERT INTO test (name) VALUES ('returned') RETURNING id, name")
+        .await
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<String>("name").unwrap(), "returned");
+
+    let rows = client
+        .fetch("WITH ids AS (SELECT id FROM test) SELECT * FROM test WHERE id IN ids")
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[tokio::test]
+async fn integration_to_args_queries() {
+    #[derive(FromRow, ToArgs, Debug, PartialEq)]
+    struct User {
+        id: i64,
+        #[rqlite(rename = "display_name")]
+        name: String,
+        age: Option<u8>,
+    }
+
+    let client = common::get_client_and_reset_db().await;
+
+    let query = "CREATE TABLE users (id INTEGER PRIMARY KEY, display_name TEXT, age INTEGER)";
+    let _ = client.exec(query).await.unwrap();
+
+    let mut user = User {
+        id: 1,
+        name: "test".to_string(),
+        age: None,
+    };
+    let _ = client.exec(user.insert_query("users")).await.unwrap();
+
+    user.age = Some(30);
+    let _ = client.exec(user.update_query("users", "id")).await.unwrap();
+
+    user.name = "upserted".to_string();
+    let _ = client.exec(user.upsert_query("users", "id")).await.unwrap();
+
+    let rows = client.fetch("SELECT * FROM users").await.unwrap();
+    let users = rows.into_typed::<User>().unwrap();
+
+    assert_eq!(users, vec![user]);
+}
+
 #[tokio::test]
 #[warn(clippy::pedantic)]
 async fn integration_fetch_typed_struct_named() {
@@ -192,9 +398,41 @@ async fn integration_queue() {
         "INSERT INTO test (name) VALUES ('test')",
     ];
 
-    let result = client.queue(queries).await;
+    let sequence_number = client.queue(queries).await.unwrap();
 
-    assert!(result.is_ok());
+    assert!(sequence_number > 0);
+
+    client
+        .wait_for_sequence(sequence_number, NonZeroU16::new(5).unwrap())
+        .await
+        .unwrap();
+
+    let rows = client.fetch("SELECT * FROM test").await.unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[tokio::test]
+async fn integration_queue_and_wait() {
+    let client = common::get_client_and_reset_db().await;
+
+    let queries = vec![
+        "CREATE TABLE test (id INTEGER PRIMARY KEY, name TEXT)",
+        "INSERT INTO test (name) VALUES ('test')",
+    ];
+
+    let first = client
+        .queue_and_wait(queries, NonZeroU16::new(5).unwrap())
+        .await
+        .unwrap();
+
+    let rows = client.fetch("SELECT * FROM test").await.unwrap();
+    assert_eq!(rows.len(), 1);
+
+    let second = client
+        .queue(vec!["INSERT INTO test (name) VALUES ('test')"])
+        .await
+        .unwrap();
+    assert!(second > first);
 }
 
 #[tokio::test]
@@ -208,6 +446,34 @@ async fn integration_default_query_params() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn integration_leader_routing() {
+    let client = common::get_client_with_leader_routing();
+
+    let _ = client.exec("DROP TABLE IF EXISTS test").await.unwrap();
+    let _ = client
+        .exec("CREATE TABLE test (id INTEGER PRIMARY KEY, name TEXT)")
+        .await
+        .unwrap();
+
+    let result = client
+        .exec("INSERT INTO test (name) VALUES ('test')")
+        .await
+        .unwrap();
+    assert!(result.changed());
+
+    let rows = client.fetch("SELECT * FROM test").await.unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[tokio::test]
+async fn integration_retry_policy() {
+    let client = common::get_client_with_retry_policy();
+
+    let rows = client.fetch("SELECT 1").await.unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
 #[tokio::test]
 async fn integration_request_fail() {
     let client = common::get_client_with_invalid_host();
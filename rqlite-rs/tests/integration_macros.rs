@@ -70,3 +70,217 @@ fn integration_derive_from_row_unnamed() {
     assert_eq!(test.0, 1);
     assert_eq!(test.1, "test");
 }
+
+fn create_row(columns: &[(&str, &str)], values: Vec<SqlValue>) -> Row {
+    let columns = columns
+        .iter()
+        .enumerate()
+        .map(|(i, (name, type_data))| Column::new((*name).to_string(), i, (*type_data).to_string()))
+        .collect::<Vec<_>>();
+
+    let column_names = columns
+        .iter()
+        .map(|c| (c.name().to_string(), c.ordinal()))
+        .collect::<HashMap<String, usize>>();
+
+    Row::from_values(
+        &Arc::new(columns),
+        &Arc::new(column_names),
+        values.into_boxed_slice(),
+    )
+}
+
+#[test]
+fn integration_derive_from_row_attributes() {
+    fn decode_tags(value: &SqlValue) -> Result<Vec<String>, rqlite_rs::IntoTypedError> {
+        let tags = value
+            .as_str()
+            .ok_or_else(|| rqlite_rs::IntoTypedError::UnexpectedType("TEXT", value.type_name()))?;
+
+        Ok(tags.split(',').map(ToString::to_string).collect())
+    }
+
+    #[derive(FromRow)]
+    #[rqlite(rename_all = "camelCase")]
+    struct Account {
+        user_id: i64,
+        #[rqlite(rename = "display_name")]
+        name: String,
+        #[rqlite(try_from = "i64")]
+        age: u8,
+        #[rqlite(default)]
+        score: f64,
+        #[rqlite(skip)]
+        cached: Option<String>,
+        #[rqlite(with = decode_tags)]
+        tags: Vec<String>,
+    }
+
+    let row = create_row(
+        &[
+            ("userId", "integer"),
+            ("display_name", "text"),
+            ("age", "integer"),
+            ("score", "real"),
+            ("tags", "text"),
+        ],
+        vec![
+            SqlValue::Integer(1),
+            SqlValue::Text("test".to_string()),
+            SqlValue::Integer(42),
+            SqlValue::Null,
+            SqlValue::Text("a,b".to_string()),
+        ],
+    );
+
+    let account = Account::from_row(row).unwrap();
+
+    assert_eq!(account.user_id, 1);
+    assert_eq!(account.name, "test");
+    assert_eq!(account.age, 42);
+    assert!(account.score.abs() < f64::EPSILON);
+    assert_eq!(account.cached, None);
+    assert_eq!(account.tags, vec!["a", "b"]);
+}
+
+#[test]
+fn integration_derive_from_row_try_from_error() {
+    #[derive(FromRow)]
+    struct Test {
+        #[rqlite(try_from = "i64")]
+        #[expect(dead_code, reason = "field only used for FromRow derive test")]
+        age: u8,
+    }
+
+    let row = create_row(&[("age", "integer")], vec![SqlValue::Integer(300)]);
+
+    assert!(matches!(
+        Test::from_row(row),
+        Err(rqlite_rs::IntoTypedError::TryFromError(column, _)) if column == "age"
+    ));
+}
+
+#[test]
+fn integration_derive_from_row_unnamed_attributes() {
+    #[derive(FromRow)]
+    struct Test(
+        #[rqlite(try_from = "i64")] u16,
+        #[rqlite(default)] String,
+        #[rqlite(skip)] Vec<String>,
+    );
+
+    let row = create_row(
+        &[("id", "integer"), ("name", "text")],
+        vec![SqlValue::Integer(7), SqlValue::Null],
+    );
+
+    let test = Test::from_row(row).unwrap();
+
+    assert_eq!(test.0, 7);
+    assert_eq!(test.1, "");
+    assert!(test.2.is_empty());
+}
+
+#[test]
+fn integration_derive_from_row_decode() {
+    use rqlite_rs::{decode::Decode, IntoTypedError};
+
+    #[derive(Debug, PartialEq)]
+    struct UserId(i64);
+
+    impl Decode<'_> for UserId {
+        fn decode(value: &SqlValue) -> Result<Self, IntoTypedError> {
+            i64::decode(value).map(Self)
+        }
+    }
+
+    #[derive(FromRow)]
+    struct Test {
+        id: UserId,
+        active: bool,
+        #[rqlite(with = rqlite_rs::decode::deserialize)]
+        scores: Vec<i64>,
+    }
+
+    let row = create_row(
+        &[("id", "integer"), ("active", "boolean"), ("scores", "text")],
+        vec![
+            SqlValue::Integer(7),
+            SqlValue::Integer(1),
+            SqlValue::Text("[1, 2]".to_string()),
+        ],
+    );
+
+    let test = Test::from_row(row).unwrap();
+
+    assert_eq!(test.id, UserId(7));
+    assert!(test.active);
+    assert_eq!(test.scores, vec![1, 2]);
+}
+
+#[test]
+fn integration_derive_to_args() {
+    use rqlite_rs::query::arguments::RqliteArgument;
+
+    #[derive(ToArgs)]
+    #[rqlite(rename_all = "camelCase")]
+    struct Test {
+        user_id: i64,
+        #[rqlite(rename = "display_name")]
+        name: String,
+        age: Option<u8>,
+        #[rqlite(skip)]
+        #[expect(dead_code, reason = "field only used for ToArgs derive test")]
+        cached: Vec<String>,
+    }
+
+    let test = Test {
+        user_id: 1,
+        name: "test".to_string(),
+        age: Some(30),
+        cached: vec![],
+    };
+
+    assert_eq!(Test::columns(), ["userId", "display_name", "age"]);
+    assert_eq!(
+        test.to_args(),
+        vec![
+            RqliteArgument::I64(1),
+            RqliteArgument::String("test".to_string()),
+            RqliteArgument::I64(30),
+        ]
+    );
+
+    let query = test.insert_query("users").unwrap();
+    assert_eq!(
+        query.query,
+        r#"INSERT INTO "users" ("userId", "display_name", "age") VALUES (?, ?, ?)"#
+    );
+}
+
+#[test]
+#[cfg(feature = "query-checked")]
+fn integration_query_checked() {
+    use rqlite_rs::query::{arguments::RqliteArgument, RqliteArguments};
+
+    let query = rqlite_rs::query_checked!(
+        schema = "tests/schema.sql",
+        "SELECT name FROM users WHERE id = ?",
+        1
+    )
+    .unwrap();
+    assert_eq!(query.query, "SELECT name FROM users WHERE id = ?");
+    assert_eq!(
+        query.args,
+        RqliteArguments::Positional(vec![RqliteArgument::I64(1)])
+    );
+
+    let query = rqlite_rs::query_checked!(
+        schema = "tests/schema.sql",
+        "INSERT INTO users (name, email) VALUES (:name, :email)",
+        name = "test",
+        email = "test@example.com",
+    )
+    .unwrap();
+    assert!(query.validate().is_ok());
+}
@@ -0,0 +1,70 @@
+use rqlite_rs::{
+    error::MigrateError,
+    migrate::{Migration, Migrator},
+};
+
+mod common;
+
+#[tokio::test]
+async fn integration_migrate_run_and_undo() {
+    let client = common::get_client_and_reset_migrations().await;
+
+    let migrator = rqlite_rs::migrate!("./tests/migrations");
+
+    let report = migrator.run(&client).await.unwrap();
+    assert_eq!(report.migrations.len(), 2);
+
+    let rows = client
+        .fetch("SELECT name FROM migrate_users")
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<String>("name").unwrap(), "admin; default");
+
+    // Running again is a no-op
+    let report = migrator.run(&client).await.unwrap();
+    assert!(report.is_empty());
+
+    let report = migrator.undo(&client, 0).await.unwrap();
+    assert_eq!(report.migrations.len(), 2);
+    assert_eq!(report.migrations[0].version, 2);
+
+    assert!(migrator.applied(&client).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn integration_migrate_dry_run() {
+    let client = common::get_client_and_reset_migrations().await;
+
+    let migrator = rqlite_rs::migrate!("./tests/migrations").dry_run(true);
+
+    let report = migrator.run(&client).await.unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.migrations.len(), 2);
+
+    assert!(migrator.applied(&client).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn integration_migrate_checksum_mismatch() {
+    let client = common::get_client_and_reset_migrations().await;
+
+    Migrator::new(vec![Migration::new(
+        1,
+        "create migrate users",
+        "CREATE TABLE migrate_users (id INTEGER PRIMARY KEY)",
+    )])
+    .run(&client)
+    .await
+    .unwrap();
+
+    let result = Migrator::new(vec![Migration::new(
+        1,
+        "create migrate users",
+        "CREATE TABLE migrate_users (id INTEGER PRIMARY KEY, name TEXT)",
+    )])
+    .run(&client)
+    .await;
+
+    assert!(matches!(result, Err(MigrateError::ChecksumMismatch(1))));
+}
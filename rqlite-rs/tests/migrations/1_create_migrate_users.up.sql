@@ -0,0 +1 @@
+CREATE TABLE migrate_users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
@@ -0,0 +1,2 @@
+DELETE FROM migrate_users;
+DROP INDEX migrate_users_name;
@@ -0,0 +1,3 @@
+-- Seed a default user
+CREATE INDEX migrate_users_name ON migrate_users (name);
+INSERT INTO migrate_users (name) VALUES ('admin; default');